@@ -0,0 +1,126 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Audit Events
+//!
+//! This module packages the structured events of the audit stream. Audit logging has
+//! strict field requirements, and [`crate::audit!`] emits an INFO event to the
+//! [`AUDIT_TARGET`] with the four required fields, so that none of them can be
+//! forgotten:
+//! - `actor`: who performed the action
+//! - `action`: what was done
+//! - `resource`: what it was done to
+//! - `outcome`: how it ended, e.g. `success` or `denied`
+//!
+//! The fields are recorded with their `Display` implementation. With `LOG_AUDIT_OUTPUT`
+//! set, the events feed the dedicated audit stream, provided `LOG_AUDIT_TARGET` keeps
+//! its default value.
+
+/// Target of the events emitted by [`crate::audit!`], the default audit target.
+pub const AUDIT_TARGET: &str = "audit";
+
+/// Emits an audit event with the required `actor`, `action`, `resource` and `outcome`
+/// fields.
+///
+/// The required fields come first, in that order, followed by optional extra fields
+/// and a message like the tracing macros. The event is emitted at INFO to the
+/// [`AUDIT_TARGET`](crate::audit::AUDIT_TARGET), with `audit event` as the default
+/// message.
+///
+/// # Examples
+///
+/// ```
+/// let user = String::from("alice");
+/// logging::audit!(
+///     actor = user,
+///     action = "export",
+///     resource = "report/7",
+///     outcome = "denied",
+///     reason = "missing role",
+///     "export of {} refused",
+///     "report/7"
+/// );
+/// ```
+///
+/// A call missing a required field doesn't compile:
+///
+/// ```compile_fail
+/// logging::audit!(actor = "alice", action = "delete", resource = "invoice/42");
+/// ```
+///
+/// ```compile_fail
+/// logging::audit!(action = "delete", resource = "invoice/42", outcome = "success");
+/// ```
+#[macro_export]
+macro_rules! audit {
+    (
+        actor = $actor:expr,
+        action = $action:expr,
+        resource = $resource:expr,
+        outcome = $outcome:expr $(,)?
+    ) => {
+        $crate::audit!(
+            actor = $actor,
+            action = $action,
+            resource = $resource,
+            outcome = $outcome,
+            "audit event"
+        )
+    };
+    (
+        actor = $actor:expr,
+        action = $action:expr,
+        resource = $resource:expr,
+        outcome = $outcome:expr,
+        $($arg:tt)+
+    ) => {
+        $crate::__tracing::info!(
+            target: $crate::audit::AUDIT_TARGET,
+            actor = %$actor,
+            action = %$action,
+            resource = %$resource,
+            outcome = %$outcome,
+            $($arg)+
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::Capture;
+
+    #[test]
+    fn audit_events_carry_the_required_fields_first() {
+        let console = Capture::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(console.clone())
+            .finish();
+
+        let user = String::from("alice");
+        tracing::subscriber::with_default(subscriber, || {
+            crate::audit!(
+                actor = user,
+                action = "delete",
+                resource = "invoice/42",
+                outcome = "success",
+            );
+            crate::audit!(
+                actor = user,
+                action = "export",
+                resource = "report/7",
+                outcome = "denied",
+                reason = "missing role",
+                "export of {} refused",
+                "report/7"
+            );
+        });
+
+        assert!(console.contains(
+            "INFO audit: audit event actor=alice action=delete resource=invoice/42 outcome=success"
+        ));
+        assert!(console.contains("export of report/7 refused"));
+        assert!(console.contains(r#"outcome=denied reason="missing role""#));
+    }
+}
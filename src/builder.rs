@@ -0,0 +1,569 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Logging Builder
+//!
+//! This module provides [`LoggingBuilder`], the programmatic entry point for settings
+//! that can't be expressed as environment variables or in a [`LoggingConfig`] document,
+//! such as a custom console event format or destination, or a resource built by the
+//! caller.
+
+use crate::{
+    config::{LoggingConfig, StructuredFormat},
+    errors::LoggingError,
+    exporters::{layers::ConsoleOptions, writer::SharedWriter},
+};
+use opentelemetry_sdk::{Resource, logs::SdkLoggerProvider};
+use tracing_subscriber::fmt::MakeWriter;
+
+pub use crate::exporters::layers::CustomFormat;
+
+/// Builds and installs the logging system.
+///
+/// The builder starts from the same settings as [`crate::provider::install`] or
+/// [`crate::provider::install_from_config`], and installation follows the same rules,
+/// including idempotency.
+///
+/// # Examples
+///
+/// A minimal custom formatter prefixing each line with the level:
+///
+/// ```no_run
+/// use logging::builder::LoggingBuilder;
+/// use std::fmt;
+/// use tracing::{Event, Subscriber};
+/// use tracing_subscriber::{
+///     fmt::{FmtContext, FormatEvent, FormatFields, format::Writer},
+///     registry::LookupSpan,
+/// };
+///
+/// struct LevelPrefixed;
+///
+/// impl<S, N> FormatEvent<S, N> for LevelPrefixed
+/// where
+///     S: Subscriber + for<'a> LookupSpan<'a>,
+///     N: for<'a> FormatFields<'a> + 'static,
+/// {
+///     fn format_event(
+///         &self,
+///         ctx: &FmtContext<'_, S, N>,
+///         mut writer: Writer<'_>,
+///         event: &Event<'_>,
+///     ) -> fmt::Result {
+///         write!(writer, "[{}] ", event.metadata().level())?;
+///         ctx.field_format().format_fields(writer.by_ref(), event)?;
+///         writeln!(writer)
+///     }
+/// }
+///
+/// fn main() {
+///     let provider = LoggingBuilder::new()
+///         .with_custom_format(Box::new(LevelPrefixed))
+///         .install()
+///         .expect("Failed to initialize logging");
+///
+///     tracing::info!("Application started");
+/// }
+/// ```
+#[derive(Default)]
+pub struct LoggingBuilder {
+    cfg: LoggingConfig,
+    console: ConsoleOptions,
+    resource: Option<Resource>,
+}
+
+impl LoggingBuilder {
+    /// Creates a builder with the settings read from the environment.
+    pub fn new() -> Self {
+        Self::from_config(LoggingConfig::new())
+    }
+
+    /// Creates a builder with the settings of a [`LoggingConfig`] document.
+    pub fn from_config(cfg: LoggingConfig) -> Self {
+        LoggingBuilder {
+            cfg,
+            console: ConsoleOptions::default(),
+            resource: None,
+        }
+    }
+
+    /// Formats the base console layer with a custom [`FormatEvent`] implementation.
+    ///
+    /// The custom format replaces the built-in `LOG_BASE_FORMAT` presets and metadata
+    /// toggles of the base console layer (stderr). The pretty/JSON stdout layer is kept,
+    /// and the browser console of the `wasm` exporter is not affected.
+    ///
+    /// [`FormatEvent`]: tracing_subscriber::fmt::FormatEvent
+    pub fn with_custom_format(mut self, format: CustomFormat) -> Self {
+        self.console.custom_format = Some(format);
+        self
+    }
+
+    /// Writes the console output to a custom [`MakeWriter`] instead of stdout and stderr.
+    ///
+    /// Every console layer writes to the given destination, without colors, e.g. an
+    /// in-memory buffer in tests or a ring buffer in an embedding application. The
+    /// audit stream, the JSON file and the exporters are not affected.
+    pub fn with_console_writer<M>(mut self, make_writer: M) -> Self
+    where
+        M: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+    {
+        self.console.writer = Some(SharedWriter::new(make_writer));
+        self
+    }
+
+    /// Writes the console output meant for stdout to a custom [`MakeWriter`].
+    ///
+    /// Only the layers writing to stdout, the structured one by default, are affected;
+    /// see `LoggingConfigs::structured_stream`. Their output isn't colored, and
+    /// [`Self::with_console_writer`] takes precedence.
+    pub fn with_stdout_writer<M>(mut self, make_writer: M) -> Self
+    where
+        M: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+    {
+        self.console.stdout_writer = Some(SharedWriter::new(make_writer));
+        self
+    }
+
+    /// Writes the console output meant for stderr to a custom [`MakeWriter`].
+    ///
+    /// Only the layers writing to stderr, the base one and the problems only stream by
+    /// default, are affected; see `LoggingConfigs::base_fmt_stream`. Their output isn't
+    /// colored, and [`Self::with_console_writer`] takes precedence.
+    pub fn with_stderr_writer<M>(mut self, make_writer: M) -> Self
+    where
+        M: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+    {
+        self.console.stderr_writer = Some(SharedWriter::new(make_writer));
+        self
+    }
+
+    /// Forces the console output to be colored with ANSI escape codes, or not.
+    ///
+    /// By default, colors follow the render mode of the environment, and are turned off
+    /// for the destinations of [`Self::with_console_writer`] and
+    /// [`Self::with_warn_writer`]. This setting overrides both, e.g. to keep a file free
+    /// of escape codes locally, or to color a custom terminal writer.
+    pub fn with_ansi(mut self, enabled: bool) -> Self {
+        self.console.ansi = Some(enabled);
+        self
+    }
+
+    /// Sets the format of the structured console layer, e.g. from a command-line flag.
+    ///
+    /// The format wins over `LOG_STRUCTURED_FORMAT`, the `structured_format` of the
+    /// config and the default of the environment, see
+    /// [`crate::config::resolve_format`].
+    pub fn with_structured_format(mut self, format: StructuredFormat) -> Self {
+        self.console.structured_format = Some(format);
+        self
+    }
+
+    /// Writes a copy of the WARN and ERROR events to a custom [`MakeWriter`].
+    ///
+    /// This enables the problems only console stream, `LOG_WARN_STREAM`, with the given
+    /// destination instead of stderr, so operators can follow the problems of a busy
+    /// service apart from the full output, which is not affected.
+    pub fn with_warn_writer<M>(mut self, make_writer: M) -> Self
+    where
+        M: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+    {
+        self.console.warn_writer = Some(SharedWriter::new(make_writer));
+        self
+    }
+
+    /// Sets whether records of the `log` crate facade are captured, as
+    /// `LOG_CAPTURE_LOG_CRATE` does.
+    ///
+    /// Capturing is on by default. Turning it off leaves the global `log` logger free for
+    /// the application.
+    ///
+    /// # Examples
+    ///
+    /// Without capture, the application sets its own `log` logger:
+    ///
+    /// ```
+    /// use logging::{
+    ///     builder::LoggingBuilder,
+    ///     config::{ExporterKind, LoggingConfig},
+    /// };
+    /// use tracing_log::log::{self, Log, Metadata, Record};
+    ///
+    /// struct AppLogger;
+    ///
+    /// impl Log for AppLogger {
+    ///     fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+    ///         true
+    ///     }
+    ///
+    ///     fn log(&self, record: &Record<'_>) {
+    ///         println!("{}", record.args());
+    ///     }
+    ///
+    ///     fn flush(&self) {}
+    /// }
+    ///
+    /// let cfg = LoggingConfig {
+    ///     exporter: Some(ExporterKind::Noop),
+    ///     ..LoggingConfig::default()
+    /// };
+    ///
+    /// LoggingBuilder::from_config(cfg)
+    ///     .capture_log_crate(false)
+    ///     .install()
+    ///     .expect("Failed to initialize logging");
+    ///
+    /// assert!(log::set_logger(&AppLogger).is_ok());
+    /// ```
+    pub fn capture_log_crate(mut self, capture: bool) -> Self {
+        self.cfg.logging.capture_log_crate = capture;
+        self
+    }
+
+    /// Attaches a resource built by the caller to the exported records.
+    ///
+    /// The resource is used verbatim by the exporters sending records to an external
+    /// system, in place of the one built from the `AppConfigs`, e.g. when a shared
+    /// telemetry setup already assembles the resource with its detectors. Nothing is
+    /// added to it: the service name, `service.instance.id` and additional resource
+    /// attributes are up to the caller. Console output and other exporters are not
+    /// affected.
+    #[cfg_attr(
+        all(feature = "otlp", not(target_arch = "wasm32")),
+        doc = r#"
+# Examples
+
+```
+use logging::{
+    builder::LoggingBuilder,
+    config::{ExporterKind, LoggingConfig, LoggingConfigs, OtlpCompression},
+};
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::Resource;
+use std::{
+    io::{ErrorKind, Read},
+    net::TcpListener,
+    thread,
+    time::{Duration, Instant},
+};
+
+// A collector recording what it receives, without answering
+let collector = TcpListener::bind("127.0.0.1:0").unwrap();
+collector.set_nonblocking(true).unwrap();
+
+let runtime = tokio::runtime::Runtime::new().unwrap();
+let _runtime = runtime.enter();
+let cfg = LoggingConfig {
+    exporter: Some(ExporterKind::Otlp),
+    level: Some("info".to_string()),
+    otlp_endpoint: Some(format!("http://{}", collector.local_addr().unwrap())),
+    otlp_timeout: Some(Duration::from_millis(500)),
+    logging: LoggingConfigs {
+        // Keeps the attributes readable on the wire
+        otlp_compression: OtlpCompression::Uncompressed,
+        console_output: false,
+        startup_banner: false,
+        ..LoggingConfigs::default()
+    },
+    ..LoggingConfig::default()
+};
+let resource = Resource::builder_empty()
+    .with_service_name("checkout")
+    .with_attribute(KeyValue::new("deployment.cell", "eu-cell-7"))
+    .build();
+
+let provider = LoggingBuilder::from_config(cfg)
+    .with_resource(resource)
+    .install()
+    .expect("Failed to initialize logging");
+tracing::info!("order placed");
+thread::spawn(move || provider.force_flush());
+
+let deadline = Instant::now() + Duration::from_secs(5);
+let mut received = Vec::new();
+let mut connection = None;
+while Instant::now() < deadline
+    && !String::from_utf8_lossy(&received).contains("eu-cell-7")
+{
+    let stream = match &mut connection {
+        Some(stream) => stream,
+        None => match collector.accept() {
+            Ok((stream, _)) => connection.insert(stream),
+            Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(10));
+                continue;
+            }
+            Err(err) => panic!("{err}"),
+        },
+    };
+    stream.set_read_timeout(Some(Duration::from_millis(100))).unwrap();
+    let mut chunk = [0; 4096];
+    match stream.read(&mut chunk) {
+        Ok(read) => received.extend_from_slice(&chunk[..read]),
+        Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+        Err(err) => panic!("{err}"),
+    }
+}
+
+let received = String::from_utf8_lossy(&received);
+assert!(received.contains("eu-cell-7"));
+assert!(received.contains("checkout"));
+assert!(received.contains("order placed"));
+```
+"#
+    )]
+    pub fn with_resource(mut self, resource: Resource) -> Self {
+        self.resource = Some(resource);
+        self
+    }
+
+    /// Installs the logging system.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`crate::provider::install_from_config`].
+    pub fn install(self) -> Result<SdkLoggerProvider, LoggingError> {
+        crate::provider::install_once(self.cfg, self.console, self.resource)
+    }
+
+    /// Returns the settings of the builder, for [`crate::test::init_from`].
+    #[cfg(any(test, feature = "test-util"))]
+    pub(crate) fn into_parts(self) -> (LoggingConfig, ConsoleOptions, Option<Resource>) {
+        (self.cfg, self.console, self.resource)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::{ConsoleStream, ExporterKind, LoggingConfigs, RenderMode, StructuredFormat},
+        test::Capture,
+    };
+
+    fn builder(level: &str) -> LoggingBuilder {
+        LoggingBuilder::from_config(LoggingConfig {
+            exporter: Some(ExporterKind::Noop),
+            level: Some(level.to_string()),
+            ..LoggingConfig::default()
+        })
+    }
+
+    #[test]
+    fn console_writer_receives_every_console_layer() {
+        let console = Capture::default();
+        let logs = crate::test::init_from(builder("info").with_console_writer(console.clone()));
+
+        tracing::info!(user_id = "123", "User logged in");
+
+        assert!(console.contains("User logged in"));
+        assert!(console.contains("user_id"));
+        assert!(logs.output().is_empty());
+    }
+
+    #[test]
+    fn warn_writer_receives_a_copy_of_the_problems() {
+        let problems = Capture::default();
+        let logs = crate::test::init_from(builder("info").with_warn_writer(problems.clone()));
+
+        tracing::info!("request served");
+        tracing::warn!("slow request");
+
+        assert!(logs.contains("request served"));
+        assert!(!problems.contains("request served"));
+        assert!(logs.contains("slow request"));
+        assert!(problems.contains("slow request"));
+    }
+
+    #[test]
+    fn captured_log_records_reach_the_console() {
+        let logs = crate::test::init_from(builder("info").capture_log_crate(true));
+
+        tracing_log::log::info!("legacy dependency started");
+
+        assert!(logs.contains("legacy dependency started"));
+    }
+
+    #[test]
+    fn ansi_colors_can_be_forced_on_a_custom_writer() {
+        let mut builder = builder("info").with_ansi(true);
+        // Never colored on its own
+        builder.cfg.logging.render_mode = Some(RenderMode::Machine);
+        let logs = crate::test::init_from(builder);
+
+        tracing::info!("colored");
+
+        assert!(logs.contains("\x1b["));
+    }
+
+    #[test]
+    fn console_streams_can_be_swapped() {
+        let (stdout, stderr) = (Capture::default(), Capture::default());
+        let builder = LoggingBuilder::from_config(LoggingConfig {
+            exporter: Some(ExporterKind::Noop),
+            level: Some("info".to_string()),
+            logging: LoggingConfigs {
+                structured_format: Some(StructuredFormat::Ndjson),
+                // The opposite of the defaults
+                base_fmt_stream: ConsoleStream::Stdout,
+                structured_stream: ConsoleStream::Stderr,
+                ..LoggingConfigs::default()
+            },
+            ..LoggingConfig::default()
+        });
+        let _logs = crate::test::init_from(
+            builder
+                .with_stdout_writer(stdout.clone())
+                .with_stderr_writer(stderr.clone()),
+        );
+
+        tracing::info!("request served");
+
+        let (stdout, stderr) = (stdout.output(), stderr.output());
+        let (stdout, stderr): (Vec<_>, Vec<_>) =
+            (stdout.lines().collect(), stderr.lines().collect());
+        assert_eq!(stdout.len(), 1);
+        assert!(stdout[0].contains("INFO") && !stdout[0].starts_with('{'));
+        assert_eq!(stderr.len(), 1);
+        assert!(stderr[0].starts_with('{') && stderr[0].contains("request served"));
+    }
+
+    #[test]
+    fn level_colors_replace_the_built_in_palette() {
+        let console = Capture::default();
+        let builder = LoggingBuilder::from_config(LoggingConfig {
+            exporter: Some(ExporterKind::Noop),
+            level: Some("trace".to_string()),
+            logging: LoggingConfigs {
+                level_colors: vec![
+                    "trace=white".to_string(),
+                    "debug=cyan".to_string(),
+                    "info=bright-blue".to_string(),
+                    "warn=blue".to_string(),
+                    "error=38;5;208".to_string(),
+                ],
+                ..LoggingConfigs::default()
+            },
+            ..LoggingConfig::default()
+        });
+        let _logs =
+            crate::test::init_from(builder.with_console_writer(console.clone()).with_ansi(true));
+
+        tracing::trace!("tracing");
+        tracing::debug!("debugging");
+        tracing::info!("informing");
+        tracing::warn!("warning");
+        tracing::error!("failing");
+
+        let output = console.output();
+        for token in [
+            "\x1b[37mTRACE\x1b[0m",
+            "\x1b[36mDEBUG\x1b[0m",
+            "\x1b[94m INFO\x1b[0m",
+            "\x1b[34m WARN\x1b[0m",
+            "\x1b[38;5;208mERROR\x1b[0m",
+        ] {
+            assert!(output.contains(token), "{token:?} missing from {output:?}");
+        }
+        // The built-in palette is gone
+        assert!(!output.contains("\x1b[33m WARN"));
+        assert!(!output.contains("\x1b[31mERROR"));
+    }
+
+    #[test]
+    fn structured_format_wins_over_the_config() {
+        let console = Capture::default();
+        let builder = LoggingBuilder::from_config(LoggingConfig {
+            exporter: Some(ExporterKind::Noop),
+            level: Some("info".to_string()),
+            logging: LoggingConfigs {
+                structured_format: Some(StructuredFormat::Pretty),
+                ..LoggingConfigs::default()
+            },
+            ..LoggingConfig::default()
+        });
+        let _logs = crate::test::init_from(
+            builder
+                .with_console_writer(console.clone())
+                .with_structured_format(StructuredFormat::Ndjson),
+        );
+
+        tracing::info!(order_id = 42, "order charged");
+
+        let record: serde_json::Value = console
+            .output()
+            .lines()
+            .find_map(|line| serde_json::from_str(line).ok())
+            .expect("an NDJSON record");
+        assert_eq!(record["level"], "INFO");
+        assert_eq!(record["order_id"], 42);
+    }
+
+    #[test]
+    fn dual_format_writes_pretty_then_marked_json() {
+        let stdout = Capture::default();
+        let builder = LoggingBuilder::from_config(LoggingConfig {
+            exporter: Some(ExporterKind::Noop),
+            level: Some("info".to_string()),
+            logging: LoggingConfigs {
+                structured_format: Some(StructuredFormat::Dual),
+                ..LoggingConfigs::default()
+            },
+            ..LoggingConfig::default()
+        });
+        let _logs =
+            crate::test::init_from(builder.with_stdout_writer(stdout.clone()).with_ansi(false));
+
+        tracing::info!(order_id = 42, "order charged");
+
+        let output = stdout.output();
+        // The pretty representation comes first
+        let (pretty, machine) = output.split_once("@json@").expect("a marked line");
+        assert!(pretty.contains("order charged"));
+        assert!(pretty.contains("order_id: 42"));
+
+        let line = machine.lines().next().unwrap();
+        let record: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert_eq!(record["message"], "order charged");
+        assert_eq!(record["order_id"], 42);
+        assert_eq!(output.matches("@json@").count(), 1);
+    }
+
+    struct LevelPrefixed;
+
+    impl<S, N> tracing_subscriber::fmt::FormatEvent<S, N> for LevelPrefixed
+    where
+        S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+        N: for<'a> tracing_subscriber::fmt::FormatFields<'a> + 'static,
+    {
+        fn format_event(
+            &self,
+            ctx: &tracing_subscriber::fmt::FmtContext<'_, S, N>,
+            mut writer: tracing_subscriber::fmt::format::Writer<'_>,
+            event: &tracing::Event<'_>,
+        ) -> std::fmt::Result {
+            write!(writer, "[{}] ", event.metadata().level())?;
+            ctx.field_format().format_fields(writer.by_ref(), event)?;
+            writeln!(writer)
+        }
+    }
+
+    #[test]
+    fn custom_format_replaces_the_base_format() {
+        let stderr = Capture::default();
+        let _logs = crate::test::init_from(
+            builder("info")
+                .with_custom_format(Box::new(LevelPrefixed))
+                .with_stderr_writer(stderr.clone()),
+        );
+
+        tracing::info!(order_id = 42, "order charged");
+        tracing::warn!("slow request");
+
+        assert_eq!(
+            stderr.output(),
+            "[INFO] order charged order_id=42\n[WARN] slow request\n"
+        );
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Error Chains
+//!
+//! This module records errors as structured fields instead of a single flattened string.
+//! Logging `error = ?err` only keeps the outermost message, or the debug dump of the
+//! whole error, while root-cause analysis needs every error of the source chain.
+//!
+//! [`crate::record_error!`] logs an ERROR event with three fields:
+//! - `error.message`: the message of the error itself
+//! - `error.chain`: the messages of the error and each of its sources, outermost first,
+//!   encoded as a JSON array
+//! - `error.backtrace`: the backtrace of the logging site, only when capturing is
+//!   enabled with `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE`
+//!
+//! Any type implementing [`std::error::Error`] is accepted, and so is an
+//! `anyhow::Error` through its `AsRef<dyn Error>` implementation, e.g.
+//! `record_error!(err.as_ref())`.
+
+use std::{
+    backtrace::{Backtrace, BacktraceStatus},
+    error::Error,
+};
+
+/// Returns the messages of an error and of each of its sources, outermost first.
+///
+/// # Examples
+///
+/// ```
+/// use std::{error::Error, fmt};
+///
+/// #[derive(Debug)]
+/// struct Wrapped(&'static str, Option<Box<Wrapped>>);
+///
+/// impl fmt::Display for Wrapped {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str(self.0)
+///     }
+/// }
+///
+/// impl Error for Wrapped {
+///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+///         self.1.as_deref().map(|source| source as _)
+///     }
+/// }
+///
+/// let err = Wrapped(
+///     "failed to load the user",
+///     Some(Box::new(Wrapped(
+///         "query failed",
+///         Some(Box::new(Wrapped("connection refused", None))),
+///     ))),
+/// );
+///
+/// assert_eq!(
+///     logging::chain::messages(&err),
+///     vec!["failed to load the user", "query failed", "connection refused"]
+/// );
+/// ```
+pub fn messages(err: &(dyn Error + 'static)) -> Vec<String> {
+    std::iter::successors(Some(err), |&err| err.source())
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Returns the chain of an error as a JSON array of messages, for the `error.chain`
+/// field.
+#[doc(hidden)]
+pub fn chain_field(err: &(dyn Error + 'static)) -> String {
+    serde_json::Value::from(messages(err)).to_string()
+}
+
+/// Captures the backtrace of the caller for the `error.backtrace` field, if enabled.
+#[doc(hidden)]
+pub fn backtrace_field() -> Option<String> {
+    let backtrace = Backtrace::capture();
+    (backtrace.status() == BacktraceStatus::Captured).then(|| backtrace.to_string())
+}
+
+/// Logs an ERROR event carrying an error and its source chain as structured fields.
+///
+/// The error is given by reference, e.g. `&err`, or `err.as_ref()` for an
+/// `anyhow::Error`. The event gets the target of the calling module, and an optional
+/// message in the format of the tracing macros. See [`crate::chain`] for the recorded
+/// fields.
+///
+/// # Examples
+///
+/// ```
+/// use std::{error::Error, fmt, io};
+///
+/// #[derive(Debug)]
+/// struct QueryError(io::Error);
+///
+/// impl fmt::Display for QueryError {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str("query failed")
+///     }
+/// }
+///
+/// impl Error for QueryError {
+///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+///         Some(&self.0)
+///     }
+/// }
+///
+/// let err = QueryError(io::Error::new(
+///     io::ErrorKind::ConnectionRefused,
+///     "connection refused",
+/// ));
+/// logging::record_error!(&err, "failed to load user {}", 42);
+/// ```
+#[macro_export]
+macro_rules! record_error {
+    ($err:expr) => {
+        $crate::record_error!($err, "error")
+    };
+    ($err:expr, $($arg:tt)+) => {{
+        let err: &(dyn ::std::error::Error + 'static) = $err;
+        $crate::__tracing::error!(
+            error.message = %err,
+            error.chain = %$crate::chain::chain_field(err),
+            error.backtrace = $crate::chain::backtrace_field(),
+            $($arg)+
+        )
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::Capture;
+    use std::{fmt, io};
+
+    #[derive(Debug)]
+    struct QueryError(io::Error);
+
+    impl fmt::Display for QueryError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("query failed")
+        }
+    }
+
+    impl Error for QueryError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn records_the_message_and_the_chain() {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(capture.clone())
+            .finish();
+
+        let err = QueryError(io::Error::new(
+            io::ErrorKind::ConnectionRefused,
+            "connection refused",
+        ));
+        tracing::subscriber::with_default(subscriber, || {
+            crate::record_error!(&err, "failed to load user {}", 42);
+        });
+
+        assert!(capture.contains("failed to load user 42"));
+        assert!(capture.contains("error.message=query failed"));
+        assert!(capture.contains(r#"error.chain=["query failed","connection refused"]"#));
+    }
+}
@@ -0,0 +1,2095 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Logging Configuration
+//!
+//! This module defines the logging-specific settings that are not covered by the
+//! `configs` crate's `AppConfigs` and `OTLPConfigs`. Like those, the settings are read
+//! from environment variables when constructed with [`LoggingConfigs::new`].
+//!
+//! [`LoggingConfig`] gathers every knob, including the exporter choice, into a single
+//! document that can be deserialized from a TOML/YAML/JSON file and passed to
+//! [`crate::provider::install_from_config`].
+
+use crate::{errors::LoggingError, exporters::envs, minimal::FilterPreset};
+use serde::{Deserialize, Deserializer, de};
+use std::{
+    collections::BTreeMap, convert::Infallible, fmt, path::PathBuf, str::FromStr, time::Duration,
+};
+use tracing::{Level, level_filters::LevelFilter};
+
+/// The complete logging setup, as a declarative document.
+///
+/// Every field is optional. Fields left out of the document fall back to the settings
+/// of the `configs` crate (`level`, `otlp_endpoint`, `otlp_timeout`) or to the defaults
+/// of [`LoggingConfigs`], which are not read from the environment here.
+///
+/// # Examples
+///
+/// ```
+/// use logging::config::{BaseFormat, ExporterKind, LoggingConfig};
+///
+/// let cfg: LoggingConfig = serde_json::from_str(
+///     r#"{
+///         "exporter": "otlp",
+///         "level": "debug",
+///         "otlp_endpoint": "http://collector:4317",
+///         "base_format": "full",
+///         "field_exclude": ["http.status<500"],
+///         "resource_attributes": { "deployment.region": "eu-west-1" },
+///         "otlp_batch": { "scheduled_delay": 500 }
+///     }"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(cfg.exporter, Some(ExporterKind::Otlp));
+/// assert_eq!(cfg.logging.base_format, BaseFormat::Full);
+/// assert_eq!(cfg.logging.otlp_batch.max_queue_size, 2_048);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Exporter to install. When unset, the exporter is chosen from the enabled
+    /// features and the environment [`crate::profile::Profile`], like
+    /// [`crate::provider::install`] does.
+    pub exporter: Option<ExporterKind>,
+
+    /// Log level overriding `AppConfigs::log_level`.
+    ///
+    /// When neither is set, the level of the environment [`crate::profile::Profile`]
+    /// applies.
+    pub level: Option<String>,
+
+    /// Collector endpoint overriding `OTLPConfigs::endpoint`.
+    pub otlp_endpoint: Option<String>,
+
+    /// Exporter timeout overriding `OTLPConfigs::exporter_timeout`, in milliseconds.
+    #[serde(deserialize_with = "optional_millis")]
+    pub otlp_timeout: Option<Duration>,
+
+    /// Outputs records are sent to simultaneously, replacing the single `exporter`.
+    ///
+    /// Each output has its own level, on top of the configured filters. When empty, the
+    /// single exporter setup is used.
+    pub outputs: Vec<OutputConfig>,
+
+    /// The remaining logging settings, inlined in the document.
+    #[serde(flatten)]
+    pub logging: LoggingConfigs,
+}
+
+impl LoggingConfig {
+    /// Creates a configuration equivalent to the environment-driven setup.
+    ///
+    /// The exporter is read from `LOG_EXPORTER` (`otlp`, `stdout`, `noop`, `wasm`,
+    /// `journald` or `gelf`), no other override is set and the logging settings are read with
+    /// [`LoggingConfigs::new`].
+    pub fn new() -> Self {
+        Self {
+            exporter: envs::parse(envs::LOG_EXPORTER_ENV_KEY),
+            logging: LoggingConfigs::new(),
+            ..Self::default()
+        }
+    }
+}
+
+/// The exporters that can be selected by a [`LoggingConfig`].
+///
+/// Selecting an exporter whose feature isn't enabled is reported as
+/// `LoggingError::InvalidFeaturesError` at installation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExporterKind {
+    /// OTLP gRPC export (`otlp` feature, not available on `wasm32`).
+    Otlp,
+    /// OpenTelemetry stdout export (`stdout` feature).
+    Stdout,
+    /// Console output only (`sdk` feature).
+    Noop,
+    /// Browser console output (`wasm` feature, `wasm32` targets only).
+    Wasm,
+    /// Systemd journal output (`journald` feature, Linux only).
+    Journald,
+    /// GELF output to Graylog (`gelf` feature, not available on `wasm32`).
+    Gelf,
+}
+
+impl ExporterKind {
+    /// Returns the error reported when this exporter is selected without its feature.
+    #[cfg(feature = "sdk")]
+    pub(crate) fn unavailable(self) -> LoggingError {
+        LoggingError::InvalidFeaturesError(
+            match self {
+                ExporterKind::Otlp => "otlp requires the `otlp` feature and a non-wasm32 target",
+                ExporterKind::Stdout => "stdout requires the `stdout` feature",
+                ExporterKind::Noop => "noop requires the `noop` feature",
+                ExporterKind::Wasm => "wasm requires the `wasm` feature and a wasm32 target",
+                ExporterKind::Journald => "journald requires the `journald` feature and Linux",
+                ExporterKind::Gelf => "gelf requires the `gelf` feature and a non-wasm32 target",
+            }
+            .to_string(),
+        )
+    }
+}
+
+impl ExporterKind {
+    /// Returns `true` if this exporter's feature is enabled and supports the target.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logging::config::ExporterKind;
+    ///
+    /// # #[cfg(not(target_arch = "wasm32"))]
+    /// assert!(!ExporterKind::Wasm.is_available());
+    /// ```
+    pub fn is_available(self) -> bool {
+        match self {
+            ExporterKind::Otlp => cfg!(all(feature = "otlp", not(target_arch = "wasm32"))),
+            ExporterKind::Stdout => cfg!(feature = "stdout"),
+            ExporterKind::Noop => cfg!(feature = "sdk"),
+            ExporterKind::Wasm => cfg!(all(feature = "wasm", target_arch = "wasm32")),
+            ExporterKind::Journald => cfg!(all(feature = "journald", target_os = "linux")),
+            ExporterKind::Gelf => cfg!(all(feature = "gelf", not(target_arch = "wasm32"))),
+        }
+    }
+}
+
+impl fmt::Display for ExporterKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ExporterKind::Otlp => "otlp",
+            ExporterKind::Stdout => "stdout",
+            ExporterKind::Noop => "noop",
+            ExporterKind::Wasm => "wasm",
+            ExporterKind::Journald => "journald",
+            ExporterKind::Gelf => "gelf",
+        })
+    }
+}
+
+impl FromStr for ExporterKind {
+    type Err = LoggingError;
+
+    fn from_str(exporter: &str) -> Result<Self, Self::Err> {
+        match exporter.to_lowercase().as_str() {
+            "otlp" => Ok(ExporterKind::Otlp),
+            "stdout" => Ok(ExporterKind::Stdout),
+            "noop" => Ok(ExporterKind::Noop),
+            "wasm" => Ok(ExporterKind::Wasm),
+            "journald" => Ok(ExporterKind::Journald),
+            "gelf" => Ok(ExporterKind::Gelf),
+            _ => Err(LoggingError::InvalidConfigError(format!(
+                "unknown exporter `{exporter}`, expected otlp, stdout, noop, wasm, journald or gelf"
+            ))),
+        }
+    }
+}
+
+/// An output of a fan-out setup, selected by its `kind`.
+///
+/// # Examples
+///
+/// ```
+/// use logging::config::{LoggingConfig, OutputConfig};
+///
+/// let cfg: LoggingConfig = serde_json::from_str(
+///     r#"{
+///         "outputs": [
+///             { "kind": "otlp", "level": "info" },
+///             { "kind": "file", "path": "/var/log/my-service.log", "level": "debug" }
+///         ]
+///     }"#,
+/// )
+/// .unwrap();
+///
+/// assert!(matches!(
+///     &cfg.outputs[0],
+///     OutputConfig::Otlp { level: Some(level), endpoint: None, .. } if level == "info"
+/// ));
+/// ```
+#[cfg_attr(
+    all(feature = "otlp", not(target_arch = "wasm32")),
+    doc = r#"
+Sending every record to two independent collectors:
+
+```
+use logging::{
+    config::{LoggingConfig, LoggingConfigs, OutputConfig},
+    provider,
+};
+use std::{
+    io::ErrorKind,
+    net::TcpListener,
+    thread,
+    time::{Duration, Instant},
+};
+
+// Two collectors, which only accept connections
+let vendor = TcpListener::bind("127.0.0.1:0").unwrap();
+let internal = TcpListener::bind("127.0.0.1:0").unwrap();
+vendor.set_nonblocking(true).unwrap();
+internal.set_nonblocking(true).unwrap();
+let output = |listener: &TcpListener| OutputConfig::Otlp {
+    level: None,
+    below: None,
+    endpoint: Some(format!("http://{}", listener.local_addr().unwrap())),
+    timeout: Some(Duration::from_millis(500)),
+    compression: None,
+    tls: None,
+};
+
+let runtime = tokio::runtime::Runtime::new().unwrap();
+let _runtime = runtime.enter();
+let cfg = LoggingConfig {
+    level: Some("info".to_string()),
+    outputs: vec![output(&vendor), output(&internal)],
+    logging: LoggingConfigs {
+        console_output: false,
+        startup_banner: false,
+        ..LoggingConfigs::default()
+    },
+    ..LoggingConfig::default()
+};
+let provider = provider::install_from_config(cfg).expect("Failed to initialize logging");
+
+tracing::info!("exported to both collectors");
+let _ = provider.force_flush();
+
+for collector in [&vendor, &internal] {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        match collector.accept() {
+            Ok(_) => break,
+            Err(err) if err.kind() == ErrorKind::WouldBlock && Instant::now() < deadline => {
+                thread::sleep(Duration::from_millis(10))
+            }
+            Err(err) => panic!("a collector was not reached: {err}"),
+        }
+    }
+}
+```
+
+Routing errors to a paging collector, while the other levels stay in a local file:
+
+```
+use logging::{
+    config::{LoggingConfig, LoggingConfigs, OutputConfig, StructuredFormat},
+    provider,
+};
+use std::{
+    io::ErrorKind,
+    net::TcpListener,
+    thread,
+    time::{Duration, Instant},
+};
+
+let paging = TcpListener::bind("127.0.0.1:0").unwrap();
+paging.set_nonblocking(true).unwrap();
+let path = std::env::temp_dir().join(format!("routing-{}.log", std::process::id()));
+
+let runtime = tokio::runtime::Runtime::new().unwrap();
+let _runtime = runtime.enter();
+let cfg = LoggingConfig {
+    level: Some("debug".to_string()),
+    outputs: vec![
+        OutputConfig::Otlp {
+            level: Some("error".to_string()),
+            below: None,
+            endpoint: Some(format!("http://{}", paging.local_addr().unwrap())),
+            timeout: Some(Duration::from_millis(500)),
+            compression: None,
+            tls: None,
+        },
+        OutputConfig::File {
+            path: path.clone(),
+            level: Some("debug".to_string()),
+            below: Some("error".to_string()),
+            format: Some(StructuredFormat::Ndjson),
+        },
+    ],
+    logging: LoggingConfigs {
+        console_output: false,
+        startup_banner: false,
+        ..LoggingConfigs::default()
+    },
+    ..LoggingConfig::default()
+};
+let provider = provider::install_from_config(cfg).expect("Failed to initialize logging");
+let reached = |within: Duration| {
+    let deadline = Instant::now() + within;
+    loop {
+        match paging.accept() {
+            Ok(_) => return true,
+            Err(err) if err.kind() == ErrorKind::WouldBlock && Instant::now() < deadline => {
+                thread::sleep(Duration::from_millis(10))
+            }
+            Err(_) => return false,
+        }
+    }
+};
+
+// An INFO only reaches the local file
+tracing::info!("cache warmed");
+let _ = provider.force_flush();
+assert!(!reached(Duration::from_millis(300)));
+
+// An ERROR only reaches the paging collector
+tracing::error!("payment provider unreachable");
+let _ = provider.force_flush();
+assert!(reached(Duration::from_secs(5)));
+
+let local = std::fs::read_to_string(&path).unwrap();
+assert!(local.contains("cache warmed"));
+assert!(!local.contains("payment provider unreachable"));
+# std::fs::remove_file(path).unwrap();
+```
+"#
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum OutputConfig {
+    /// OTLP gRPC export (`otlp` feature, not available on `wasm32`).
+    ///
+    /// Several OTLP outputs with their own endpoints each receive every record, e.g. a
+    /// vendor backend and an internal collector. Connections to an `https` endpoint use
+    /// TLS.
+    Otlp {
+        /// Most verbose level exported, defaults to every level that passes the filters.
+        level: Option<String>,
+        /// Level from which records are no longer exported, e.g. `error` to leave errors to
+        /// another output. Defaults to no upper bound.
+        #[serde(default)]
+        below: Option<String>,
+        /// Collector endpoint, defaults to the configured one, with its failover
+        /// endpoints.
+        #[serde(default)]
+        endpoint: Option<String>,
+        /// Export timeout in milliseconds, defaults to the configured one.
+        #[serde(default, deserialize_with = "optional_millis")]
+        timeout: Option<Duration>,
+        /// Compression of the requests, defaults to `LoggingConfigs::otlp_compression`.
+        #[serde(default)]
+        compression: Option<OtlpCompression>,
+        /// TLS settings of an `https` endpoint, defaults to `LoggingConfigs::otlp_tls`.
+        #[serde(default)]
+        tls: Option<TlsConfigs>,
+    },
+    /// OpenTelemetry stdout export (`stdout` feature).
+    Stdout {
+        /// Most verbose level exported, defaults to every level that passes the filters.
+        level: Option<String>,
+        /// Level from which records are no longer exported, e.g. `error` to leave errors to
+        /// another output. Defaults to no upper bound.
+        #[serde(default)]
+        below: Option<String>,
+    },
+    /// A file opened in append mode, created if missing.
+    File {
+        /// Path of the file.
+        path: PathBuf,
+        /// Most verbose level written, defaults to every level that passes the filters.
+        level: Option<String>,
+        /// Level from which records are no longer written, e.g. `error` to leave errors to
+        /// another output. Defaults to no upper bound.
+        #[serde(default)]
+        below: Option<String>,
+        /// Format of the written records, defaults to JSON.
+        format: Option<StructuredFormat>,
+    },
+}
+
+/// Logging settings read from the environment.
+///
+/// Every setting defaults to the crate's historical behavior when its environment
+/// variable is unset, so an empty environment produces the same pipeline as before
+/// these settings existed.
+///
+/// When deserialized, missing settings take their default value and durations are
+/// expressed in milliseconds.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfigs {
+    /// Field conditions of which at least one must match for an event to be kept.
+    ///
+    /// Read from `LOG_FIELD_INCLUDE` as a comma-separated list, e.g. `audit=true`.
+    /// An empty list keeps every event.
+    pub field_include: Vec<String>,
+
+    /// Field conditions that cause a matching event to be dropped.
+    ///
+    /// Read from `LOG_FIELD_EXCLUDE` as a comma-separated list, e.g. `http.status<500`.
+    pub field_exclude: Vec<String>,
+
+    /// Regular expressions of the messages that cause an event to be dropped.
+    ///
+    /// Read from `LOG_MESSAGE_DENYLIST` as a semicolon-separated list, since patterns
+    /// may contain commas, e.g. `^connection reset;retry \d+/3`. Matching events are
+    /// dropped whatever their level and target. An invalid pattern fails the
+    /// installation. Empty by default.
+    pub message_denylist: Vec<String>,
+
+    /// Targets allowed to log, switching target filtering to allowlist mode.
+    ///
+    /// Read from `LOG_TARGETS_ALLOWLIST` as a comma-separated list of `target` or
+    /// `target=level` entries. When empty, the default denylist filtering is used.
+    pub target_allowlist: Vec<String>,
+
+    /// Presets of the dependencies whose verbosity is capped at WARN.
+    ///
+    /// Read from `LOG_FILTER_PRESETS` as a comma-separated list of `aws`, `messaging`,
+    /// `web` and `telemetry`, e.g. `aws,messaging` for a service without HTTP. Unknown
+    /// names are ignored, so `none` keeps every dependency at the configured level.
+    /// Defaults to every preset. Unused with a target allowlist.
+    pub filter_presets: Vec<FilterPreset>,
+
+    /// Targets that never log, whatever the level.
+    ///
+    /// Read from `LOG_SILENCED_TARGETS` as a comma-separated list of targets, e.g. a
+    /// dependency that logs secrets at WARN. The targets and their submodules are set
+    /// to `OFF` after every other target filter, and scoped levels don't relax them.
+    pub silenced_targets: Vec<String>,
+
+    /// Fraction of the events kept for each sampled target, keyed by target.
+    ///
+    /// Read from `LOG_TARGET_SAMPLE_RATES` as a comma-separated list of `target=rate`
+    /// entries, e.g. `my_app::poller=0.01` to keep one event out of a hundred from a
+    /// polling loop. A target covers its submodules, and the other targets are not
+    /// sampled. Empty by default.
+    pub target_sample_rates: BTreeMap<String, String>,
+
+    /// Level of the internal logs of the OpenTelemetry SDK, the OTLP exporter and tonic.
+    ///
+    /// Read from `LOG_OTEL_INTERNAL_LEVEL` (e.g. `off`, `error`, `warn`). Defaults to
+    /// `warn`. When the collector is unreachable, the SDK reports each failed export at
+    /// WARN level; those events go through the same subscriber and can flood the console,
+    /// so `off` or `error` is advisable for unstable networks. Not
+    /// applied in allowlist mode, where these targets are only enabled when allowlisted.
+    #[serde(deserialize_with = "level_filter")]
+    pub otel_internal_level: LevelFilter,
+
+    /// Whether records of the `log` crate facade are captured into the pipeline.
+    ///
+    /// Read from `LOG_CAPTURE_LOG_CRATE`. Defaults to `true`: a `LogTracer` is set as the
+    /// global `log` logger, so dependencies logging through `log` reach every output.
+    /// Applications setting up their own `log` logger, e.g. with `env_logger::init()`,
+    /// turn it off to keep theirs.
+    pub capture_log_crate: bool,
+
+    /// Whether events are written to the console (stderr and stdout).
+    ///
+    /// Read from `LOG_CONSOLE_OUTPUT`. Defaults to `true`. Exporting to OpenTelemetry is
+    /// independent of this setting, so disabling it with the OTLP exporter gives an
+    /// export-only setup, e.g. for containers whose stdout is collected separately.
+    ///
+    /// Every console layer is left out: the base layer, the structured and bunyan
+    /// layers, and the problems only stream. Nothing is written to the console, not even
+    /// the startup banner.
+    pub console_output: bool,
+
+    /// What installing does when the OTLP exporter can't be built.
+    ///
+    /// Read from `LOG_ON_EXPORTER_FAILURE` (`fail` or `fallback-to-console`). Defaults to
+    /// `fail`: installing returns the error, so compliance workloads refuse to run
+    /// without central export. With `fallback-to-console`, logging is installed without
+    /// an exporter, writing to the console only, and a WARN event reports the error.
+    pub on_exporter_failure: FailPolicy,
+
+    /// Whether the console layers write one event at a time.
+    ///
+    /// Read from `LOG_CONSOLE_SERIALIZED`. The base, structured and WARN layers then
+    /// share a single lock, so their lines never interleave, even under concurrency,
+    /// at the cost of throughput. Meant for local debugging. Defaults to `false`.
+    pub console_serialized: bool,
+
+    /// Size, in bytes, of the buffers gathering the console writes, if any.
+    ///
+    /// Read from `LOG_CONSOLE_BUFFER`. Each console destination writes its buffered
+    /// lines at once when the buffer is full and every `console_flush_interval`, which
+    /// saves a system call per event for high-volume output. Lines still buffered are
+    /// written by [`crate::provider::flush_console`] and [`crate::provider::shutdown`].
+    /// Disabled by default.
+    pub console_buffer: Option<usize>,
+
+    /// Interval between two flushes of the console write buffers.
+    ///
+    /// Read from `LOG_CONSOLE_FLUSH_INTERVAL`, in milliseconds. Only used with
+    /// `console_buffer`; it bounds how late a line shows up. Defaults to one second.
+    #[serde(deserialize_with = "millis")]
+    pub console_flush_interval: Duration,
+
+    /// Whether WARN and ERROR events are also written to a stream of their own.
+    ///
+    /// Read from `LOG_WARN_STREAM`. The extra stream goes to stderr in the compact base
+    /// format, next to the full console output, so operators can tail just the problems.
+    /// [`crate::builder::LoggingBuilder::with_warn_writer`] sends it elsewhere. Defaults
+    /// to `false`.
+    pub warn_stream: bool,
+
+    /// Number of console lines withheld until an error, if any.
+    ///
+    /// Read from `LOG_ERROR_BUFFER`. The console stays silent, holding its most recent
+    /// lines, until an event at or above `error_buffer_trigger` is logged: the held
+    /// lines are then written, followed by that event, and buffering resumes. Meant for
+    /// CLI tools that only print logs to explain a failure. Disabled by default.
+    pub error_buffer: Option<usize>,
+
+    /// Level of the events flushing the error buffer.
+    ///
+    /// Read from `LOG_ERROR_BUFFER_TRIGGER` as a level name. Defaults to `error`.
+    #[serde(deserialize_with = "level")]
+    pub error_buffer_trigger: Level,
+
+    /// Level of the console output, when it differs from the subscriber level.
+    ///
+    /// Read from `LOG_CONSOLE_LEVEL` as a level name, e.g. `warn`. The console gets the
+    /// configured target filters with this level as default, so it can be quieter than
+    /// the export while `LOG_LEVEL` stays the most verbose level any output receives.
+    /// Follows `LOG_LEVEL` by default.
+    #[serde(deserialize_with = "optional_level")]
+    pub console_level: Option<Level>,
+
+    /// Level of the events handed to the OpenTelemetry export bridge, when it differs
+    /// from the subscriber level.
+    ///
+    /// Read from `LOG_EXPORT_LEVEL` as a level name, e.g. `warn`, to export only WARN
+    /// and ERROR records while the console shows everything down to `LOG_LEVEL`. Like
+    /// `console_level`, it can only narrow `LOG_LEVEL`. Follows `LOG_LEVEL` by default.
+    #[serde(deserialize_with = "optional_level")]
+    pub export_level: Option<Level>,
+
+    /// Whether exported events follow the sampling decision of their trace.
+    ///
+    /// Read from `LOG_FOLLOW_TRACE_SAMPLING`. When enabled, events of an unsampled trace
+    /// are not exported and events of a sampled trace are, so the exported logs match
+    /// the exported traces. Events outside any trace only go through the level filters.
+    /// The console is not affected. Defaults to `false`.
+    pub follow_trace_sampling: bool,
+
+    /// Format of the base console layer, writing to stderr by default.
+    ///
+    /// Read from `LOG_BASE_FORMAT` (`compact`, `full` or `pretty`). Defaults to `compact`.
+    /// This is independent of the pretty/JSON switch of the structured stdout layer.
+    pub base_format: BaseFormat,
+
+    /// Format of the structured console layer, writing to stdout by default.
+    ///
+    /// Read from `LOG_STRUCTURED_FORMAT` (`pretty`, `json`, `ndjson`, `pretty-json` or
+    /// `dual`).
+    /// When unset, the format of the environment [`crate::profile::Profile`] applies:
+    /// pretty locally, JSON elsewhere. See [`resolve_format`] for the precedence of the
+    /// sources.
+    pub structured_format: Option<StructuredFormat>,
+
+    /// Stream the base console layer writes to.
+    ///
+    /// Read from `LOG_BASE_FMT_STREAM` (`stdout` or `stderr`). Defaults to `stderr`.
+    pub base_fmt_stream: ConsoleStream,
+
+    /// Stream the structured console layer writes to.
+    ///
+    /// Read from `LOG_STRUCTURED_STREAM` (`stdout` or `stderr`). Defaults to `stdout`.
+    /// Setting both streams to `stdout` keeps every console line on a single stream.
+    pub structured_stream: ConsoleStream,
+
+    /// How the console output is rendered: colors and default structured format.
+    ///
+    /// Read from `LOG_RENDER_MODE` (`interactive`, `plain`, `machine` or `piped`). When
+    /// unset, the mode of the environment [`crate::profile::Profile`] applies:
+    /// interactive locally, machine elsewhere, subject to `tty_detection`. `plain` suits
+    /// CI, with pretty output and no colors.
+    pub render_mode: Option<RenderMode>,
+
+    /// Whether the interactive mode of the environment falls back to the piped one when
+    /// the structured console stream isn't a terminal.
+    ///
+    /// Read from `LOG_TTY_DETECTION`. Defaults to `true`, so a local service piped into a
+    /// file or CI logs writes plain JSON lines instead of colored pretty output. A
+    /// configured `render_mode` is never changed.
+    pub tty_detection: bool,
+
+    /// Per-level overrides of the structured console format.
+    ///
+    /// Read from `LOG_STRUCTURED_LEVEL_FORMATS` as a comma-separated list of
+    /// `level=format` entries, e.g. `error=pretty` to make errors stand out in an
+    /// otherwise JSON console. Levels without an entry use `structured_format`.
+    pub structured_level_formats: Vec<String>,
+
+    /// Per-level colors of the level token in the colored console output.
+    ///
+    /// Read from `LOG_LEVEL_COLORS` as a comma-separated list of `level=color` entries,
+    /// e.g. `warn=blue,error=magenta` for a palette red/green colorblind readers can tell
+    /// apart. A color is a name, such as `cyan` or `bright-red`, or raw SGR parameters
+    /// such as `38;5;208`; see [`crate::exporters::colors::LevelColors`]. Levels without an
+    /// entry keep the built-in palette. Applies to the base console layer, whatever its
+    /// format, and to the pretty structured layer.
+    pub level_colors: Vec<String>,
+
+    /// File every event is also written to as JSON, in Bunyan format, if any.
+    ///
+    /// Read from `LOG_JSON_FILE`. The file is opened in append mode and created if
+    /// missing. It's independent of the console formats, so a local setup can keep the
+    /// pretty console and still get a file to query with `jq`. Works with every exporter.
+    pub json_file: Option<PathBuf>,
+
+    /// Metadata included by the base console layer.
+    pub base_metadata: BaseMetadataConfigs,
+
+    /// Whether JSON output nests dotted field names into objects.
+    ///
+    /// Read from `LOG_JSON_NESTED_FIELDS`. When enabled, `http.method` and `http.status`
+    /// are emitted as `"http": {"method": .., "status": ..}`. Colliding names are kept
+    /// flat, see [`crate::exporters::json::JsonWriter::with_nested_fields`]. Defaults to
+    /// `false`.
+    pub json_nested_fields: bool,
+
+    /// Names given to the top-level fields of the JSON output, keyed by emitted name.
+    ///
+    /// Read from `LOG_JSON_FIELD_NAMES` as a comma-separated list of `field=name`
+    /// entries, e.g. `time=@timestamp,level=log.level,msg=message` to match a pipeline
+    /// expecting another schema than Bunyan's. Unmapped fields keep their name. Empty by
+    /// default.
+    pub json_field_names: BTreeMap<String, String>,
+
+    /// Standard Bunyan fields left out of the JSON output.
+    ///
+    /// Read from `LOG_JSON_OMITTED_FIELDS` as a comma-separated list, e.g. `pid,hostname`
+    /// in containers, whose resource attributes already identify the instance. Only `v`,
+    /// `pid`, `hostname`, `name`, `level`, `time`, `target`, `line` and `file` can be
+    /// omitted. Empty by default, so every standard field is written.
+    pub json_omitted_fields: Vec<String>,
+
+    /// Prefix of the JSON lines of the [`StructuredFormat::Dual`] format.
+    ///
+    /// Read from `LOG_DUAL_MARKER`. Defaults to `@json@`, so the machine lines read
+    /// `@json@{"timestamp":..}`.
+    pub dual_marker: String,
+
+    /// Maximum length of the event message, in bytes.
+    ///
+    /// Read from `LOG_MAX_MESSAGE_LENGTH`. Longer messages are clipped on a character
+    /// boundary and end with `…(truncated)`, before reaching the console and the
+    /// exporter. Unlimited by default.
+    pub max_message_length: Option<usize>,
+
+    /// Maximum length of the other event field values, in bytes.
+    ///
+    /// Read from `LOG_MAX_FIELD_LENGTH`, and applied like `max_message_length` to string
+    /// and debug-formatted values, e.g. to keep a serialized payload logged by mistake
+    /// under the per-record size limit of the collector. Unlimited by default.
+    pub max_field_length: Option<usize>,
+
+    /// Process and runtime fields added to every event.
+    ///
+    /// Read from `LOG_PROCESS_FIELDS` as a comma-separated list of `pid`, `thread.id`,
+    /// `thread.name`, `process.runtime` and `process.uptime_ms`, the milliseconds since
+    /// logging was installed. Every event is rebuilt to carry them, so it's empty, and
+    /// free, by default.
+    pub process_fields: Vec<String>,
+
+    /// Whether the events of a trace carry its context as a `traceparent` field.
+    ///
+    /// Read from `LOG_TRACEPARENT`. The field follows the W3C Trace Context format,
+    /// `00-{trace_id}-{span_id}-{flags}`, for backends that correlate logs and traces on
+    /// that string. The trace is the one of the current span when spans are bridged to
+    /// OpenTelemetry, or of the current OpenTelemetry context; events outside any trace
+    /// don't get the field. Requires the `sdk` feature. Defaults to `false`.
+    pub traceparent: bool,
+
+    /// Whether the events carry their target split into `crate` and `module` fields.
+    ///
+    /// Read from `LOG_TARGET_FIELDS`. The `crate` field is the first segment of the
+    /// target and `module` the rest, e.g. `my_app` and `db::pool` for `my_app::db::pool`,
+    /// so backends can group by crate without parsing targets in queries. A target
+    /// without `::` is only a crate, and its events get no `module`. Defaults to `false`.
+    pub target_fields: bool,
+
+    /// Level of the summary event logged when a span closes, if any.
+    ///
+    /// Read from `LOG_SPAN_TIMING` as a level name, e.g. `debug`. The summary reports the
+    /// span name with its total, busy and idle durations. Disabled by default.
+    #[serde(deserialize_with = "optional_level")]
+    pub span_timing: Option<Level>,
+
+    /// Number of recent log lines kept in memory, if any.
+    ///
+    /// Read from `LOG_RECENT_LOGS`. The lines are formatted as plain text after the
+    /// configured filters and read with [`crate::provider::recent_logs`], e.g. to serve
+    /// them from a debug endpoint. Disabled by default.
+    pub recent_logs: Option<usize>,
+
+    /// Whether the events are counted by level and target.
+    ///
+    /// Read from `LOG_VOLUME_COUNTERS`. The counts are read with
+    /// [`crate::provider::log_volume`], e.g. to alert on a spike of errors without
+    /// exporting the logs. Defaults to `false`.
+    pub volume_counters: bool,
+
+    /// Whether the sizes of the events are measured into a histogram.
+    ///
+    /// Read from `LOG_EVENT_SIZE_HISTOGRAM`. The buckets are read with
+    /// [`crate::provider::size_histogram`], e.g. to alert on oversized logs before they
+    /// break the collector. Defaults to `false`.
+    pub event_size_histogram: bool,
+
+    /// Upper bounds of the buckets of the event size histogram, in bytes.
+    ///
+    /// Read from `LOG_EVENT_SIZE_BUCKETS` as a comma-separated list, e.g. `1024,65536`;
+    /// entries that aren't numbers are ignored. Defaults to 256 B, 1 KiB, 4 KiB, 16 KiB
+    /// and 64 KiB. Larger events are counted in a last, unbounded bucket.
+    pub event_size_buckets: Vec<usize>,
+
+    /// Interval between two heartbeat events, if any.
+    ///
+    /// Read from `LOG_HEARTBEAT_INTERVAL`, in milliseconds. A background thread emits a
+    /// `heartbeat` event with the uptime of the logging system through the usual
+    /// pipeline, for liveness monitoring without a health check. The thread stops with
+    /// [`crate::provider::shutdown`]. Disabled by default.
+    #[serde(deserialize_with = "optional_millis")]
+    pub heartbeat_interval: Option<Duration>,
+
+    /// Level of the heartbeat events.
+    ///
+    /// Read from `LOG_HEARTBEAT_LEVEL` as a level name. The heartbeats go through the
+    /// usual filters, so the level must be enabled for the `logging` target. Defaults to
+    /// INFO.
+    #[serde(deserialize_with = "level")]
+    pub heartbeat_level: Level,
+
+    /// Whether an event summarizing the configuration is logged once installed.
+    ///
+    /// Read from `LOG_STARTUP_BANNER`. The INFO event goes through the installed pipeline
+    /// and carries the selected exporter, the level, the console format, the OTLP
+    /// endpoint when exporting to OTLP and the additional resource attributes, so the
+    /// active setup can be checked from the logs themselves. Defaults to `true`.
+    pub startup_banner: bool,
+
+    /// Whether the resolved configuration is logged at DEBUG once installed.
+    ///
+    /// Read from `LOG_RESOLVED_CONFIG`. The `resolved logging configuration` event
+    /// carries the application and OTLP settings the crate resolved, and every logging
+    /// setting, for diagnosing environment-specific behavior. Credentials of URLs and
+    /// secret-looking resource attributes are redacted, see
+    /// [`crate::exporters::redact`]. Defaults to `false`.
+    pub resolved_config: bool,
+
+    /// Name of the event field holding the time the event happened.
+    ///
+    /// Read from `LOG_TIMESTAMP_FIELD`, defaults to `ts`. Records exported to OTLP get
+    /// their timestamp from this field, as an RFC 3339 string or milliseconds since the
+    /// Unix epoch, and from the time they were recorded otherwise, which is always their
+    /// observed timestamp. This keeps replayed or batched events in order. The JSON
+    /// console and file records take their `time`, or `timestamp`, from it too.
+    pub timestamp_field: String,
+
+    /// Settings of the dedicated audit log stream.
+    pub audit: AuditConfigs,
+
+    /// Batch processor settings used by the OTLP exporter.
+    pub otlp_batch: BatchConfigs,
+
+    /// Failover settings of the OTLP exporter.
+    pub otlp_failover: FailoverConfigs,
+
+    /// Keepalive settings of the OTLP gRPC connections.
+    pub otlp_keepalive: KeepaliveConfigs,
+
+    /// TLS settings of the connections to `https` OTLP endpoints.
+    pub otlp_tls: TlsConfigs,
+
+    /// Compression of the OTLP export requests.
+    ///
+    /// Read from `LOG_OTLP_COMPRESSION` (`gzip` or `none`). Defaults to `gzip`; `none`
+    /// saves CPU when the collector is on the same host.
+    pub otlp_compression: OtlpCompression,
+
+    /// Whether the body of the OTLP records is a map of the event fields.
+    ///
+    /// Read from `LOG_OTLP_STRUCTURED_BODY`. The body becomes a `kvlist` with the
+    /// `message` and every event field, for backends that only index the body; the
+    /// fields stay attributes too. Defaults to `false`, a string body holding the message.
+    pub otlp_structured_body: bool,
+
+    /// Maximum time spent establishing a connection to an OTLP collector.
+    ///
+    /// Read from `LOG_OTLP_CONNECT_TIMEOUT`, in milliseconds. Defaults to 2s. This only
+    /// bounds the TCP and TLS handshakes, so an export to a collector that is down fails
+    /// quickly. The whole export, response included, is bounded by the export timeout,
+    /// `OTLPConfigs::exporter_timeout` or `OTEL_BLRP_EXPORT_TIMEOUT`, usually longer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logging::config::LoggingConfigs;
+    /// use std::time::Duration;
+    ///
+    /// assert_eq!(
+    ///     LoggingConfigs::default().otlp_connect_timeout,
+    ///     Duration::from_secs(2)
+    /// );
+    ///
+    /// let cfgs: LoggingConfigs =
+    ///     serde_json::from_str(r#"{ "otlp_connect_timeout": 500 }"#).unwrap();
+    /// assert_eq!(cfgs.otlp_connect_timeout, Duration::from_millis(500));
+    /// ```
+    #[serde(deserialize_with = "millis")]
+    pub otlp_connect_timeout: Duration,
+
+    /// Whether the OTLP collectors are checked for connectivity at installation.
+    ///
+    /// Read from `LOG_OTLP_PREFLIGHT`. Defaults to `false`. Once an OTLP exporter is
+    /// installed, an INFO event, `exporter initialized`, reports its `exporter` (`otlp`)
+    /// and `endpoint`, one per OTLP output. With the preflight, installation also opens a
+    /// TCP connection to each endpoint, bounded by [`Self::otlp_connect_timeout`], and
+    /// the event reports whether it succeeded as `connected`, so automated checks can
+    /// verify that logs can leave the service. The event is turned off with the startup
+    /// banner, [`Self::startup_banner`].
+    pub otlp_preflight: bool,
+
+    /// Maximum size of an OTLP export request, in bytes.
+    ///
+    /// Read from `LOG_OTLP_MAX_ENCODING_MESSAGE_SIZE`. Defaults to 16 MiB, above the
+    /// 4 MiB default of gRPC, for high-volume services exporting large batches. A batch
+    /// over the limit isn't sent, and its export fails with an error giving its size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logging::config::LoggingConfigs;
+    ///
+    /// let cfgs = LoggingConfigs::default();
+    /// assert_eq!(cfgs.otlp_max_encoding_message_size, 16 * 1024 * 1024);
+    /// assert_eq!(cfgs.otlp_max_decoding_message_size, 16 * 1024 * 1024);
+    ///
+    /// let cfgs: LoggingConfigs = serde_json::from_str(
+    ///     r#"{ "otlp_max_encoding_message_size": 33554432, "otlp_max_decoding_message_size": 1048576 }"#,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(cfgs.otlp_max_encoding_message_size, 32 * 1024 * 1024);
+    /// assert_eq!(cfgs.otlp_max_decoding_message_size, 1024 * 1024);
+    /// ```
+    pub otlp_max_encoding_message_size: usize,
+
+    /// Maximum size of an OTLP export response, in bytes.
+    ///
+    /// Read from `LOG_OTLP_MAX_DECODING_MESSAGE_SIZE`. Defaults to 16 MiB.
+    pub otlp_max_decoding_message_size: usize,
+
+    /// Settings of the GELF exporter.
+    pub gelf: GelfConfigs,
+
+    /// Interval between two background flushes of the stdout exporter, if any.
+    ///
+    /// Read from `LOG_STDOUT_FLUSH_INTERVAL`, in milliseconds. The stdout exporter
+    /// exports each record as it's emitted to keep them in order; a periodic flush limits
+    /// what an abrupt shutdown can lose. Disabled by default.
+    #[serde(deserialize_with = "optional_millis")]
+    pub stdout_flush_interval: Option<Duration>,
+
+    /// Whether the exporter is flushed right after each ERROR event.
+    ///
+    /// Read from `LOG_FLUSH_ON_ERROR`. The thread logging the error waits for the
+    /// export, so that the error reaches the collector even if the process dies next.
+    /// Applies to the stdout and OTLP exporters, and their outputs. Defaults to `false`.
+    pub flush_on_error: bool,
+
+    /// Overrides of the OTLP severity assigned to each tracing level.
+    ///
+    /// Read from `LOG_OTLP_SEVERITY_MAP` as a comma-separated list of `level=severity`
+    /// entries, where the severity is an OTLP severity name or number, e.g.
+    /// `trace=debug,warn=15`. Levels without an entry keep the standard mapping.
+    pub otlp_severity_map: Vec<String>,
+
+    /// Rewrite rules applied to the event names of exported records.
+    ///
+    /// Read from `LOG_EVENT_NAME_RULES` as a semicolon-separated list of
+    /// `pattern=>replacement` entries, where the pattern is a regular expression, e.g.
+    /// `/\d+=>/{id}` to turn `GET /users/12345` into `GET /users/{id}`. The rules are
+    /// applied in order, to every match, and only to the OTLP export: the console and
+    /// the `event.name` attribute keep the original name. Empty by default.
+    pub event_name_rules: Vec<String>,
+
+    /// OpenTelemetry baggage keys attached as attributes to exported records.
+    ///
+    /// Read from `LOG_BAGGAGE_KEYS` as a comma-separated list, e.g. `tenant.id,request.id`.
+    /// Only records sent to an exporter carry them; console output is unaffected.
+    pub baggage_keys: Vec<String>,
+
+    /// Whether the service name and namespace are converted to lower kebab case.
+    ///
+    /// Read from `LOG_SERVICE_NAME_KEBAB_CASE`. Both are always trimmed, and an empty
+    /// service name is rejected at installation. Defaults to `false`.
+    pub service_name_kebab_case: bool,
+
+    /// Key of the resource attribute carrying the deployment environment.
+    ///
+    /// Read from `LOG_ENVIRONMENT_ATTRIBUTE_KEY`. Defaults to `deployment.environment`,
+    /// following the OpenTelemetry semantic conventions; set it to `environment` to keep
+    /// the key used by earlier versions.
+    pub environment_attribute_key: String,
+
+    /// Hostname reported in place of the one of the machine, if any.
+    ///
+    /// Read from `LOG_HOSTNAME`. The hostname is exported as the `host.name` resource
+    /// attribute, written in the `hostname` field of the Bunyan records and used as the
+    /// default host of GELF messages. Setting it helps in containers, whose hostname is
+    /// often a meaningless pod id. Detected by default.
+    pub hostname: Option<String>,
+
+    /// Identifier of this instance of the service, if any.
+    ///
+    /// Read from `LOG_SERVICE_INSTANCE_ID`. It's exported as the `service.instance.id`
+    /// resource attribute, which tells apart the replicas of a horizontally scaled
+    /// service. Defaults to the value of the variable named by `service_instance_id_env`,
+    /// or else to a random UUID generated once per process.
+    pub service_instance_id: Option<String>,
+
+    /// Name of the environment variable holding the identifier of this instance.
+    ///
+    /// Read from `LOG_SERVICE_INSTANCE_ID_ENV`, e.g. `POD_NAME` or `HOSTNAME`. Only used
+    /// when `service_instance_id` is unset, and ignored when the variable is blank.
+    pub service_instance_id_env: Option<String>,
+
+    /// Additional resource attributes attached to exported records.
+    ///
+    /// They take precedence over the attributes set by the crate, so they can override
+    /// e.g. `telemetry.sdk.name` and `telemetry.sdk.version`. Not read from the
+    /// environment, since the OpenTelemetry SDK already merges the standard
+    /// `OTEL_RESOURCE_ATTRIBUTES` variable into the resource.
+    pub resource_attributes: BTreeMap<String, String>,
+
+    /// Maximum time spent detecting the cloud provider with the `cloud-detect` feature.
+    ///
+    /// Read from `LOG_CLOUD_DETECT_TIMEOUT`, in milliseconds. Defaults to 200ms.
+    #[serde(deserialize_with = "millis")]
+    pub cloud_detect_timeout: Duration,
+
+    /// Maximum time the logger provider gets to flush and shut down.
+    ///
+    /// Read from `LOG_SHUTDOWN_TIMEOUT`, in milliseconds. Defaults to 5s, short enough
+    /// for the process to exit before the kill timer of most orchestrators when the
+    /// collector is unreachable. See [`crate::provider::shutdown_timeout`].
+    #[serde(deserialize_with = "millis")]
+    pub shutdown_timeout: Duration,
+}
+
+impl LoggingConfigs {
+    /// Creates the logging settings from the current environment variables.
+    pub fn new() -> Self {
+        Self {
+            field_include: envs::list(envs::LOG_FIELD_INCLUDE_ENV_KEY),
+            field_exclude: envs::list(envs::LOG_FIELD_EXCLUDE_ENV_KEY),
+            message_denylist: envs::split(envs::LOG_MESSAGE_DENYLIST_ENV_KEY, ';'),
+            target_allowlist: envs::list(envs::LOG_TARGETS_ALLOWLIST_ENV_KEY),
+            filter_presets: Some(envs::list(envs::LOG_FILTER_PRESETS_ENV_KEY))
+                .filter(|presets| !presets.is_empty())
+                .map(|presets| {
+                    presets
+                        .iter()
+                        .filter_map(|preset| preset.parse().ok())
+                        .collect()
+                })
+                .unwrap_or_else(|| FilterPreset::ALL.to_vec()),
+            silenced_targets: envs::list(envs::LOG_SILENCED_TARGETS_ENV_KEY),
+            target_sample_rates: envs::map(envs::LOG_TARGET_SAMPLE_RATES_ENV_KEY),
+            otel_internal_level: envs::parse(envs::LOG_OTEL_INTERNAL_LEVEL_ENV_KEY)
+                .unwrap_or(LevelFilter::WARN),
+            capture_log_crate: envs::flag(envs::LOG_CAPTURE_LOG_CRATE_ENV_KEY).unwrap_or(true),
+            console_output: envs::flag(envs::LOG_CONSOLE_OUTPUT_ENV_KEY).unwrap_or(true),
+            on_exporter_failure: envs::parse(envs::LOG_ON_EXPORTER_FAILURE_ENV_KEY)
+                .unwrap_or_default(),
+            console_serialized: envs::flag(envs::LOG_CONSOLE_SERIALIZED_ENV_KEY).unwrap_or(false),
+            console_buffer: envs::parse(envs::LOG_CONSOLE_BUFFER_ENV_KEY),
+            console_flush_interval: envs::millis(envs::LOG_CONSOLE_FLUSH_INTERVAL_ENV_KEY)
+                .unwrap_or(DEFAULT_CONSOLE_FLUSH_INTERVAL),
+            warn_stream: envs::flag(envs::LOG_WARN_STREAM_ENV_KEY).unwrap_or(false),
+            error_buffer: envs::parse(envs::LOG_ERROR_BUFFER_ENV_KEY),
+            error_buffer_trigger: envs::parse(envs::LOG_ERROR_BUFFER_TRIGGER_ENV_KEY)
+                .unwrap_or(Level::ERROR),
+            console_level: envs::parse(envs::LOG_CONSOLE_LEVEL_ENV_KEY),
+            export_level: envs::parse(envs::LOG_EXPORT_LEVEL_ENV_KEY),
+            follow_trace_sampling: envs::flag(envs::LOG_FOLLOW_TRACE_SAMPLING_ENV_KEY)
+                .unwrap_or(false),
+            base_format: envs::parse(envs::LOG_BASE_FORMAT_ENV_KEY).unwrap_or_default(),
+            structured_format: envs::parse(envs::LOG_STRUCTURED_FORMAT_ENV_KEY),
+            base_fmt_stream: envs::parse(envs::LOG_BASE_FMT_STREAM_ENV_KEY)
+                .unwrap_or(ConsoleStream::Stderr),
+            structured_stream: envs::parse(envs::LOG_STRUCTURED_STREAM_ENV_KEY)
+                .unwrap_or(ConsoleStream::Stdout),
+            render_mode: envs::parse(envs::LOG_RENDER_MODE_ENV_KEY),
+            tty_detection: envs::flag(envs::LOG_TTY_DETECTION_ENV_KEY).unwrap_or(true),
+            structured_level_formats: envs::list(envs::LOG_STRUCTURED_LEVEL_FORMATS_ENV_KEY),
+            level_colors: envs::list(envs::LOG_LEVEL_COLORS_ENV_KEY),
+            json_file: envs::parse(envs::LOG_JSON_FILE_ENV_KEY),
+            base_metadata: BaseMetadataConfigs::new(),
+            json_nested_fields: envs::flag(envs::LOG_JSON_NESTED_FIELDS_ENV_KEY).unwrap_or(false),
+            json_field_names: envs::map(envs::LOG_JSON_FIELD_NAMES_ENV_KEY),
+            json_omitted_fields: envs::list(envs::LOG_JSON_OMITTED_FIELDS_ENV_KEY),
+            dual_marker: std::env::var(envs::LOG_DUAL_MARKER_ENV_KEY)
+                .unwrap_or_else(|_| DEFAULT_DUAL_MARKER.to_string()),
+            max_message_length: envs::parse(envs::LOG_MAX_MESSAGE_LENGTH_ENV_KEY),
+            max_field_length: envs::parse(envs::LOG_MAX_FIELD_LENGTH_ENV_KEY),
+            process_fields: envs::list(envs::LOG_PROCESS_FIELDS_ENV_KEY),
+            traceparent: envs::flag(envs::LOG_TRACEPARENT_ENV_KEY).unwrap_or(false),
+            target_fields: envs::flag(envs::LOG_TARGET_FIELDS_ENV_KEY).unwrap_or(false),
+            span_timing: envs::parse(envs::LOG_SPAN_TIMING_ENV_KEY),
+            recent_logs: envs::parse(envs::LOG_RECENT_LOGS_ENV_KEY),
+            volume_counters: envs::flag(envs::LOG_VOLUME_COUNTERS_ENV_KEY).unwrap_or(false),
+            event_size_histogram: envs::flag(envs::LOG_EVENT_SIZE_HISTOGRAM_ENV_KEY)
+                .unwrap_or(false),
+            event_size_buckets: Some(
+                envs::list(envs::LOG_EVENT_SIZE_BUCKETS_ENV_KEY)
+                    .iter()
+                    .filter_map(|bound| bound.parse().ok())
+                    .collect::<Vec<_>>(),
+            )
+            .filter(|bounds| !bounds.is_empty())
+            .unwrap_or_else(|| DEFAULT_EVENT_SIZE_BUCKETS.to_vec()),
+            heartbeat_interval: envs::millis(envs::LOG_HEARTBEAT_INTERVAL_ENV_KEY),
+            heartbeat_level: envs::parse(envs::LOG_HEARTBEAT_LEVEL_ENV_KEY).unwrap_or(Level::INFO),
+            startup_banner: envs::flag(envs::LOG_STARTUP_BANNER_ENV_KEY).unwrap_or(true),
+            resolved_config: envs::flag(envs::LOG_RESOLVED_CONFIG_ENV_KEY).unwrap_or(false),
+            timestamp_field: envs::parse(envs::LOG_TIMESTAMP_FIELD_ENV_KEY)
+                .unwrap_or_else(|| DEFAULT_TIMESTAMP_FIELD.to_string()),
+            audit: AuditConfigs::new(),
+            otlp_batch: BatchConfigs::new(),
+            otlp_failover: FailoverConfigs::new(),
+            otlp_keepalive: KeepaliveConfigs::new(),
+            otlp_tls: TlsConfigs::new(),
+            otlp_compression: envs::parse(envs::LOG_OTLP_COMPRESSION_ENV_KEY).unwrap_or_default(),
+            otlp_structured_body: envs::flag(envs::LOG_OTLP_STRUCTURED_BODY_ENV_KEY)
+                .unwrap_or(false),
+            otlp_preflight: envs::flag(envs::LOG_OTLP_PREFLIGHT_ENV_KEY).unwrap_or(false),
+            otlp_connect_timeout: envs::millis(envs::LOG_OTLP_CONNECT_TIMEOUT_ENV_KEY)
+                .unwrap_or(DEFAULT_OTLP_CONNECT_TIMEOUT),
+            otlp_max_encoding_message_size: envs::parse(
+                envs::LOG_OTLP_MAX_ENCODING_MESSAGE_SIZE_ENV_KEY,
+            )
+            .unwrap_or(DEFAULT_OTLP_MAX_MESSAGE_SIZE),
+            otlp_max_decoding_message_size: envs::parse(
+                envs::LOG_OTLP_MAX_DECODING_MESSAGE_SIZE_ENV_KEY,
+            )
+            .unwrap_or(DEFAULT_OTLP_MAX_MESSAGE_SIZE),
+            gelf: GelfConfigs::new(),
+            stdout_flush_interval: envs::millis(envs::LOG_STDOUT_FLUSH_INTERVAL_ENV_KEY),
+            flush_on_error: envs::flag(envs::LOG_FLUSH_ON_ERROR_ENV_KEY).unwrap_or(false),
+            otlp_severity_map: envs::list(envs::LOG_OTLP_SEVERITY_MAP_ENV_KEY),
+            event_name_rules: envs::split(envs::LOG_EVENT_NAME_RULES_ENV_KEY, ';'),
+            baggage_keys: envs::list(envs::LOG_BAGGAGE_KEYS_ENV_KEY),
+            service_name_kebab_case: envs::flag(envs::LOG_SERVICE_NAME_KEBAB_CASE_ENV_KEY)
+                .unwrap_or(false),
+            environment_attribute_key: envs::parse(envs::LOG_ENVIRONMENT_ATTRIBUTE_KEY_ENV_KEY)
+                .unwrap_or_else(|| DEFAULT_ENVIRONMENT_ATTRIBUTE_KEY.to_string()),
+            hostname: envs::parse(envs::LOG_HOSTNAME_ENV_KEY),
+            service_instance_id: envs::parse(envs::LOG_SERVICE_INSTANCE_ID_ENV_KEY),
+            service_instance_id_env: envs::parse(envs::LOG_SERVICE_INSTANCE_ID_ENV_ENV_KEY),
+            resource_attributes: BTreeMap::new(),
+            cloud_detect_timeout: envs::millis(envs::LOG_CLOUD_DETECT_TIMEOUT_ENV_KEY)
+                .unwrap_or(DEFAULT_CLOUD_DETECT_TIMEOUT),
+            shutdown_timeout: envs::millis(envs::LOG_SHUTDOWN_TIMEOUT_ENV_KEY)
+                .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT),
+        }
+    }
+}
+
+impl Default for LoggingConfigs {
+    fn default() -> Self {
+        Self {
+            field_include: Vec::new(),
+            field_exclude: Vec::new(),
+            message_denylist: Vec::new(),
+            target_allowlist: Vec::new(),
+            filter_presets: FilterPreset::ALL.to_vec(),
+            silenced_targets: Vec::new(),
+            target_sample_rates: BTreeMap::new(),
+            otel_internal_level: LevelFilter::WARN,
+            capture_log_crate: true,
+            console_output: true,
+            on_exporter_failure: FailPolicy::Fail,
+            console_serialized: false,
+            console_buffer: None,
+            console_flush_interval: DEFAULT_CONSOLE_FLUSH_INTERVAL,
+            warn_stream: false,
+            error_buffer: None,
+            error_buffer_trigger: Level::ERROR,
+            console_level: None,
+            export_level: None,
+            follow_trace_sampling: false,
+            base_format: BaseFormat::default(),
+            structured_format: None,
+            base_fmt_stream: ConsoleStream::Stderr,
+            structured_stream: ConsoleStream::Stdout,
+            render_mode: None,
+            tty_detection: true,
+            structured_level_formats: Vec::new(),
+            level_colors: Vec::new(),
+            json_file: None,
+            base_metadata: BaseMetadataConfigs::default(),
+            json_nested_fields: false,
+            json_field_names: BTreeMap::new(),
+            json_omitted_fields: Vec::new(),
+            dual_marker: DEFAULT_DUAL_MARKER.to_string(),
+            max_message_length: None,
+            max_field_length: None,
+            process_fields: Vec::new(),
+            traceparent: false,
+            target_fields: false,
+            span_timing: None,
+            recent_logs: None,
+            volume_counters: false,
+            event_size_histogram: false,
+            event_size_buckets: DEFAULT_EVENT_SIZE_BUCKETS.to_vec(),
+            heartbeat_interval: None,
+            heartbeat_level: Level::INFO,
+            startup_banner: true,
+            resolved_config: false,
+            timestamp_field: DEFAULT_TIMESTAMP_FIELD.to_string(),
+            audit: AuditConfigs::default(),
+            otlp_batch: BatchConfigs::default(),
+            otlp_failover: FailoverConfigs::default(),
+            otlp_keepalive: KeepaliveConfigs::default(),
+            otlp_tls: TlsConfigs::default(),
+            otlp_compression: OtlpCompression::Gzip,
+            otlp_structured_body: false,
+            otlp_connect_timeout: DEFAULT_OTLP_CONNECT_TIMEOUT,
+            otlp_preflight: false,
+            otlp_max_encoding_message_size: DEFAULT_OTLP_MAX_MESSAGE_SIZE,
+            otlp_max_decoding_message_size: DEFAULT_OTLP_MAX_MESSAGE_SIZE,
+            gelf: GelfConfigs::default(),
+            stdout_flush_interval: None,
+            flush_on_error: false,
+            otlp_severity_map: Vec::new(),
+            event_name_rules: Vec::new(),
+            baggage_keys: Vec::new(),
+            service_name_kebab_case: false,
+            environment_attribute_key: DEFAULT_ENVIRONMENT_ATTRIBUTE_KEY.to_string(),
+            hostname: None,
+            service_instance_id: None,
+            service_instance_id_env: None,
+            resource_attributes: BTreeMap::new(),
+            cloud_detect_timeout: DEFAULT_CLOUD_DETECT_TIMEOUT,
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+        }
+    }
+}
+
+/// Default key of the resource attribute carrying the deployment environment.
+const DEFAULT_ENVIRONMENT_ATTRIBUTE_KEY: &str = "deployment.environment";
+
+/// Default name of the event field holding the time the event happened.
+const DEFAULT_TIMESTAMP_FIELD: &str = "ts";
+
+/// Default maximum time spent detecting the cloud provider.
+const DEFAULT_CLOUD_DETECT_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Default maximum time spent shutting the logger provider down.
+pub(crate) const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default of [`LoggingConfigs::event_size_buckets`].
+const DEFAULT_EVENT_SIZE_BUCKETS: [usize; 5] = [256, 1_024, 4_096, 16_384, 65_536];
+
+/// Default of [`LoggingConfigs::console_flush_interval`].
+const DEFAULT_CONSOLE_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Default of [`LoggingConfigs::dual_marker`].
+const DEFAULT_DUAL_MARKER: &str = "@json@";
+
+/// Default of [`LoggingConfigs::otlp_connect_timeout`].
+const DEFAULT_OTLP_CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Default of [`LoggingConfigs::otlp_max_encoding_message_size`] and
+/// [`LoggingConfigs::otlp_max_decoding_message_size`].
+const DEFAULT_OTLP_MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// Compression of the OTLP export requests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum OtlpCompression {
+    /// Gzip-compressed requests and responses.
+    #[default]
+    #[serde(rename = "gzip")]
+    Gzip,
+    /// Uncompressed requests and responses.
+    #[serde(rename = "none")]
+    Uncompressed,
+}
+
+impl FromStr for OtlpCompression {
+    type Err = LoggingError;
+
+    fn from_str(compression: &str) -> Result<Self, Self::Err> {
+        match compression.to_lowercase().as_str() {
+            "gzip" => Ok(OtlpCompression::Gzip),
+            "none" => Ok(OtlpCompression::Uncompressed),
+            _ => Err(LoggingError::InvalidConfigError(format!(
+                "unknown OTLP compression `{compression}`, expected gzip or none"
+            ))),
+        }
+    }
+}
+
+/// Behavior of the OTLP batch queue when a record is emitted while it's full.
+///
+/// Every record lost to a full queue is counted in the `dropped` export statistic,
+/// whatever the policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Backpressure {
+    /// The new record is dropped, and the queued ones are kept.
+    #[default]
+    DropNew,
+    /// The oldest queued record is dropped to make room for the new one, so the most
+    /// recent records survive an outage of any length. Records already being exported
+    /// are not dropped.
+    DropOld,
+    /// The caller waits until the queue has room, so no record is lost while the
+    /// exports catch up. A slow or unreachable collector then slows the application
+    /// down, and the caller gives up after the export timeout, dropping its record like
+    /// with `DropNew`. The wait never outlasts it: callers blocked on every worker of
+    /// the Tokio runtime running the exports, or on the thread of a current-thread
+    /// runtime, stall the exports until the records are dropped.
+    Block,
+}
+
+impl FromStr for Backpressure {
+    type Err = LoggingError;
+
+    fn from_str(backpressure: &str) -> Result<Self, Self::Err> {
+        match backpressure.to_lowercase().as_str() {
+            "drop-new" => Ok(Backpressure::DropNew),
+            "drop-old" => Ok(Backpressure::DropOld),
+            "block" => Ok(Backpressure::Block),
+            _ => Err(LoggingError::InvalidConfigError(format!(
+                "unknown backpressure `{backpressure}`, expected drop-new, drop-old or block"
+            ))),
+        }
+    }
+}
+
+/// Format of the base console layer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BaseFormat {
+    /// Every event squeezed on a single line.
+    #[default]
+    Compact,
+    /// The default `tracing_subscriber` single-line format, with span context.
+    Full,
+    /// A multi-line layout, easier to read for events with many fields.
+    Pretty,
+}
+
+impl FromStr for BaseFormat {
+    type Err = LoggingError;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_lowercase().as_str() {
+            "compact" => Ok(BaseFormat::Compact),
+            "full" => Ok(BaseFormat::Full),
+            "pretty" => Ok(BaseFormat::Pretty),
+            _ => Err(LoggingError::InvalidConfigError(format!(
+                "unknown base format `{format}`, expected compact, full or pretty"
+            ))),
+        }
+    }
+}
+
+/// What installing does when the exporter can't be built.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FailPolicy {
+    /// Installing fails with the error of the exporter.
+    #[default]
+    Fail,
+    /// Logging is installed without exporter, writing to the console only.
+    FallbackToConsole,
+}
+
+impl FromStr for FailPolicy {
+    type Err = LoggingError;
+
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        match policy.to_lowercase().as_str() {
+            "fail" => Ok(FailPolicy::Fail),
+            "fallback-to-console" => Ok(FailPolicy::FallbackToConsole),
+            _ => Err(LoggingError::InvalidConfigError(format!(
+                "unknown exporter failure policy `{policy}`, expected fail or fallback-to-console"
+            ))),
+        }
+    }
+}
+
+/// Standard stream a console layer writes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConsoleStream {
+    /// The standard output.
+    Stdout,
+    /// The standard error.
+    Stderr,
+}
+
+impl FromStr for ConsoleStream {
+    type Err = LoggingError;
+
+    fn from_str(stream: &str) -> Result<Self, Self::Err> {
+        match stream.to_lowercase().as_str() {
+            "stdout" => Ok(ConsoleStream::Stdout),
+            "stderr" => Ok(ConsoleStream::Stderr),
+            _ => Err(LoggingError::InvalidConfigError(format!(
+                "unknown console stream `{stream}`, expected stdout or stderr"
+            ))),
+        }
+    }
+}
+
+/// Format of the structured console layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StructuredFormat {
+    /// A multi-line, human-readable layout.
+    Pretty,
+    /// One JSON object per event, in Bunyan format.
+    Json,
+    /// One plain JSON object per event, with the `timestamp`, the `level` name, the
+    /// `target`, the `message` and the event fields at the top level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logging::{
+    ///     config::{LoggingConfig, LoggingConfigs, OutputConfig, StructuredFormat},
+    ///     provider,
+    /// };
+    ///
+    /// let path = std::env::temp_dir().join(format!("ndjson-{}.log", std::process::id()));
+    /// let cfg = LoggingConfig {
+    ///     level: Some("info".to_string()),
+    ///     outputs: vec![OutputConfig::File {
+    ///         path: path.clone(),
+    ///         level: None,
+    ///         below: None,
+    ///         format: Some(StructuredFormat::Ndjson),
+    ///     }],
+    ///     logging: LoggingConfigs {
+    ///         console_output: false,
+    ///         startup_banner: false,
+    ///         ..LoggingConfigs::default()
+    ///     },
+    ///     ..LoggingConfig::default()
+    /// };
+    /// provider::install_from_config(cfg).expect("Failed to initialize logging");
+    ///
+    /// tracing::info!(user_id = "123", "User logged in");
+    /// tracing::warn!(attempts = 3, "Retrying");
+    ///
+    /// let output = std::fs::read_to_string(&path).unwrap();
+    /// let records: Vec<serde_json::Value> = output
+    ///     .lines()
+    ///     .map(|line| serde_json::from_str(line).unwrap())
+    ///     .collect();
+    /// assert_eq!(records.len(), 2);
+    /// assert_eq!(records[0]["level"], "INFO");
+    /// assert_eq!(records[0]["message"], "User logged in");
+    /// assert_eq!(records[0]["user_id"], "123");
+    /// assert_eq!(records[1]["level"], "WARN");
+    /// assert_eq!(records[1]["attempts"], 3);
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    Ndjson,
+    /// The plain JSON objects of [`StructuredFormat::Ndjson`], indented over several
+    /// lines for reading in a terminal.
+    ///
+    /// Combined with an NDJSON file output, the terminal gets readable records while the
+    /// file keeps compact ones for later processing, e.g. with `jq`.
+    #[serde(rename = "pretty-json")]
+    PrettyJson,
+    /// Every event twice on the same stream: in the [`StructuredFormat::Pretty`] layout
+    /// for the humans tailing it, then as a [`StructuredFormat::Ndjson`] line prefixed
+    /// with [`LoggingConfigs::dual_marker`], which a log shipper can pick out of the
+    /// stream.
+    Dual,
+}
+
+impl fmt::Display for StructuredFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StructuredFormat::Pretty => "pretty",
+            StructuredFormat::Json => "json",
+            StructuredFormat::Ndjson => "ndjson",
+            StructuredFormat::PrettyJson => "pretty-json",
+            StructuredFormat::Dual => "dual",
+        })
+    }
+}
+
+impl FromStr for StructuredFormat {
+    type Err = LoggingError;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_lowercase().as_str() {
+            "pretty" => Ok(StructuredFormat::Pretty),
+            "json" => Ok(StructuredFormat::Json),
+            "ndjson" => Ok(StructuredFormat::Ndjson),
+            "pretty-json" => Ok(StructuredFormat::PrettyJson),
+            "dual" => Ok(StructuredFormat::Dual),
+            _ => Err(LoggingError::InvalidConfigError(format!(
+                "unknown structured format `{format}`, expected pretty, json, ndjson, pretty-json or dual"
+            ))),
+        }
+    }
+}
+
+/// Resolves the format of the structured console layer from its sources, the first set
+/// one winning.
+///
+/// From the highest precedence to the lowest: the `explicit` format, set in code with
+/// [`crate::builder::LoggingBuilder::with_structured_format`], e.g. from a command-line
+/// flag; the `env` format, `LOG_STRUCTURED_FORMAT`; the `configured` format, the
+/// `structured_format` of a [`LoggingConfig`] document; and the `default` format of the
+/// [`RenderMode`], derived from `AppConfigs` through the environment
+/// [`crate::profile::Profile`].
+///
+/// # Examples
+///
+/// ```
+/// use logging::config::{StructuredFormat, resolve_format};
+/// use StructuredFormat::{Json, Ndjson, Pretty, PrettyJson};
+///
+/// // Only the default
+/// assert_eq!(resolve_format(None, None, None, Json), Json);
+///
+/// // A single source over the default
+/// assert_eq!(resolve_format(Some(Pretty), None, None, Json), Pretty);
+/// assert_eq!(resolve_format(None, Some(Ndjson), None, Json), Ndjson);
+/// assert_eq!(resolve_format(None, None, Some(PrettyJson), Json), PrettyJson);
+///
+/// // Two sources, the higher one wins
+/// assert_eq!(resolve_format(Some(Pretty), Some(Ndjson), None, Json), Pretty);
+/// assert_eq!(resolve_format(Some(Pretty), None, Some(PrettyJson), Json), Pretty);
+/// assert_eq!(resolve_format(None, Some(Ndjson), Some(PrettyJson), Json), Ndjson);
+///
+/// // Every source
+/// assert_eq!(resolve_format(Some(Pretty), Some(Ndjson), Some(PrettyJson), Json), Pretty);
+///
+/// // Ties resolve to the shared format
+/// assert_eq!(resolve_format(Some(Json), Some(Json), Some(Json), Json), Json);
+/// assert_eq!(resolve_format(None, Some(Pretty), Some(Pretty), Json), Pretty);
+/// assert_eq!(resolve_format(Some(Json), None, None, Json), Json);
+/// ```
+pub fn resolve_format(
+    explicit: Option<StructuredFormat>,
+    env: Option<StructuredFormat>,
+    configured: Option<StructuredFormat>,
+    default: StructuredFormat,
+) -> StructuredFormat {
+    explicit.or(env).or(configured).unwrap_or(default)
+}
+
+/// How the console output is rendered, independently of the deployment environment.
+///
+/// Each mode decides whether the output is colored and the default layout of the
+/// structured stdout layer, which `LOG_STRUCTURED_FORMAT` still overrides. When not
+/// configured, the mode comes from the environment [`crate::profile::Profile`].
+///
+/// # Examples
+///
+/// ```
+/// use logging::config::{RenderMode, StructuredFormat};
+///
+/// // A developer terminal
+/// assert!(RenderMode::Interactive.ansi());
+/// assert_eq!(RenderMode::Interactive.structured_format(), StructuredFormat::Pretty);
+///
+/// // CI logs, read by humans without a terminal
+/// assert!(!RenderMode::Plain.ansi());
+/// assert_eq!(RenderMode::Plain.structured_format(), StructuredFormat::Pretty);
+///
+/// // Log collectors
+/// assert!(!RenderMode::Machine.ansi());
+/// assert_eq!(RenderMode::Machine.structured_format(), StructuredFormat::Json);
+///
+/// // A local service piped into a file
+/// assert!(!RenderMode::Piped.ansi());
+/// assert_eq!(RenderMode::Piped.structured_format(), StructuredFormat::Ndjson);
+///
+/// assert_eq!("plain".parse::<RenderMode>().unwrap(), RenderMode::Plain);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderMode {
+    /// Colored, human-readable output for a terminal.
+    Interactive,
+    /// Human-readable output without colors, e.g. for CI logs.
+    Plain,
+    /// JSON output without colors, for log collectors.
+    Machine,
+    /// Plain JSON lines without colors, for interactive output that doesn't reach a
+    /// terminal.
+    Piped,
+}
+
+impl RenderMode {
+    /// Whether the console output is colored with ANSI escape codes.
+    pub fn ansi(&self) -> bool {
+        matches!(self, RenderMode::Interactive)
+    }
+
+    /// Format of the structured console layer, unless `LOG_STRUCTURED_FORMAT` is set.
+    pub fn structured_format(&self) -> StructuredFormat {
+        match self {
+            RenderMode::Interactive | RenderMode::Plain => StructuredFormat::Pretty,
+            RenderMode::Machine => StructuredFormat::Json,
+            RenderMode::Piped => StructuredFormat::Ndjson,
+        }
+    }
+
+    /// Returns the mode suiting the console output, whether or not it's a terminal.
+    ///
+    /// The pretty, colored output of the interactive mode is meant for terminals: piped
+    /// into a file or CI logs, it's wasteful and hard to parse, so the interactive mode
+    /// becomes [`RenderMode::Piped`]. Other modes are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logging::config::{RenderMode, StructuredFormat};
+    ///
+    /// let tty = RenderMode::Interactive.for_output(true);
+    /// let file = RenderMode::Interactive.for_output(false);
+    /// assert_eq!(tty.structured_format(), StructuredFormat::Pretty);
+    /// assert_eq!(file.structured_format(), StructuredFormat::Ndjson);
+    /// assert_eq!(RenderMode::Plain.for_output(false), RenderMode::Plain);
+    /// ```
+    pub fn for_output(self, is_terminal: bool) -> RenderMode {
+        match self {
+            RenderMode::Interactive if !is_terminal => RenderMode::Piped,
+            mode => mode,
+        }
+    }
+}
+
+impl fmt::Display for RenderMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RenderMode::Interactive => "interactive",
+            RenderMode::Plain => "plain",
+            RenderMode::Machine => "machine",
+            RenderMode::Piped => "piped",
+        })
+    }
+}
+
+impl FromStr for RenderMode {
+    type Err = LoggingError;
+
+    fn from_str(mode: &str) -> Result<Self, Self::Err> {
+        match mode.to_lowercase().as_str() {
+            "interactive" => Ok(RenderMode::Interactive),
+            "plain" => Ok(RenderMode::Plain),
+            "machine" => Ok(RenderMode::Machine),
+            "piped" => Ok(RenderMode::Piped),
+            _ => Err(LoggingError::InvalidConfigError(format!(
+                "unknown render mode `{mode}`, expected interactive, plain, machine or piped"
+            ))),
+        }
+    }
+}
+
+/// Metadata toggles of the base console layer.
+///
+/// Everything is included by default. Source locations are noisy and can leak the
+/// internal path structure of release builds into logs shipped off-box, so operators
+/// may want to turn them off in production.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct BaseMetadataConfigs {
+    /// Whether the emitting thread id is included (`LOG_WITH_THREAD_IDS`).
+    pub thread_ids: bool,
+
+    /// Whether the emitting thread name is included (`LOG_WITH_THREAD_NAMES`).
+    pub thread_names: bool,
+
+    /// Whether the source file is included (`LOG_WITH_FILE`).
+    pub file: bool,
+
+    /// Whether the source line number is included (`LOG_WITH_LINE_NUMBER`).
+    pub line_number: bool,
+
+    /// Master switch for source locations (`LOG_WITH_SOURCE_LOCATION`).
+    ///
+    /// When `false`, neither the file nor the line number are included, regardless
+    /// of their own toggles.
+    pub source_location: bool,
+
+    /// Least severe level whose events get a source location
+    /// (`LOG_SOURCE_LOCATION_LEVEL`).
+    ///
+    /// Set to e.g. `warn` to locate the problems without paying for the locations of
+    /// the high-volume INFO stream. Every event gets one when unset.
+    #[serde(deserialize_with = "optional_level")]
+    pub source_location_level: Option<Level>,
+}
+
+impl BaseMetadataConfigs {
+    /// Creates the metadata toggles from the current environment variables.
+    pub fn new() -> Self {
+        Self {
+            thread_ids: envs::flag(envs::LOG_WITH_THREAD_IDS_ENV_KEY).unwrap_or(true),
+            thread_names: envs::flag(envs::LOG_WITH_THREAD_NAMES_ENV_KEY).unwrap_or(true),
+            file: envs::flag(envs::LOG_WITH_FILE_ENV_KEY).unwrap_or(true),
+            line_number: envs::flag(envs::LOG_WITH_LINE_NUMBER_ENV_KEY).unwrap_or(true),
+            source_location: envs::flag(envs::LOG_WITH_SOURCE_LOCATION_ENV_KEY).unwrap_or(true),
+            source_location_level: envs::parse(envs::LOG_SOURCE_LOCATION_LEVEL_ENV_KEY),
+        }
+    }
+}
+
+impl Default for BaseMetadataConfigs {
+    fn default() -> Self {
+        Self {
+            thread_ids: true,
+            thread_names: true,
+            file: true,
+            line_number: true,
+            source_location: true,
+            source_location_level: None,
+        }
+    }
+}
+
+/// Settings of the audit log stream.
+///
+/// Audit events are the events whose target is `target` or one of its submodules.
+/// When an `output` is configured, they are additionally written to that destination
+/// as JSON lines, regardless of the configured log level and filters.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct AuditConfigs {
+    /// Target identifying audit events (`LOG_AUDIT_TARGET`, defaults to `audit`).
+    pub target: String,
+
+    /// Destination of the audit stream (`LOG_AUDIT_OUTPUT`).
+    ///
+    /// Accepts `stdout`, `stderr` or a file path. The audit stream is disabled when unset.
+    pub output: Option<AuditOutput>,
+}
+
+impl AuditConfigs {
+    /// Creates the audit settings from the current environment variables.
+    pub fn new() -> Self {
+        Self {
+            target: envs::parse(envs::LOG_AUDIT_TARGET_ENV_KEY)
+                .unwrap_or_else(|| Self::default().target),
+            output: envs::parse(envs::LOG_AUDIT_OUTPUT_ENV_KEY),
+        }
+    }
+}
+
+impl Default for AuditConfigs {
+    fn default() -> Self {
+        Self {
+            target: "audit".to_string(),
+            output: None,
+        }
+    }
+}
+
+/// Destination of the audit log stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditOutput {
+    Stdout,
+    Stderr,
+    /// A file opened in append mode, created if missing.
+    File(PathBuf),
+}
+
+impl FromStr for AuditOutput {
+    type Err = Infallible;
+
+    fn from_str(output: &str) -> Result<Self, Self::Err> {
+        Ok(match output {
+            "stdout" => AuditOutput::Stdout,
+            "stderr" => AuditOutput::Stderr,
+            path => AuditOutput::File(PathBuf::from(path)),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for AuditOutput {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let output = String::deserialize(deserializer)?;
+        let Ok(output) = output.parse::<AuditOutput>();
+        Ok(output)
+    }
+}
+
+/// Settings of the batch processor that buffers records before they are exported.
+///
+/// The settings are read from the standard OpenTelemetry `OTEL_BLRP_*` environment
+/// variables and default to the OpenTelemetry SDK defaults. Latency-sensitive services
+/// usually want a short `scheduled_delay`, while high-volume services want a larger
+/// `max_queue_size` to absorb bursts.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct BatchConfigs {
+    /// Delay between two consecutive batch exports (`OTEL_BLRP_SCHEDULE_DELAY`, in ms).
+    #[serde(deserialize_with = "millis")]
+    pub scheduled_delay: Duration,
+
+    /// Maximum number of records buffered for export (`OTEL_BLRP_MAX_QUEUE_SIZE`).
+    ///
+    /// Records emitted while the queue is full are handled according to
+    /// [`Self::backpressure`].
+    pub max_queue_size: usize,
+
+    /// Maximum number of records sent in a single export
+    /// (`OTEL_BLRP_MAX_EXPORT_BATCH_SIZE`). Must not exceed `max_queue_size`.
+    pub max_export_batch_size: usize,
+
+    /// Maximum duration of a single export (`OTEL_BLRP_EXPORT_TIMEOUT`, in ms).
+    ///
+    /// The batch processor bounds each export through the exporter's own timeout, so
+    /// when set this overrides `OTLPConfigs::exporter_timeout`.
+    #[serde(deserialize_with = "optional_millis")]
+    pub max_export_timeout: Option<Duration>,
+
+    /// What happens to a record emitted while the queue is full
+    /// (`LOG_OTLP_BACKPRESSURE`).
+    ///
+    /// Defaults to [`Backpressure::DropNew`], the behavior of the OpenTelemetry SDK.
+    pub backpressure: Backpressure,
+}
+
+impl BatchConfigs {
+    /// Creates the batch settings from the current environment variables.
+    pub fn new() -> Self {
+        let defaults = Self::default();
+
+        Self {
+            scheduled_delay: envs::millis(envs::OTEL_BLRP_SCHEDULE_DELAY_ENV_KEY)
+                .unwrap_or(defaults.scheduled_delay),
+            max_queue_size: envs::parse(envs::OTEL_BLRP_MAX_QUEUE_SIZE_ENV_KEY)
+                .unwrap_or(defaults.max_queue_size),
+            max_export_batch_size: envs::parse(envs::OTEL_BLRP_MAX_EXPORT_BATCH_SIZE_ENV_KEY)
+                .unwrap_or(defaults.max_export_batch_size),
+            max_export_timeout: envs::millis(envs::OTEL_BLRP_EXPORT_TIMEOUT_ENV_KEY),
+            backpressure: envs::parse(envs::LOG_OTLP_BACKPRESSURE_ENV_KEY).unwrap_or_default(),
+        }
+    }
+
+    /// Checks that the batch settings form a coherent combination.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LoggingError::InvalidBatchConfigError` if any size or duration is zero,
+    /// or if `max_export_batch_size` is greater than `max_queue_size`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logging::config::BatchConfigs;
+    ///
+    /// let cfgs = BatchConfigs {
+    ///     max_queue_size: 100,
+    ///     max_export_batch_size: 500,
+    ///     ..BatchConfigs::default()
+    /// };
+    /// assert!(cfgs.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), LoggingError> {
+        let invalid = |reason: &str| Err(LoggingError::InvalidBatchConfigError(reason.into()));
+
+        if self.scheduled_delay.is_zero() {
+            return invalid("scheduled_delay must be greater than zero");
+        }
+        if self.max_queue_size == 0 {
+            return invalid("max_queue_size must be greater than zero");
+        }
+        if self.max_export_batch_size == 0 {
+            return invalid("max_export_batch_size must be greater than zero");
+        }
+        if self.max_export_batch_size > self.max_queue_size {
+            return invalid("max_export_batch_size must not exceed max_queue_size");
+        }
+        if self
+            .max_export_timeout
+            .is_some_and(|timeout| timeout.is_zero())
+        {
+            return invalid("max_export_timeout must be greater than zero");
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for BatchConfigs {
+    fn default() -> Self {
+        Self {
+            scheduled_delay: Duration::from_millis(1_000),
+            max_queue_size: 2_048,
+            max_export_batch_size: 512,
+            max_export_timeout: None,
+            backpressure: Backpressure::default(),
+        }
+    }
+}
+
+/// Failover settings of the OTLP exporter.
+///
+/// Records are exported to the `OTLPConfigs` endpoint, the primary. When `threshold`
+/// consecutive exports fail, the exporter fails over to the next endpoint of
+/// `endpoints` and retries the failed batch there once. Failing over from the last
+/// endpoint wraps around to the primary. Once `recovery` has elapsed since a failover,
+/// the next export tries the primary again, and returns to the secondaries through the
+/// same threshold if it's still unreachable.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct FailoverConfigs {
+    /// Secondary endpoints, in failover order (`LOG_OTLP_FAILOVER_ENDPOINTS`).
+    ///
+    /// Failover is disabled when empty, which is the default.
+    pub endpoints: Vec<String>,
+
+    /// Consecutive failed exports before failing over (`LOG_OTLP_FAILOVER_THRESHOLD`).
+    ///
+    /// Defaults to 3, and is treated as 1 when set to 0.
+    pub threshold: u32,
+
+    /// Delay before the primary is tried again (`LOG_OTLP_FAILOVER_RECOVERY`, in ms).
+    ///
+    /// Defaults to 30 seconds.
+    #[serde(deserialize_with = "millis")]
+    pub recovery: Duration,
+}
+
+impl FailoverConfigs {
+    /// Creates the failover settings from the current environment variables.
+    pub fn new() -> Self {
+        let defaults = Self::default();
+
+        Self {
+            endpoints: envs::list(envs::LOG_OTLP_FAILOVER_ENDPOINTS_ENV_KEY),
+            threshold: envs::parse(envs::LOG_OTLP_FAILOVER_THRESHOLD_ENV_KEY)
+                .unwrap_or(defaults.threshold),
+            recovery: envs::millis(envs::LOG_OTLP_FAILOVER_RECOVERY_ENV_KEY)
+                .unwrap_or(defaults.recovery),
+        }
+    }
+}
+
+impl Default for FailoverConfigs {
+    fn default() -> Self {
+        Self {
+            endpoints: Vec::new(),
+            threshold: 3,
+            recovery: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Keepalive settings of the OTLP gRPC connections.
+///
+/// Intermediaries such as NAT gateways and proxies silently drop connections that stay
+/// idle for too long, and the exporter only notices when the next export fails. HTTP/2
+/// keepalive pings keep the connections to the collectors alive, and detect dead ones
+/// before an export is attempted.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct KeepaliveConfigs {
+    /// Interval between two pings (`LOG_OTLP_KEEPALIVE_INTERVAL`, in ms).
+    ///
+    /// Defaults to 30 seconds. Keepalive is disabled when set to 0.
+    #[serde(deserialize_with = "millis")]
+    pub interval: Duration,
+
+    /// Delay after which an unanswered ping closes the connection
+    /// (`LOG_OTLP_KEEPALIVE_TIMEOUT`, in ms).
+    ///
+    /// Defaults to 10 seconds.
+    #[serde(deserialize_with = "millis")]
+    pub timeout: Duration,
+
+    /// Whether pings are sent on connections without ongoing exports
+    /// (`LOG_OTLP_KEEPALIVE_WHILE_IDLE`).
+    ///
+    /// Defaults to `true`, since idle connections are the ones dropped by intermediaries.
+    pub while_idle: bool,
+}
+
+impl KeepaliveConfigs {
+    /// Creates the keepalive settings from the current environment variables.
+    pub fn new() -> Self {
+        let defaults = Self::default();
+
+        Self {
+            interval: envs::millis(envs::LOG_OTLP_KEEPALIVE_INTERVAL_ENV_KEY)
+                .unwrap_or(defaults.interval),
+            timeout: envs::millis(envs::LOG_OTLP_KEEPALIVE_TIMEOUT_ENV_KEY)
+                .unwrap_or(defaults.timeout),
+            while_idle: envs::flag(envs::LOG_OTLP_KEEPALIVE_WHILE_IDLE_ENV_KEY)
+                .unwrap_or(defaults.while_idle),
+        }
+    }
+
+    /// Returns `true` if keepalive pings are sent.
+    pub fn is_enabled(&self) -> bool {
+        !self.interval.is_zero()
+    }
+}
+
+impl Default for KeepaliveConfigs {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            timeout: Duration::from_secs(10),
+            while_idle: true,
+        }
+    }
+}
+
+/// TLS settings of the OTLP gRPC connections to `https` endpoints.
+///
+/// Collector certificates are verified against the native root certificates, and the
+/// configured CA certificate if any. A client certificate and its key are presented
+/// for mutual TLS when both are set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct TlsConfigs {
+    /// Path of the PEM certificate of an additional CA trusted to sign the collector
+    /// certificates (`OTEL_EXPORTER_OTLP_CERTIFICATE`).
+    pub ca_certificate: Option<PathBuf>,
+
+    /// Domain name the collector certificates are verified against, instead of the host
+    /// of the endpoint (`LOG_OTLP_TLS_DOMAIN`).
+    pub domain_name: Option<String>,
+
+    /// Path of the PEM client certificate (`OTEL_EXPORTER_OTLP_CLIENT_CERTIFICATE`).
+    pub client_certificate: Option<PathBuf>,
+
+    /// Path of the PEM private key of the client certificate
+    /// (`OTEL_EXPORTER_OTLP_CLIENT_KEY`).
+    pub client_key: Option<PathBuf>,
+}
+
+impl TlsConfigs {
+    /// Creates the TLS settings from the current environment variables.
+    pub fn new() -> Self {
+        Self {
+            ca_certificate: envs::parse(envs::OTEL_EXPORTER_OTLP_CERTIFICATE_ENV_KEY),
+            domain_name: envs::parse(envs::LOG_OTLP_TLS_DOMAIN_ENV_KEY),
+            client_certificate: envs::parse(envs::OTEL_EXPORTER_OTLP_CLIENT_CERTIFICATE_ENV_KEY),
+            client_key: envs::parse(envs::OTEL_EXPORTER_OTLP_CLIENT_KEY_ENV_KEY),
+        }
+    }
+}
+
+/// Settings of the GELF exporter, sending events to Graylog.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct GelfConfigs {
+    /// Address of the Graylog input, as `host:port` (`LOG_GELF_ADDRESS`).
+    ///
+    /// Defaults to `127.0.0.1:12201`, the default port of the GELF inputs.
+    pub address: String,
+
+    /// Transport of the messages (`LOG_GELF_TRANSPORT`, `udp` or `tcp`).
+    ///
+    /// Defaults to `udp`.
+    pub transport: GelfTransport,
+
+    /// Value of the `host` field of the messages (`LOG_GELF_HOST`).
+    ///
+    /// Defaults to the hostname, see [`LoggingConfigs::hostname`].
+    pub host: Option<String>,
+
+    /// Maximum size of a UDP datagram, in bytes (`LOG_GELF_CHUNK_SIZE`).
+    ///
+    /// Larger messages are split into chunks of this size, headers included. Defaults
+    /// to 1420, which fits the MTU of most networks.
+    pub chunk_size: usize,
+}
+
+impl GelfConfigs {
+    /// Creates the GELF settings from the current environment variables.
+    pub fn new() -> Self {
+        let defaults = Self::default();
+
+        Self {
+            address: envs::parse(envs::LOG_GELF_ADDRESS_ENV_KEY).unwrap_or(defaults.address),
+            transport: envs::parse(envs::LOG_GELF_TRANSPORT_ENV_KEY).unwrap_or(defaults.transport),
+            host: envs::parse(envs::LOG_GELF_HOST_ENV_KEY),
+            chunk_size: envs::parse(envs::LOG_GELF_CHUNK_SIZE_ENV_KEY)
+                .unwrap_or(defaults.chunk_size),
+        }
+    }
+}
+
+impl Default for GelfConfigs {
+    fn default() -> Self {
+        Self {
+            address: "127.0.0.1:12201".to_string(),
+            transport: GelfTransport::default(),
+            host: None,
+            chunk_size: 1420,
+        }
+    }
+}
+
+/// Transport of the GELF messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GelfTransport {
+    /// One datagram per message, chunked when it exceeds the chunk size.
+    #[default]
+    Udp,
+    /// A persistent connection, with messages delimited by a null byte.
+    Tcp,
+}
+
+impl FromStr for GelfTransport {
+    type Err = LoggingError;
+
+    fn from_str(transport: &str) -> Result<Self, Self::Err> {
+        match transport.to_lowercase().as_str() {
+            "udp" => Ok(GelfTransport::Udp),
+            "tcp" => Ok(GelfTransport::Tcp),
+            _ => Err(LoggingError::InvalidConfigError(format!(
+                "unknown GELF transport `{transport}`, expected udp or tcp"
+            ))),
+        }
+    }
+}
+
+/// Deserializes a level filter from its name.
+fn level_filter<'de, D: Deserializer<'de>>(deserializer: D) -> Result<LevelFilter, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(de::Error::custom)
+}
+
+/// Deserializes a level from its name.
+fn level<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Level, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(de::Error::custom)
+}
+
+/// Deserializes an optional level from its name.
+fn optional_level<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Level>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|level| level.parse().map_err(de::Error::custom))
+        .transpose()
+}
+
+/// Deserializes a duration expressed in milliseconds.
+fn millis<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    u64::deserialize(deserializer).map(Duration::from_millis)
+}
+
+/// Deserializes an optional duration expressed in milliseconds.
+fn optional_millis<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Duration>, D::Error> {
+    Option::<u64>::deserialize(deserializer).map(|millis| millis.map(Duration::from_millis))
+}
@@ -0,0 +1,251 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Correlation IDs
+//!
+//! This module packages the propagation of a request correlation id, such as the
+//! `X-Correlation-ID` header, to every event logged while handling the request.
+//!
+//! [`with_correlation_id`] runs a future inside a `correlation` span carrying the id as
+//! its `correlation_id` field, so the console shows it on every event emitted within.
+//! JSON records get it as a `correlation_id` field and exported records as a
+//! `correlation_id` attribute. The id
+//! is bound to the future: tasks spawned from it must be wrapped again, e.g. with
+//! [`crate::task::in_current_context`].
+
+use std::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tracing::Span;
+
+thread_local! {
+    static CORRELATION_ID: RefCell<Option<Arc<str>>> = const { RefCell::new(None) };
+}
+
+/// Name of the span field and of the exported attribute carrying the correlation id.
+pub const CORRELATION_ID_FIELD: &str = "correlation_id";
+
+/// Runs `future` with the given correlation id attached to every event it logs.
+///
+/// # Examples
+///
+/// ```no_run
+/// use logging::correlation::{correlation_id, with_correlation_id};
+///
+/// async fn handle(header: &str) {
+///     with_correlation_id(header, async {
+///         tracing::info!("processing request");
+///
+///         // Spawned tasks don't inherit the id, it's passed on explicitly
+///         let id = correlation_id().unwrap_or_default();
+///         tokio::spawn(with_correlation_id(id, async {
+///             tracing::info!("background work");
+///         }));
+///     })
+///     .await;
+/// }
+/// ```
+pub fn with_correlation_id<F: Future>(id: impl Into<String>, future: F) -> WithCorrelationId<F> {
+    let id: Arc<str> = Arc::from(id.into());
+    WithCorrelationId {
+        span: tracing::info_span!("correlation", correlation_id = %id),
+        id,
+        future,
+    }
+}
+
+/// Returns the correlation id of the future being polled on this thread, if any.
+pub fn correlation_id() -> Option<String> {
+    CORRELATION_ID.with(|current| current.borrow().as_deref().map(String::from))
+}
+
+/// Returns the correlation id of the future being polled on this thread, if any,
+/// without copying it.
+pub(crate) fn current() -> Option<Arc<str>> {
+    CORRELATION_ID.with(|current| current.borrow().clone())
+}
+
+/// A future running with a correlation id, created by [`with_correlation_id`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct WithCorrelationId<F> {
+    id: Arc<str>,
+    span: Span,
+    future: F,
+}
+
+impl<F: Future> Future for WithCorrelationId<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: the inner future is never moved out of the pinned wrapper
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+
+        let _entered = this.span.enter();
+        let _restore = set_current(Some(this.id.clone()));
+        future.poll(cx)
+    }
+}
+
+/// Makes `id` the correlation id of this thread until the returned guard is dropped.
+pub(crate) fn set_current(id: Option<Arc<str>>) -> Restore {
+    Restore(CORRELATION_ID.with(|current| current.replace(id)))
+}
+
+/// Restores the correlation id of the enclosing scope when dropped.
+pub(crate) struct Restore(Option<Arc<str>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CORRELATION_ID.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+#[cfg(any(feature = "stdout", feature = "otlp"))]
+pub(crate) use processor::CorrelationProcessor;
+
+#[cfg(any(feature = "stdout", feature = "otlp"))]
+mod processor {
+    use super::CORRELATION_ID_FIELD;
+    use opentelemetry::{
+        InstrumentationScope,
+        logs::{AnyValue, LogRecord},
+    };
+    use opentelemetry_sdk::{
+        Resource,
+        error::OTelSdkResult,
+        logs::{LogProcessor, SdkLogRecord},
+    };
+    use std::time::Duration;
+
+    /// A [`LogProcessor`] that attaches the current correlation id to records before
+    /// delegating to another processor.
+    ///
+    /// An attribute already set by the event itself is never overwritten.
+    #[derive(Debug)]
+    pub(crate) struct CorrelationProcessor<P> {
+        inner: P,
+    }
+
+    impl<P> CorrelationProcessor<P> {
+        /// Wraps `inner`.
+        pub(crate) fn new(inner: P) -> Self {
+            CorrelationProcessor { inner }
+        }
+    }
+
+    impl<P: LogProcessor> LogProcessor for CorrelationProcessor<P> {
+        fn emit(&self, data: &mut SdkLogRecord, instrumentation: &InstrumentationScope) {
+            if let Some(id) = super::current()
+                && !data
+                    .attributes_iter()
+                    .any(|(key, _)| key.as_str() == CORRELATION_ID_FIELD)
+            {
+                data.add_attribute(
+                    CORRELATION_ID_FIELD,
+                    AnyValue::String(id.to_string().into()),
+                );
+            }
+            self.inner.emit(data, instrumentation);
+        }
+
+        fn force_flush(&self) -> OTelSdkResult {
+            self.inner.force_flush()
+        }
+
+        fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+            self.inner.shutdown_with_timeout(timeout)
+        }
+
+        fn set_resource(&mut self, resource: &Resource) {
+            self.inner.set_resource(resource);
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "stdout", feature = "otlp")))]
+mod tests {
+    use super::*;
+    use crate::{config::LoggingConfig, test::init_with};
+    use opentelemetry::logs::AnyValue;
+    use opentelemetry_sdk::logs::SdkLogRecord;
+
+    fn correlation_attribute(record: &SdkLogRecord) -> Option<String> {
+        record
+            .attributes_iter()
+            .find(|(key, _)| key.as_str() == CORRELATION_ID_FIELD)
+            .map(|(_, value)| match value {
+                AnyValue::String(value) => value.to_string(),
+                other => format!("{other:?}"),
+            })
+    }
+
+    #[tokio::test]
+    async fn events_of_the_wrapped_future_carry_the_id() {
+        let logs = init_with(LoggingConfig {
+            level: Some("info".to_string()),
+            ..LoggingConfig::default()
+        });
+
+        with_correlation_id("req-42", async {
+            tokio::task::yield_now().await;
+            assert_eq!(correlation_id().as_deref(), Some("req-42"));
+            tracing::info!("order charged");
+
+            // The spawned task runs in a scope of its own, with the id passed on
+            let id = correlation_id().unwrap_or_default();
+            tokio::spawn(with_correlation_id(id, async {
+                tokio::task::yield_now().await;
+                tracing::info!("receipt sent");
+            }))
+            .await
+            .unwrap();
+        })
+        .await;
+        tracing::info!("request done");
+        assert_eq!(correlation_id(), None);
+
+        assert!(logs.contains("correlation_id=req-42"));
+        let exported = logs.exported();
+        assert_eq!(
+            logs.exported_bodies(),
+            ["order charged", "receipt sent", "request done"]
+        );
+        assert_eq!(
+            correlation_attribute(&exported[0]).as_deref(),
+            Some("req-42")
+        );
+        assert_eq!(
+            correlation_attribute(&exported[1]).as_deref(),
+            Some("req-42")
+        );
+        assert_eq!(correlation_attribute(&exported[2]), None);
+    }
+
+    #[tokio::test]
+    async fn event_attribute_wins_over_the_id() {
+        let logs = init_with(LoggingConfig {
+            level: Some("info".to_string()),
+            ..LoggingConfig::default()
+        });
+
+        with_correlation_id("req-42", async {
+            tracing::info!(correlation_id = "req-7", "order charged");
+        })
+        .await;
+
+        let exported = logs.exported();
+        assert_eq!(exported.len(), 1);
+        assert_eq!(
+            correlation_attribute(&exported[0]).as_deref(),
+            Some("req-7")
+        );
+    }
+}
@@ -24,6 +24,88 @@ pub enum LoggingError {
     #[error("logging internal error")]
     InternalError,
 
-    #[error("this exporter requires specific features, allowed features are: otlp and stdout")]
-    InvalidFeaturesError,
+    /// Represents an exporter that isn't available with the enabled features.
+    ///
+    /// The contained string names the exporter and the feature, or target, it requires.
+    #[error("unavailable exporter: {0}")]
+    InvalidFeaturesError(String),
+
+    /// Represents a configuration value that could not be understood.
+    ///
+    /// The contained string describes the offending value and what was expected.
+    #[error("invalid logging configuration: {0}")]
+    InvalidConfigError(String),
+
+    /// Represents a field filter condition that could not be parsed.
+    ///
+    /// The contained string is the offending condition as it was configured.
+    #[error("invalid field filter condition: {0}")]
+    InvalidFieldFilterError(String),
+
+    /// Represents a message denylist pattern that isn't a valid regular expression.
+    ///
+    /// The contained string is the offending pattern with the reason it was rejected.
+    #[error("invalid message filter pattern: {0}")]
+    InvalidMessageFilterError(String),
+
+    /// Represents a `target=level` filter directive that could not be parsed.
+    ///
+    /// The contained string names the offending token and what was expected.
+    #[error("invalid filter directive: {0}")]
+    InvalidDirectiveError(String),
+
+    /// Represents an incoherent batch processor configuration.
+    ///
+    /// The contained string describes which constraint was violated.
+    #[error("invalid batch processor configuration: {0}")]
+    InvalidBatchConfigError(String),
+
+    /// Represents an audit stream destination that could not be opened.
+    ///
+    /// The contained string describes the destination and the underlying I/O error.
+    #[error("invalid audit output: {0}")]
+    InvalidAuditOutputError(String),
+
+    /// Represents a service name that can't identify the service in exported records.
+    ///
+    /// The contained string describes the offending name.
+    #[error("invalid service name: {0}")]
+    InvalidServiceNameError(String),
+
+    /// Represents a systemd journal that can't be reached.
+    ///
+    /// The contained string is the underlying I/O error.
+    #[error("journald unavailable: {0}")]
+    JournaldUnavailableError(String),
+
+    /// Represents a Graylog GELF input that can't be reached.
+    ///
+    /// The contained string is the underlying I/O error.
+    #[error("gelf unavailable: {0}")]
+    GelfUnavailableError(String),
+
+    /// Represents a tracing subscriber that could not be installed.
+    ///
+    /// The contained string describes why, e.g. another global subscriber being set.
+    #[error("failed to install the tracing subscriber: {0}")]
+    SubscriberInstallError(String),
+
+    /// Represents an installation attempted once logging is already installed, through
+    /// another entry point.
+    ///
+    /// The contained string describes the installation that was refused.
+    #[error("logging already initialized: {0}")]
+    AlreadyInitializedError(String),
+
+    /// Represents an exporter installed outside of the async runtime it requires.
+    ///
+    /// The contained string describes the missing runtime.
+    #[error("runtime unavailable: {0}")]
+    RuntimeUnavailableError(String),
+
+    /// Represents a configuration that could not be applied to the installed logging.
+    ///
+    /// The contained string describes why, e.g. logging not being installed yet.
+    #[error("failed to reconfigure logging: {0}")]
+    ReconfigureError(String),
 }
@@ -0,0 +1,126 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Audit Log Stream
+//!
+//! This module provides a secondary log stream dedicated to compliance audit events.
+//! Events whose target is the configured audit target (`audit` by default) or one of
+//! its submodules (`audit::...`) are written as JSON lines to a dedicated destination.
+//!
+//! The audit stream has its own always-on filter: audit events reach it regardless of
+//! the configured log level, target filters or field filters, which only govern the
+//! main stream.
+//!
+//! ```no_run
+//! // With LOG_AUDIT_OUTPUT=/var/log/my-service/audit.log
+//! tracing::info!(target: "audit", actor = "alice", action = "delete", "resource removed");
+//! ```
+
+use crate::{
+    config::{AuditConfigs, AuditOutput},
+    errors::LoggingError,
+    exporters::{layers::BoxedLayer, writer::BrokenPipeGuard},
+};
+use configs::app::AppConfigs;
+use std::{fs::OpenOptions, sync::Arc};
+use tracing_bunyan_formatter::BunyanFormattingLayer;
+use tracing_subscriber::{Layer, filter::filter_fn};
+
+/// Builds the audit stream layer, or `None` when no audit output is configured.
+///
+/// # Errors
+///
+/// Returns `LoggingError::InvalidAuditOutputError` if the audit file can't be opened.
+pub(crate) fn layer(
+    app_cfgs: &AppConfigs,
+    cfgs: &AuditConfigs,
+) -> Result<Option<BoxedLayer>, LoggingError> {
+    let Some(output) = &cfgs.output else {
+        return Ok(None);
+    };
+
+    let target = cfgs.target.clone();
+    let filter = filter_fn(move |meta| is_audit_target(meta.target(), &target));
+    let name = app_cfgs.name.clone();
+
+    let layer = match output {
+        AuditOutput::Stdout => {
+            BunyanFormattingLayer::new(name, BrokenPipeGuard::new(std::io::stdout))
+                .with_filter(filter)
+                .boxed()
+        }
+        AuditOutput::Stderr => {
+            BunyanFormattingLayer::new(name, BrokenPipeGuard::new(std::io::stderr))
+                .with_filter(filter)
+                .boxed()
+        }
+        AuditOutput::File(path) => {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|err| {
+                    LoggingError::InvalidAuditOutputError(format!("{}: {err}", path.display()))
+                })?;
+
+            BunyanFormattingLayer::new(name, Arc::new(file))
+                .with_filter(filter)
+                .boxed()
+        }
+    };
+
+    Ok(Some(layer))
+}
+
+/// Returns `true` if `target` is the audit target or one of its submodules.
+fn is_audit_target(target: &str, audit_target: &str) -> bool {
+    target
+        .strip_prefix(audit_target)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::{LoggingConfig, LoggingConfigs},
+        test::init_with,
+    };
+
+    #[test]
+    fn audit_events_reach_the_audit_file_when_the_level_is_off() {
+        let path = std::env::temp_dir().join(format!("audit-off-{}.log", std::process::id()));
+        let logs = init_with(LoggingConfig {
+            level: Some("off".to_string()),
+            logging: LoggingConfigs {
+                audit: AuditConfigs {
+                    output: Some(AuditOutput::File(path.clone())),
+                    ..AuditConfigs::default()
+                },
+                ..LoggingConfigs::default()
+            },
+            ..LoggingConfig::default()
+        });
+
+        tracing::info!(target: "audit::billing", actor = "alice", "invoice deleted");
+        tracing::error!(target: "my_app", "charge failed");
+
+        let audit = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(audit.lines().count(), 1);
+        let record: serde_json::Value = serde_json::from_str(&audit).unwrap();
+        assert_eq!(record["msg"], "invoice deleted");
+        assert_eq!(record["actor"], "alice");
+        assert!(!logs.contains("invoice deleted"));
+        assert!(!logs.contains("charge failed"));
+    }
+
+    #[test]
+    fn audit_targets_include_their_submodules_only() {
+        assert!(is_audit_target("audit", "audit"));
+        assert!(is_audit_target("audit::billing", "audit"));
+        assert!(!is_audit_target("auditor", "audit"));
+        assert!(!is_audit_target("my_app::audit", "audit"));
+    }
+}
@@ -0,0 +1,520 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Backpressure
+//!
+//! This module bounds the records handed to a batch processor and applies a
+//! [`Backpressure`] policy when the bound is reached. The OpenTelemetry SDK silently
+//! drops the new records once its queue is full; with the bound enforced in front of
+//! it, a full queue can also drop the oldest records, or make the caller wait.
+//!
+//! A [`BoundedQueue`] counts the records queued or being exported. Its processor
+//! wrapper admits, drops or holds back the emitted records, and its exporter wrapper
+//! releases their room once exported.
+//!
+//! To drop the oldest records, the queue holds the records itself, in a ring from
+//! which the oldest one is evicted to make room for a new one. The records handed to
+//! the batch processor only stand for the held ones: each batch exports as many held
+//! records as it contains, oldest first. A record is handed over only while the batch
+//! processor has room, so it never drops any, and the newest records always survive.
+//!
+//! A caller held back by [`Backpressure::Block`] waits for the block timeout at most,
+//! the export timeout in the OTLP exporter, before its record is dropped and counted
+//! like with [`Backpressure::DropNew`]. Callers blocked on every worker of a Tokio
+//! runtime would otherwise stall the transport running on that runtime, and the queue
+//! would never drain.
+
+use crate::config::Backpressure;
+use opentelemetry::InstrumentationScope;
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    logs::{LogBatch, LogExporter, LogProcessor, SdkLogRecord},
+};
+use std::{
+    collections::VecDeque,
+    sync::{
+        Arc, Condvar, Mutex, MutexGuard, PoisonError,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+/// Default of [`BoundedQueue::with_block_timeout`].
+const DEFAULT_BLOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A bound on the records queued by a batch processor, shared by the
+/// [`BoundedProcessor`] in front of it and the [`BoundedExporter`] behind it.
+///
+/// # Examples
+///
+/// ```
+/// use logging::{config::Backpressure, exporters::backpressure::BoundedQueue};
+/// use opentelemetry_sdk::logs::{
+///     BatchConfigBuilder, BatchLogProcessor, InMemoryLogExporter, SdkLoggerProvider,
+/// };
+///
+/// let queue = BoundedQueue::new(512, Backpressure::DropOld);
+/// let batch = BatchLogProcessor::builder(queue.exporter(InMemoryLogExporter::default()))
+///     .with_batch_config(
+///         BatchConfigBuilder::default()
+///             .with_max_queue_size(queue.batch_queue_size())
+///             .build(),
+///     )
+///     .build();
+/// let provider = SdkLoggerProvider::builder()
+///     .with_log_processor(queue.processor(batch))
+///     .build();
+///
+/// assert!(queue.is_empty());
+/// assert_eq!(queue.dropped(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BoundedQueue {
+    state: Arc<State>,
+    block_timeout: Duration,
+}
+
+#[derive(Debug)]
+struct State {
+    max_queue_size: usize,
+    policy: Backpressure,
+    queued: Mutex<Queued>,
+    room: Condvar,
+    dropped: AtomicU64,
+    closed: AtomicBool,
+}
+
+#[derive(Debug, Default)]
+struct Queued {
+    /// Records handed to the batch processor and not exported yet, or with
+    /// [`Backpressure::DropOld`] not taken by an export yet.
+    records: usize,
+    /// Records held with [`Backpressure::DropOld`], oldest first.
+    held: VecDeque<(SdkLogRecord, InstrumentationScope)>,
+    /// Held records being exported.
+    exporting: usize,
+}
+
+impl BoundedQueue {
+    /// Creates a queue holding at most `max_queue_size` records, applying `policy` once
+    /// it's full.
+    pub fn new(max_queue_size: usize, policy: Backpressure) -> Self {
+        BoundedQueue {
+            state: Arc::new(State {
+                max_queue_size,
+                policy,
+                queued: Mutex::new(Queued::default()),
+                room: Condvar::new(),
+                dropped: AtomicU64::new(0),
+                closed: AtomicBool::new(false),
+            }),
+            block_timeout: DEFAULT_BLOCK_TIMEOUT,
+        }
+    }
+
+    /// Sets how long a caller waits for room with [`Backpressure::Block`] before its
+    /// record is dropped, 10 seconds by default.
+    ///
+    /// It applies to the wrappers created afterwards.
+    pub fn with_block_timeout(mut self, timeout: Duration) -> Self {
+        self.block_timeout = timeout;
+        self
+    }
+
+    /// Wraps the batch processor `inner`, bounding the records emitted to it.
+    pub fn processor<P>(&self, inner: P) -> BoundedProcessor<P> {
+        BoundedProcessor {
+            inner,
+            queue: self.clone(),
+        }
+    }
+
+    /// Wraps the exporter `inner` of the batch processor, releasing the room of the
+    /// exported records.
+    pub fn exporter<E>(&self, inner: E) -> BoundedExporter<E> {
+        BoundedExporter {
+            inner,
+            queue: self.clone(),
+        }
+    }
+
+    /// Returns the queue size the batch processor must be built with, the
+    /// `max_queue_size` of the queue.
+    pub fn batch_queue_size(&self) -> usize {
+        self.state.max_queue_size
+    }
+
+    /// Returns the number of records queued or being exported.
+    pub fn len(&self) -> usize {
+        let queued = self.state.lock();
+        match self.state.policy {
+            Backpressure::DropOld => queued.held.len() + queued.exporting,
+            Backpressure::DropNew | Backpressure::Block => queued.records,
+        }
+    }
+
+    /// Returns `true` when no record is queued or being exported.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of records dropped because the queue was full.
+    pub fn dropped(&self) -> u64 {
+        self.state.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Decides whether a new record is handed to the batch processor, waiting for room
+    /// with [`Backpressure::Block`] until the block timeout.
+    fn admit(&self) -> bool {
+        let state = &self.state;
+        let mut queued = state.lock();
+        let mut deadline = None;
+        loop {
+            if queued.records < state.max_queue_size {
+                queued.records += 1;
+                return true;
+            }
+            // Nothing exports the queue anymore once shut down
+            if state.policy == Backpressure::Block && !state.closed.load(Ordering::Acquire) {
+                let deadline = *deadline.get_or_insert_with(|| Instant::now() + self.block_timeout);
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if !remaining.is_zero() {
+                    queued = state
+                        .room
+                        .wait_timeout(queued, remaining)
+                        .map(|(queued, _)| queued)
+                        .unwrap_or_else(|err| err.into_inner().0);
+                    continue;
+                }
+            }
+            state.dropped.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+    }
+
+    /// Holds a new record with [`Backpressure::DropOld`], evicting the oldest held one
+    /// if the queue is full, and decides whether a record is handed to the batch
+    /// processor to stand for it.
+    ///
+    /// There are always at least as many records in the batch processor as held ones,
+    /// so every held record gets exported.
+    fn hold(&self, record: &SdkLogRecord, instrumentation: &InstrumentationScope) -> bool {
+        let state = &self.state;
+        let mut queued = state.lock();
+        if queued.held.len() >= state.max_queue_size {
+            queued.held.pop_front();
+            state.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        queued
+            .held
+            .push_back((record.clone(), instrumentation.clone()));
+
+        let handed = queued.records < state.max_queue_size;
+        if handed {
+            queued.records += 1;
+        }
+        handed
+    }
+
+    /// Takes the held records exported in place of a batch of `records`, oldest first.
+    ///
+    /// The batch leaves room for new records right away, so the ones held during the
+    /// export get a record of their own in the batch processor.
+    fn take_held(&self, records: usize) -> Vec<(SdkLogRecord, InstrumentationScope)> {
+        let mut queued = self.state.lock();
+        queued.records = queued.records.saturating_sub(records);
+        let taken = records.min(queued.held.len());
+        queued.exporting += taken;
+        queued.held.drain(..taken).collect()
+    }
+
+    /// Releases the room of `records` records that left the batch processor, and of
+    /// the `held` records exported in their place.
+    fn release(&self, records: usize, held: usize) {
+        let mut queued = self.state.lock();
+        queued.records = queued.records.saturating_sub(records);
+        queued.exporting -= held;
+        drop(queued);
+        self.state.room.notify_all();
+    }
+
+    /// Stops waiting for room, as the queue won't be exported anymore.
+    fn close(&self) {
+        self.state.closed.store(true, Ordering::Release);
+        let _queued = self.state.lock();
+        self.state.room.notify_all();
+    }
+}
+
+impl State {
+    fn lock(&self) -> MutexGuard<'_, Queued> {
+        self.queued.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A [`LogProcessor`] applying the policy of a [`BoundedQueue`] before delegating to a
+/// batch processor.
+#[derive(Debug)]
+pub struct BoundedProcessor<P> {
+    inner: P,
+    queue: BoundedQueue,
+}
+
+impl<P: LogProcessor> LogProcessor for BoundedProcessor<P> {
+    fn emit(&self, data: &mut SdkLogRecord, instrumentation: &InstrumentationScope) {
+        let handed = match self.queue.state.policy {
+            Backpressure::DropOld => self.queue.hold(data, instrumentation),
+            Backpressure::DropNew | Backpressure::Block => self.queue.admit(),
+        };
+        if handed {
+            self.inner.emit(data, instrumentation);
+        }
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        let result = self.inner.shutdown_with_timeout(timeout);
+        self.queue.close();
+        result
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+/// A [`LogExporter`] releasing the room of the records exported from a
+/// [`BoundedQueue`], and exporting its held records in place of the batches with
+/// [`Backpressure::DropOld`].
+#[derive(Debug)]
+pub struct BoundedExporter<E> {
+    inner: E,
+    queue: BoundedQueue,
+}
+
+impl<E: LogExporter> LogExporter for BoundedExporter<E> {
+    async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
+        let records = batch.iter().count();
+        if self.queue.state.policy != Backpressure::DropOld {
+            let result = self.inner.export(batch).await;
+            self.queue.release(records, 0);
+            return result;
+        }
+
+        let held = self.queue.take_held(records);
+        let result = if held.is_empty() {
+            Ok(())
+        } else {
+            let refs: Vec<_> = held
+                .iter()
+                .map(|(record, instrumentation)| (record, instrumentation))
+                .collect();
+            self.inner.export(LogBatch::new(&refs)).await
+        };
+        self.queue.release(0, held.len());
+        result
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::logs::{AnyValue, LogRecord, Logger, LoggerProvider};
+    use opentelemetry_sdk::logs::{BatchConfigBuilder, BatchLogProcessor, SdkLoggerProvider};
+    use std::thread;
+
+    /// An exporter holding its batches until released.
+    #[derive(Debug, Clone, Default)]
+    struct Slow {
+        exported: Arc<Mutex<Vec<String>>>,
+        released: Arc<(Mutex<bool>, Condvar)>,
+    }
+
+    impl Slow {
+        fn release(&self) {
+            *self.released.0.lock().unwrap() = true;
+            self.released.1.notify_all();
+        }
+
+        fn exported(&self) -> Vec<String> {
+            self.exported.lock().unwrap().clone()
+        }
+    }
+
+    impl LogExporter for Slow {
+        async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
+            let (released, cvar) = &*self.released;
+            drop(cvar.wait_while(released.lock().unwrap(), |released| !*released));
+            self.exported.lock().unwrap().extend(batch.iter().map(
+                |(record, _)| match record.body() {
+                    Some(AnyValue::String(body)) => body.to_string(),
+                    _ => String::new(),
+                },
+            ));
+            Ok(())
+        }
+    }
+
+    fn pipeline(queue: BoundedQueue) -> (SdkLoggerProvider, Slow) {
+        let exporter = Slow::default();
+        let batch = BatchLogProcessor::builder(queue.exporter(exporter.clone()))
+            .with_batch_config(
+                BatchConfigBuilder::default()
+                    .with_scheduled_delay(Duration::from_secs(3600))
+                    .with_max_queue_size(queue.batch_queue_size())
+                    .with_max_export_batch_size(queue.batch_queue_size())
+                    .build(),
+            )
+            .build();
+        let provider = SdkLoggerProvider::builder()
+            .with_log_processor(queue.processor(batch))
+            .build();
+        (provider, exporter)
+    }
+
+    fn emit(provider: &SdkLoggerProvider, body: &str) {
+        let logger = provider.logger("orders");
+        let mut record = logger.create_log_record();
+        record.set_body(AnyValue::String(body.to_string().into()));
+        logger.emit(record);
+    }
+
+    #[test]
+    fn drop_new_drops_the_records_of_a_full_queue() {
+        let queue = BoundedQueue::new(2, Backpressure::DropNew);
+        let (provider, exporter) = pipeline(queue.clone());
+
+        for body in ["1", "2", "3", "4"] {
+            emit(&provider, body);
+        }
+        assert_eq!(queue.dropped(), 2);
+        assert_eq!(queue.len(), 2);
+
+        exporter.release();
+        provider.force_flush().unwrap();
+        assert_eq!(exporter.exported(), ["1", "2"]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn drop_old_drops_the_oldest_records() {
+        let queue = BoundedQueue::new(2, Backpressure::DropOld);
+        let (provider, exporter) = pipeline(queue.clone());
+
+        // The newest records survive however long the overflow lasts
+        let bodies: Vec<String> = (1..=10).map(|body| body.to_string()).collect();
+        for body in &bodies {
+            emit(&provider, body);
+        }
+        assert_eq!(queue.dropped(), 8);
+        assert_eq!(queue.len(), 2);
+
+        exporter.release();
+        provider.force_flush().unwrap();
+        assert_eq!(exporter.exported(), ["9", "10"]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn block_waits_for_room() {
+        let queue = BoundedQueue::new(2, Backpressure::Block);
+        let (provider, exporter) = pipeline(queue.clone());
+        emit(&provider, "1");
+        emit(&provider, "2");
+
+        let returned = Arc::new(AtomicBool::new(false));
+        let caller = thread::spawn({
+            let (provider, returned) = (provider.clone(), returned.clone());
+            move || {
+                emit(&provider, "3");
+                returned.store(true, Ordering::SeqCst);
+            }
+        });
+        thread::sleep(Duration::from_millis(200));
+        assert!(!returned.load(Ordering::SeqCst));
+
+        // The flush exports the first two records, making room for the third
+        exporter.release();
+        provider.force_flush().unwrap();
+        caller.join().unwrap();
+        provider.force_flush().unwrap();
+        assert_eq!(queue.dropped(), 0);
+        assert_eq!(exporter.exported(), ["1", "2", "3"]);
+    }
+
+    #[test]
+    fn block_drops_the_record_after_the_timeout() {
+        let queue = BoundedQueue::new(2, Backpressure::Block)
+            .with_block_timeout(Duration::from_millis(100));
+        let (provider, exporter) = pipeline(queue.clone());
+        emit(&provider, "1");
+        emit(&provider, "2");
+
+        // Nothing exports the queue, e.g. the runtime of the transport is stalled
+        let start = Instant::now();
+        emit(&provider, "3");
+        let waited = start.elapsed();
+        assert!(waited >= Duration::from_millis(100));
+        assert!(waited < Duration::from_secs(5));
+        assert_eq!(queue.dropped(), 1);
+
+        exporter.release();
+        provider.force_flush().unwrap();
+        assert_eq!(exporter.exported(), ["1", "2"]);
+    }
+
+    #[test]
+    fn block_stops_waiting_once_shut_down() {
+        let queue = BoundedQueue::new(1, Backpressure::Block);
+        let (provider, exporter) = pipeline(queue.clone());
+        emit(&provider, "1");
+        exporter.release();
+        provider.shutdown().unwrap();
+
+        // A full queue that nothing will export anymore
+        queue.state.lock().records = 1;
+        let start = Instant::now();
+        assert!(!queue.admit());
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(queue.dropped(), 1);
+    }
+
+    #[test]
+    fn drop_old_keeps_the_records_emitted_during_an_export() {
+        let queue = BoundedQueue::new(2, Backpressure::DropOld);
+        let (provider, exporter) = pipeline(queue.clone());
+        emit(&provider, "1");
+        emit(&provider, "2");
+
+        // The exporter waits while the first batch is being exported
+        let flush = thread::spawn({
+            let provider = provider.clone();
+            move || provider.force_flush()
+        });
+        thread::sleep(Duration::from_millis(100));
+        for body in ["3", "4", "5"] {
+            emit(&provider, body);
+        }
+        assert_eq!(queue.len(), 4);
+
+        exporter.release();
+        flush.join().unwrap().unwrap();
+        provider.force_flush().unwrap();
+        assert_eq!(exporter.exported(), ["1", "2", "4", "5"]);
+        assert_eq!(queue.dropped(), 1);
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Baggage Attributes
+//!
+//! This module attaches OpenTelemetry baggage entries to exported log records.
+//! Request-scoped metadata such as a tenant or request id is commonly propagated as
+//! baggage; with the configured keys, every record emitted while that baggage is in the
+//! current OpenTelemetry `Context` carries it as attributes, without repeating it as a
+//! field on each event.
+
+use opentelemetry::{
+    Context, InstrumentationScope, Key,
+    baggage::BaggageExt,
+    logs::{AnyValue, LogRecord},
+};
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    logs::{LogProcessor, SdkLogRecord},
+};
+use std::time::Duration;
+
+/// A [`LogProcessor`] that copies selected baggage entries onto records before
+/// delegating to another processor.
+///
+/// Baggage is read from the current OpenTelemetry `Context` of the emitting thread.
+/// Keys absent from the baggage are skipped, and an attribute already set by the event
+/// itself is never overwritten.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::baggage::BaggageProcessor;
+/// use opentelemetry_sdk::logs::{SdkLoggerProvider, SimpleLogProcessor};
+///
+/// let processor = BaggageProcessor::new(
+///     SimpleLogProcessor::new(opentelemetry_stdout::LogExporter::default()),
+///     vec!["tenant.id".to_string(), "request.id".to_string()],
+/// );
+/// let provider = SdkLoggerProvider::builder()
+///     .with_log_processor(processor)
+///     .build();
+/// ```
+#[derive(Debug)]
+pub struct BaggageProcessor<P> {
+    inner: P,
+    keys: Vec<Key>,
+}
+
+impl<P> BaggageProcessor<P> {
+    /// Wraps `inner`, attaching the baggage entries named by `keys` to every record.
+    pub fn new(inner: P, keys: Vec<String>) -> Self {
+        BaggageProcessor {
+            inner,
+            keys: keys.into_iter().map(Key::from).collect(),
+        }
+    }
+}
+
+impl<P: LogProcessor> LogProcessor for BaggageProcessor<P> {
+    fn emit(&self, data: &mut SdkLogRecord, instrumentation: &InstrumentationScope) {
+        if !self.keys.is_empty() {
+            Context::map_current(|cx| {
+                let baggage = cx.baggage();
+                for key in &self.keys {
+                    let Some(value) = baggage.get(key) else {
+                        continue;
+                    };
+                    if data.attributes_iter().any(|(existing, _)| existing == key) {
+                        continue;
+                    }
+                    data.add_attribute(key.clone(), AnyValue::String(value.clone()));
+                }
+            });
+        }
+        self.inner.emit(data, instrumentation);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+#[cfg(all(test, any(feature = "stdout", feature = "otlp")))]
+mod tests {
+    use super::*;
+    use crate::{
+        config::{LoggingConfig, LoggingConfigs},
+        test::init_with,
+    };
+    use opentelemetry::KeyValue;
+
+    fn attribute(record: &SdkLogRecord, key: &str) -> Option<String> {
+        record
+            .attributes_iter()
+            .find(|(existing, _)| existing.as_str() == key)
+            .map(|(_, value)| match value {
+                AnyValue::String(value) => value.to_string(),
+                other => format!("{other:?}"),
+            })
+    }
+
+    fn init() -> crate::test::TestGuard {
+        init_with(LoggingConfig {
+            level: Some("info".to_string()),
+            logging: LoggingConfigs {
+                baggage_keys: vec!["tenant.id".to_string(), "request.id".to_string()],
+                ..LoggingConfigs::default()
+            },
+            ..LoggingConfig::default()
+        })
+    }
+
+    #[test]
+    fn attaches_the_configured_baggage_keys() {
+        let logs = init();
+        {
+            let _cx = Context::current_with_baggage(vec![
+                KeyValue::new("tenant.id", "acme"),
+                KeyValue::new("session", "s-1"),
+            ])
+            .attach();
+            tracing::info!("order placed");
+        }
+
+        let exported = logs.exported();
+        assert_eq!(exported.len(), 1);
+        assert_eq!(
+            attribute(&exported[0], "tenant.id").as_deref(),
+            Some("acme")
+        );
+        assert_eq!(attribute(&exported[0], "request.id"), None);
+        assert_eq!(attribute(&exported[0], "session"), None);
+    }
+
+    #[test]
+    fn absent_baggage_leaves_the_record_alone() {
+        let logs = init();
+        tracing::info!("order placed");
+
+        let exported = logs.exported();
+        assert_eq!(exported.len(), 1);
+        assert_eq!(attribute(&exported[0], "tenant.id"), None);
+    }
+
+    #[test]
+    fn event_fields_win_over_the_baggage() {
+        let logs = init();
+        {
+            let _cx =
+                Context::current_with_baggage(vec![KeyValue::new("tenant.id", "acme")]).attach();
+            tracing::info!(tenant.id = "globex", "order placed");
+        }
+
+        let exported = logs.exported();
+        assert_eq!(exported.len(), 1);
+        assert_eq!(
+            attribute(&exported[0], "tenant.id").as_deref(),
+            Some("globex")
+        );
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Bare Console Exporter
+//!
+//! This module provides a dependency-light logging setup for applications that only
+//! want console output. It installs the same console formatting and filtering as the
+//! other exporters, but doesn't depend on OpenTelemetry at all: no logger provider is
+//! created and nothing is exported.
+//!
+//! Build the crate with `default-features = false, features = ["bare"]` to leave the
+//! OpenTelemetry crates out of the dependency tree entirely.
+
+use crate::{
+    config::LoggingConfigs,
+    errors::LoggingError,
+    exporters::layers::{ConsoleOptions, console_layers, install_subscriber},
+};
+use configs::app::AppConfigs;
+
+/// Installs and configures the bare console exporter.
+///
+/// This function sets up a logging system that formats and outputs logs to the console
+/// without any OpenTelemetry integration. It configures the formatting based on the
+/// environment:
+/// - In local environments, logs are formatted in a pretty, human-readable format
+/// - In non-local environments, logs are formatted as JSON in Bunyan format
+///
+/// # Returns
+///
+/// * `Result<(), LoggingError>` - On success, returns `()` since there's no logger
+///   provider to hand back. On failure, returns a `LoggingError`.
+///
+/// # Errors
+///
+/// Returns `LoggingError::SubscriberInstallError` if the tracing subscriber can't be
+/// set, e.g. because another global subscriber is already set.
+///
+/// Returns `LoggingError::AlreadyInitializedError` if logging is already installed, by
+/// this function, [`crate::provider::install`] or the exporter of another module.
+///
+/// # Examples
+///
+/// ```no_run
+/// use logging::exporters::bare;
+///
+/// fn main() {
+///     bare::install().expect("Failed to set up logging");
+///     // Now logs will be written to the console
+///     tracing::info!("Application started");
+/// }
+/// ```
+pub fn install() -> Result<(), LoggingError> {
+    crate::provider::install_exclusive("bare", || {
+        install_with(&AppConfigs::new(), &LoggingConfigs::new())
+    })
+}
+
+/// Installs the console-only logging system with the given settings.
+pub(crate) fn install_with(
+    app_cfgs: &AppConfigs,
+    log_cfgs: &LoggingConfigs,
+) -> Result<(), LoggingError> {
+    let layers = console_layers(app_cfgs, log_cfgs, ConsoleOptions::default())?;
+    install_subscriber(app_cfgs, log_cfgs, layers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::{LoggingConfig, RenderMode, StructuredFormat},
+        test::init_with,
+    };
+
+    #[test]
+    fn formats_the_console_as_configured() {
+        let logs = init_with(LoggingConfig {
+            level: Some("info".to_string()),
+            logging: LoggingConfigs {
+                render_mode: Some(RenderMode::Machine),
+                structured_format: Some(StructuredFormat::Ndjson),
+                ..LoggingConfigs::default()
+            },
+            ..LoggingConfig::default()
+        });
+
+        tracing::info!(order_id = 42, "order charged");
+
+        let record: serde_json::Value = logs
+            .output()
+            .lines()
+            .find_map(|line| serde_json::from_str(line).ok())
+            .expect("an NDJSON record");
+        assert_eq!(record["message"], "order charged");
+        assert_eq!(record["order_id"], 42);
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Structured Bodies
+//!
+//! This module turns the body of exported log records into a map of the event fields.
+//! The tracing bridge exports the message as a string body and the fields as
+//! attributes, but some backends only index the body: with a structured body, the
+//! fields of an event can be queried there too.
+//!
+//! The body is an OTLP `kvlist` holding the `message` and every event field. The
+//! attributes are left as they are, so queries on them keep working. It's enabled with
+//! `LOG_OTLP_STRUCTURED_BODY`; the string body stays the default for compatibility.
+
+use opentelemetry::{
+    InstrumentationScope, Key,
+    logs::{AnyValue, LogRecord},
+};
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    logs::{LogProcessor, SdkLogRecord},
+};
+use std::{collections::HashMap, time::Duration};
+
+/// Key of the original body in a structured body.
+pub const MESSAGE_KEY: &str = "message";
+
+/// A [`LogProcessor`] that replaces the body of records with a map of their message and
+/// attributes before delegating to another processor.
+///
+/// The map is built from the attributes present when the record reaches the processor:
+/// wrapping the processors that add attributes of their own, such as the correlation
+/// id, keeps it to the event fields. When disabled, the records are only delegated.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::body::StructuredBodyProcessor;
+/// use opentelemetry::{Key, logs::AnyValue};
+/// use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
+/// use opentelemetry_sdk::logs::{InMemoryLogExporter, SdkLoggerProvider, SimpleLogProcessor};
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let exporter = InMemoryLogExporter::default();
+/// let provider = SdkLoggerProvider::builder()
+///     .with_log_processor(StructuredBodyProcessor::new(
+///         SimpleLogProcessor::new(exporter.clone()),
+///         true,
+///     ))
+///     .build();
+/// let subscriber =
+///     tracing_subscriber::registry().with(OpenTelemetryTracingBridge::new(&provider));
+///
+/// tracing::subscriber::with_default(subscriber, || {
+///     tracing::info!(order_id = 42, status = "paid", "order charged");
+/// });
+///
+/// let logs = exporter.get_emitted_logs().unwrap();
+/// let Some(AnyValue::Map(body)) = logs[0].record.body() else {
+///     panic!("the body isn't a kvlist");
+/// };
+/// assert_eq!(body[&Key::from("message")], AnyValue::from("order charged"));
+/// assert_eq!(body[&Key::from("order_id")], AnyValue::Int(42));
+/// assert_eq!(body[&Key::from("status")], AnyValue::from("paid"));
+///
+/// // The fields stay attributes too
+/// assert!(logs[0].record.attributes_iter().any(|(key, _)| key.as_str() == "order_id"));
+/// ```
+#[derive(Debug)]
+pub struct StructuredBodyProcessor<P> {
+    inner: P,
+    enabled: bool,
+}
+
+impl<P> StructuredBodyProcessor<P> {
+    /// Wraps `inner`, structuring the body of the records if `enabled`.
+    pub fn new(inner: P, enabled: bool) -> Self {
+        StructuredBodyProcessor { inner, enabled }
+    }
+}
+
+impl<P: LogProcessor> LogProcessor for StructuredBodyProcessor<P> {
+    fn emit(&self, data: &mut SdkLogRecord, instrumentation: &InstrumentationScope) {
+        if self.enabled {
+            let mut body: HashMap<Key, AnyValue> = data.attributes_iter().cloned().collect();
+            if let Some(message) = data.body() {
+                body.insert(Key::from_static_str(MESSAGE_KEY), message.clone());
+            }
+            data.set_body(AnyValue::Map(Box::new(body)));
+        }
+        self.inner.emit(data, instrumentation);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
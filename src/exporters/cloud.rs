@@ -0,0 +1,253 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Cloud Resource Detection
+//!
+//! This module detects the cloud provider the process runs on by querying the instance
+//! metadata service, and describes it with the OpenTelemetry `cloud.*` resource
+//! attributes. AWS (IMDSv2) and GCP are supported.
+//!
+//! Detection is bounded by a single timeout shared by every request, so it never delays
+//! startup for longer than configured. Any failure, including running outside of a
+//! cloud, silently yields no attributes.
+
+use opentelemetry::KeyValue;
+use serde_json::Value;
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpStream},
+    time::{Duration, Instant},
+};
+
+/// Address of the instance metadata service, shared by AWS and GCP.
+const METADATA_ADDR: ([u8; 4], u16) = ([169, 254, 169, 254], 80);
+
+/// Detects the `cloud.provider`, `cloud.region` and `cloud.account.id` attributes.
+///
+/// Returns an empty list when no provider could be detected within `timeout`.
+pub(crate) fn detect(timeout: Duration) -> Vec<KeyValue> {
+    detect_from(SocketAddr::from(METADATA_ADDR), timeout)
+}
+
+/// Detects the cloud attributes from the metadata service listening on `addr`.
+fn detect_from(addr: SocketAddr, timeout: Duration) -> Vec<KeyValue> {
+    let client = MetadataClient {
+        addr,
+        deadline: Instant::now() + timeout,
+    };
+
+    detect_aws(&client)
+        .or_else(|| detect_gcp(&client))
+        .unwrap_or_default()
+}
+
+/// Reads the AWS instance identity document through IMDSv2.
+fn detect_aws(client: &MetadataClient) -> Option<Vec<KeyValue>> {
+    let token = client.request(
+        "PUT",
+        "/latest/api/token",
+        &[("X-aws-ec2-metadata-token-ttl-seconds", "60")],
+    )?;
+    let document = client.request(
+        "GET",
+        "/latest/dynamic/instance-identity/document",
+        &[("X-aws-ec2-metadata-token", token.trim())],
+    )?;
+
+    let document: Value = serde_json::from_str(&document).ok()?;
+    let region = document.get("region")?.as_str()?;
+    let account_id = document.get("accountId")?.as_str()?;
+
+    Some(vec![
+        KeyValue::new("cloud.provider", "aws"),
+        KeyValue::new("cloud.region", region.to_string()),
+        KeyValue::new("cloud.account.id", account_id.to_string()),
+    ])
+}
+
+/// Reads the GCP project and zone from the metadata server.
+fn detect_gcp(client: &MetadataClient) -> Option<Vec<KeyValue>> {
+    let headers = [("Metadata-Flavor", "Google")];
+    let project_id = client.request("GET", "/computeMetadata/v1/project/project-id", &headers)?;
+    // Formatted as `projects/<number>/zones/<region>-<zone letter>`
+    let zone = client.request("GET", "/computeMetadata/v1/instance/zone", &headers)?;
+    let zone = zone.trim().rsplit('/').next()?;
+    let (region, _) = zone.rsplit_once('-')?;
+
+    Some(vec![
+        KeyValue::new("cloud.provider", "gcp"),
+        KeyValue::new("cloud.region", region.to_string()),
+        KeyValue::new("cloud.account.id", project_id.trim().to_string()),
+    ])
+}
+
+/// A minimal HTTP/1.0 client for the metadata service.
+struct MetadataClient {
+    addr: SocketAddr,
+    deadline: Instant,
+}
+
+impl MetadataClient {
+    /// Sends a request and returns the body of a successful response.
+    fn request(&self, method: &str, path: &str, headers: &[(&str, &str)]) -> Option<String> {
+        let mut stream = TcpStream::connect_timeout(&self.addr, self.remaining()?).ok()?;
+        stream.set_write_timeout(Some(self.remaining()?)).ok()?;
+
+        let mut request = format!("{method} {path} HTTP/1.0\r\nHost: {}\r\n", self.addr.ip());
+        for (name, value) in headers {
+            request.push_str(&format!("{name}: {value}\r\n"));
+        }
+        request.push_str("Content-Length: 0\r\n\r\n");
+        stream.write_all(request.as_bytes()).ok()?;
+
+        let mut response = Vec::new();
+        let mut chunk = [0; 4096];
+        loop {
+            stream.set_read_timeout(Some(self.remaining()?)).ok()?;
+            match stream.read(&mut chunk).ok()? {
+                0 => break,
+                read => response.extend_from_slice(&chunk[..read]),
+            }
+        }
+
+        let response = String::from_utf8(response).ok()?;
+        let (head, body) = response.split_once("\r\n\r\n")?;
+        let status = head.split_whitespace().nth(1)?;
+        (status == "200").then(|| body.to_string())
+    }
+
+    /// Returns the time left before the deadline, if any.
+    fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .checked_duration_since(Instant::now())
+            .filter(|remaining| !remaining.is_zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{net::TcpListener, thread};
+
+    /// Serves the metadata requests with `respond`, given the request line and headers,
+    /// which returns the status and body of the response. Returns the address served.
+    fn serve(respond: fn(&str) -> (u16, &'static str)) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request = Vec::new();
+                let mut chunk = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    match stream.read(&mut chunk) {
+                        Ok(0) | Err(_) => break,
+                        Ok(read) => request.extend_from_slice(&chunk[..read]),
+                    }
+                }
+                let (status, body) = respond(&String::from_utf8_lossy(&request));
+                let _ = write!(stream, "HTTP/1.0 {status} -\r\n\r\n{body}");
+            }
+        });
+        addr
+    }
+
+    fn attributes(addr: SocketAddr) -> Vec<(String, String)> {
+        detect_from(addr, Duration::from_secs(2))
+            .into_iter()
+            .map(|kv| (kv.key.to_string(), kv.value.to_string()))
+            .collect()
+    }
+
+    fn expected(provider: &str, region: &str, account_id: &str) -> Vec<(String, String)> {
+        [
+            ("cloud.provider", provider),
+            ("cloud.region", region),
+            ("cloud.account.id", account_id),
+        ]
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .to_vec()
+    }
+
+    #[test]
+    fn detects_aws_through_imdsv2() {
+        let addr = serve(|request| {
+            if request.starts_with("PUT /latest/api/token ") {
+                (200, "token-1\n")
+            } else if request.starts_with("GET /latest/dynamic/instance-identity/document ")
+                && request.contains("X-aws-ec2-metadata-token: token-1\r\n")
+            {
+                (200, r#"{"region":"eu-west-3","accountId":"123456789012"}"#)
+            } else {
+                (401, "")
+            }
+        });
+
+        assert_eq!(
+            attributes(addr),
+            expected("aws", "eu-west-3", "123456789012")
+        );
+    }
+
+    #[test]
+    fn detects_gcp_when_aws_is_absent() {
+        let addr = serve(|request| {
+            if !request.contains("Metadata-Flavor: Google\r\n") {
+                (404, "")
+            } else if request.starts_with("GET /computeMetadata/v1/project/project-id ") {
+                (200, "acme-prod")
+            } else if request.starts_with("GET /computeMetadata/v1/instance/zone ") {
+                (200, "projects/42/zones/europe-west1-b")
+            } else {
+                (404, "")
+            }
+        });
+
+        assert_eq!(
+            attributes(addr),
+            expected("gcp", "europe-west1", "acme-prod")
+        );
+    }
+
+    #[test]
+    fn malformed_responses_yield_nothing() {
+        let addr = serve(|request| {
+            if request.starts_with("PUT ") {
+                (200, "token-1")
+            } else {
+                (200, "not json")
+            }
+        });
+
+        assert!(attributes(addr).is_empty());
+    }
+
+    #[test]
+    fn unreachable_service_yields_nothing() {
+        // Nothing listens on the port once the listener is dropped
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+
+        assert!(attributes(addr).is_empty());
+    }
+
+    #[test]
+    fn unresponsive_service_is_bounded_by_the_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        // Accepts the connections but never responds
+        thread::spawn(move || {
+            let streams: Vec<_> = listener.incoming().flatten().collect();
+            drop(streams);
+        });
+
+        let start = Instant::now();
+        let attributes = detect_from(addr, Duration::from_millis(200));
+
+        assert!(attributes.is_empty());
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Level Colors
+//!
+//! This module provides the colors of the level token in the colored console formats.
+//! The built-in formatters of `tracing-subscriber` paint levels with a fixed palette,
+//! whose yellow warnings and red errors are hard to tell apart for red/green colorblind
+//! readers.
+//!
+//! [`LevelColors`] maps levels to ANSI colors, defaulting to the built-in palette, and
+//! the console layers repaint the level token of their colored output with it. Only the
+//! level token is affected: the rest of the line keeps the colors of its format.
+
+use crate::errors::LoggingError;
+use std::{collections::BTreeMap, fmt};
+use tracing::{Event, Level};
+use tracing_subscriber::{
+    Registry,
+    fmt::{FmtContext, FormatEvent, FormatFields, format::Writer},
+};
+
+/// Sequence resetting the style after a painted token.
+const RESET: &str = "\x1b[0m";
+
+/// The colors of the level token, as ANSI SGR parameters per level.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::colors::LevelColors;
+/// use tracing::Level;
+///
+/// let colors = LevelColors::from_entries(&[
+///     "warn=blue".to_string(),
+///     "error=38;5;208".to_string(),
+/// ])
+/// .unwrap();
+/// assert_eq!(colors.code(Level::WARN), "34");
+/// assert_eq!(colors.code(Level::ERROR), "38;5;208");
+///
+/// // Levels without an entry keep the built-in palette
+/// assert_eq!(colors.code(Level::INFO), "32");
+/// assert!(LevelColors::from_entries(&["warn=beige".to_string()]).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LevelColors {
+    /// Colors replacing the built-in ones.
+    overrides: BTreeMap<Level, String>,
+}
+
+impl LevelColors {
+    /// Parses `level=color` entries into level colors.
+    ///
+    /// A color is one of `black`, `red`, `green`, `yellow`, `blue`, `magenta`, `cyan`
+    /// and `white`, optionally prefixed with `bright-`, or raw SGR parameters such as
+    /// `1;34` or `38;5;208`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LoggingError::InvalidConfigError` if an entry is malformed or a color is
+    /// unknown.
+    pub fn from_entries(entries: &[String]) -> Result<Self, LoggingError> {
+        let overrides = entries
+            .iter()
+            .map(|entry| {
+                let invalid = || {
+                    LoggingError::InvalidConfigError(format!(
+                        "invalid level color `{entry}`, expected level=color"
+                    ))
+                };
+
+                let (level, color) = entry.split_once('=').ok_or_else(invalid)?;
+                let level: Level = level.trim().parse().map_err(|_| invalid())?;
+                Ok((level, color_code(color.trim())?))
+            })
+            .collect::<Result<_, LoggingError>>()?;
+        Ok(Self { overrides })
+    }
+
+    /// Returns the SGR parameters of the level token of `level`.
+    pub fn code(&self, level: Level) -> &str {
+        self.overrides
+            .get(&level)
+            .map(String::as_str)
+            .unwrap_or_else(|| default_code(level))
+    }
+}
+
+/// Returns the SGR parameters of a color name, or the parameters themselves.
+fn color_code(color: &str) -> Result<String, LoggingError> {
+    let (base, name) = match color.strip_prefix("bright-") {
+        Some(name) => (90, name),
+        None => (30, color),
+    };
+    let offset = match name {
+        "black" => Some(0),
+        "red" => Some(1),
+        "green" => Some(2),
+        "yellow" => Some(3),
+        "blue" => Some(4),
+        "magenta" => Some(5),
+        "cyan" => Some(6),
+        "white" => Some(7),
+        _ => None,
+    };
+    if let Some(offset) = offset {
+        return Ok((base + offset).to_string());
+    }
+
+    let is_sgr = !color.is_empty()
+        && color
+            .split(';')
+            .all(|param| !param.is_empty() && param.parse::<u8>().is_ok());
+    if is_sgr {
+        Ok(color.to_string())
+    } else {
+        Err(LoggingError::InvalidConfigError(format!(
+            "unknown color `{color}`, expected a color name or SGR parameters"
+        )))
+    }
+}
+
+/// The SGR parameters of the built-in palette of `tracing-subscriber`.
+fn default_code(level: Level) -> &'static str {
+    match level {
+        Level::TRACE => "35",
+        Level::DEBUG => "34",
+        Level::INFO => "32",
+        Level::WARN => "33",
+        Level::ERROR => "31",
+    }
+}
+
+/// The level token as written by the built-in formatters, padded to five characters.
+fn token(level: Level) -> &'static str {
+    match level {
+        Level::TRACE => "TRACE",
+        Level::DEBUG => "DEBUG",
+        Level::INFO => " INFO",
+        Level::WARN => " WARN",
+        Level::ERROR => "ERROR",
+    }
+}
+
+/// Formats events with another format, repainting their level token with the
+/// configured colors.
+///
+/// The inner format must enable its colors with `Format::with_ansi`, as it's given an
+/// intermediate writer. Uncolored output, and levels keeping the built-in color, are
+/// written directly.
+#[derive(Clone)]
+pub(crate) struct Colored<F> {
+    inner: F,
+    colors: LevelColors,
+}
+
+impl<F> Colored<F> {
+    pub(crate) fn new(inner: F, colors: LevelColors) -> Self {
+        Self { inner, colors }
+    }
+}
+
+impl<N, F> FormatEvent<Registry, N> for Colored<F>
+where
+    N: for<'a> FormatFields<'a> + 'static,
+    F: FormatEvent<Registry, N>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, Registry, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let level = *event.metadata().level();
+        let code = self.colors.code(level);
+        if !writer.has_ansi_escapes() || code == default_code(level) {
+            return self.inner.format_event(ctx, writer, event);
+        }
+
+        // The inner format colors the line itself, whatever its writer
+        let mut line = String::new();
+        self.inner
+            .format_event(ctx, Writer::new(&mut line), event)?;
+        let token = token(level);
+        let line = line.replacen(
+            &format!("\x1b[{}m{token}{RESET}", default_code(level)),
+            &format!("\x1b[{code}m{token}{RESET}"),
+            1,
+        );
+        writer.write_str(&line)
+    }
+}
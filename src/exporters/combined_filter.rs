@@ -0,0 +1,288 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Combined Filtering
+//!
+//! This module evaluates the filters of the crate in a single per-layer [`Filter`].
+//! Stacked with [`FilterExt::and`](tracing_subscriber::filter::FilterExt::and), the
+//! target, silenced target, message, field and sampling filters each take a step of
+//! every `enabled` and `event_enabled` call, and every combinator in between too. On hot
+//! paths, that cost grows with each filter configured.
+//!
+//! [`CombinedFilter`] holds the same rules and checks them in one pass, the cheapest
+//! first: the level and target of the callsite, then the silenced targets, then the
+//! message denylist and the field conditions, which visit the event, and finally the
+//! target sampling, so that only the events passing every other rule are counted. The
+//! rules that aren't configured are skipped. It takes the same decisions as the stacked
+//! filters.
+
+use crate::{
+    exporters::{
+        field_filter::FieldFilter, message_filter::MessageFilter, sampling::TargetSamplingFilter,
+    },
+    scoped::ScopedLevelFilter,
+};
+use tracing::{Event, Metadata, subscriber::Interest};
+use tracing_subscriber::{
+    filter::{LevelFilter, Targets},
+    layer::{Context, Filter},
+};
+
+/// A per-layer filter applying the target, silenced target, message, field and sampling
+/// rules in a single pass.
+///
+/// The target filter is relaxed by the [`crate::scoped_level`] of the emitting thread,
+/// but the silenced targets aren't.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::{
+///     combined_filter::CombinedFilter,
+///     filters::{self, LevelFilter, Targets},
+///     message_filter::MessageFilter,
+/// };
+/// use tracing_subscriber::{Layer, layer::SubscriberExt};
+///
+/// let silenced = filters::silence_targets(
+///     Targets::new().with_default(LevelFilter::TRACE),
+///     &["leaky_dep".to_string()],
+/// );
+/// let filter = CombinedFilter::new(filters::target_filters("info"))
+///     .with_silenced(Some(silenced))
+///     .with_messages(MessageFilter::new(&["^connection reset".to_string()]).unwrap());
+/// let subscriber = tracing_subscriber::registry()
+///     .with(tracing_subscriber::fmt::layer().with_filter(filter));
+/// ```
+#[derive(Debug)]
+pub struct CombinedFilter {
+    targets: ScopedLevelFilter<Targets>,
+    silenced: Option<Targets>,
+    messages: MessageFilter,
+    fields: FieldFilter,
+    sampling: TargetSamplingFilter,
+}
+
+impl CombinedFilter {
+    /// Creates a filter enabling what `targets` enables, without any other rule.
+    pub fn new(targets: Targets) -> Self {
+        CombinedFilter {
+            targets: ScopedLevelFilter::new(targets),
+            silenced: None,
+            messages: MessageFilter::default(),
+            fields: FieldFilter::default(),
+            sampling: TargetSamplingFilter::default(),
+        }
+    }
+
+    /// Disables what `silenced` doesn't enable, whatever the scoped level of the thread.
+    pub fn with_silenced(mut self, silenced: Option<Targets>) -> Self {
+        self.silenced = silenced;
+        self
+    }
+
+    /// Drops the events whose message matches the denylist of `messages`.
+    pub fn with_messages(mut self, messages: MessageFilter) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    /// Keeps the events meeting the field conditions of `fields`.
+    pub fn with_fields(mut self, fields: FieldFilter) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Samples the events of the targets of `sampling`, once every other rule passed.
+    pub fn with_sampling(mut self, sampling: TargetSamplingFilter) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Returns `true` when a rule needs to see the events themselves.
+    fn filters_events(&self) -> bool {
+        !self.messages.is_empty() || !self.fields.is_empty() || !self.sampling.is_empty()
+    }
+}
+
+impl<S> Filter<S> for CombinedFilter {
+    fn enabled(&self, meta: &Metadata<'_>, cx: &Context<'_, S>) -> bool {
+        self.targets.enabled(meta, cx)
+            && self
+                .silenced
+                .as_ref()
+                .is_none_or(|silenced| silenced.would_enable(meta.target(), meta.level()))
+    }
+
+    fn callsite_enabled(&self, meta: &'static Metadata<'static>) -> Interest {
+        let interest = Filter::<S>::callsite_enabled(&self.targets, meta);
+        if interest.is_never() {
+            return interest;
+        }
+        if let Some(silenced) = &self.silenced
+            && !silenced.would_enable(meta.target(), meta.level())
+        {
+            return Interest::never();
+        }
+        if self.filters_events() {
+            return Interest::sometimes();
+        }
+        interest
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Filter::<S>::max_level_hint(&self.targets)
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, cx: &Context<'_, S>) -> bool {
+        self.messages.allows(event)
+            && self.fields.allows(event)
+            && Filter::<S>::event_enabled(&self.sampling, event, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{exporters::filters, test::Capture};
+    use std::{collections::BTreeMap, io, time::Instant};
+    use tracing_subscriber::{
+        Layer, Registry, filter::FilterExt, fmt::MakeWriter, layer::SubscriberExt,
+    };
+
+    fn targets() -> Targets {
+        filters::target_filters("info").with_target("my_app::db", LevelFilter::DEBUG)
+    }
+
+    fn silenced() -> Targets {
+        filters::silence_targets(
+            Targets::new().with_default(LevelFilter::TRACE),
+            &["leaky_dep".to_string()],
+        )
+    }
+
+    fn fields() -> FieldFilter {
+        FieldFilter::new(&[], &["http.status<500".to_string()]).unwrap()
+    }
+
+    fn messages() -> MessageFilter {
+        MessageFilter::new(&["^connection reset".to_string()]).unwrap()
+    }
+
+    fn sampling() -> TargetSamplingFilter {
+        let rates = BTreeMap::from([("my_app::poller".to_string(), "0.1".to_string())]);
+        TargetSamplingFilter::new(&rates).unwrap()
+    }
+
+    /// Emits `rounds` rounds of nine events, of which `filter` keeps some.
+    fn emit<F, W>(filter: F, writer: W, rounds: usize)
+    where
+        F: Filter<Registry> + Send + Sync + 'static,
+        W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+    {
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .without_time()
+                .with_ansi(false)
+                .with_writer(writer)
+                .with_filter(filter),
+        );
+        tracing::subscriber::with_default(subscriber, || {
+            for i in 0..rounds {
+                tracing::debug!(target: "my_app::db", i, "query");
+                tracing::debug!(target: "my_app::api", i, "hidden below info");
+                tracing::info!(target: "my_app::api", i, http.status = 200, "served");
+                tracing::info!(target: "my_app::api", i, http.status = 503, "failed");
+                tracing::warn!(target: "hyper", i, "connection reset by peer");
+                tracing::warn!(target: "hyper", i, "connection pool exhausted");
+                tracing::info!(target: "hyper", i, "hidden below warn");
+                tracing::error!(target: "leaky_dep::auth", i, "token=secret");
+                tracing::info!(target: "my_app::poller", i, "polled");
+            }
+        });
+    }
+
+    /// Returns the console output of 100 rounds of events, filtered by `filter`.
+    fn output<F>(filter: F) -> String
+    where
+        F: Filter<Registry> + Send + Sync + 'static,
+    {
+        let console = Capture::default();
+        emit(filter, console.clone(), 100);
+        console.output()
+    }
+
+    /// Returns the events per second logged through `filter`.
+    fn throughput<F>(filter: F) -> f64
+    where
+        F: Filter<Registry> + Send + Sync + 'static,
+    {
+        const ROUNDS: usize = 20_000;
+        let start = Instant::now();
+        emit(filter, io::sink, ROUNDS);
+        (ROUNDS * 9) as f64 / start.elapsed().as_secs_f64()
+    }
+
+    #[test]
+    fn keeps_the_same_events_as_the_stacked_filters() {
+        let combined = output(
+            CombinedFilter::new(targets())
+                .with_silenced(Some(silenced()))
+                .with_messages(messages())
+                .with_fields(fields())
+                .with_sampling(sampling()),
+        );
+        let stacked = output(
+            targets()
+                .and(silenced())
+                .and(fields())
+                .and(messages())
+                .and(sampling()),
+        );
+
+        assert_eq!(combined, stacked);
+        assert_eq!(combined.matches("query").count(), 100);
+        assert_eq!(combined.matches("served").count(), 0);
+        assert_eq!(combined.matches("failed").count(), 100);
+        assert_eq!(combined.matches("connection pool exhausted").count(), 100);
+        assert_eq!(combined.matches("polled").count(), 10);
+        for dropped in ["hidden", "connection reset", "token=secret"] {
+            assert!(!combined.contains(dropped), "{dropped}");
+        }
+    }
+
+    // Timing-sensitive, so it only runs on demand:
+    // `cargo test --all-features -- --ignored --nocapture keeps_pace`
+    #[test]
+    #[ignore]
+    fn keeps_pace_with_the_stacked_filters() {
+        let combined = || {
+            CombinedFilter::new(targets())
+                .with_silenced(Some(silenced()))
+                .with_messages(messages())
+                .with_fields(fields())
+                .with_sampling(sampling())
+        };
+        let stacked = || {
+            targets()
+                .and(silenced())
+                .and(fields())
+                .and(messages())
+                .and(sampling())
+        };
+
+        // Warm up both paths before timing them
+        throughput(combined());
+        throughput(stacked());
+        let combined = throughput(combined());
+        let stacked = throughput(stacked());
+
+        println!("combined: {combined:.0} events/s, stacked: {stacked:.0} events/s");
+        // Loose bound, so a noisy machine doesn't fail it
+        assert!(
+            combined > stacked * 0.8,
+            "{combined:.0} < {stacked:.0} events/s"
+        );
+    }
+}
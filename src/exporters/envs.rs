@@ -27,14 +27,387 @@ use tracing::level_filters::LevelFilter;
 /// - "error", "Error", "ERROR" -> `LevelFilter::ERROR`
 /// - "trace", "Trace", "TRACE" -> `LevelFilter::TRACE`
 /// - Any other value -> `LevelFilter::OFF`
-#[allow(dead_code)]
 pub fn log_level(level: &str) -> LevelFilter {
-    match level {
-        "debug" | "Debug" | "DEBUG" => LevelFilter::DEBUG,
-        "info" | "Info" | "INFO" => LevelFilter::INFO,
-        "warn" | "Warn" | "WARN" => LevelFilter::WARN,
-        "error" | "Error" | "ERROR" => LevelFilter::ERROR,
-        "trace" | "Trace" | "TRACE" => LevelFilter::TRACE,
-        _ => LevelFilter::OFF,
+    crate::minimal::parse_level(level).unwrap_or(LevelFilter::OFF)
+}
+
+/// Environment variable holding the default level, over `LOG_LEVEL`.
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub(crate) const APP_LOG_LEVEL_ENV_KEY: &str = "APP_LOG_LEVEL";
+
+/// Environment variable holding the default level, over `RUST_LOG`.
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub(crate) const LOG_LEVEL_ENV_KEY: &str = "LOG_LEVEL";
+
+/// Environment variable holding `tracing` directives, whose default level is used when
+/// no other level is set.
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub(crate) const RUST_LOG_ENV_KEY: &str = "RUST_LOG";
+
+/// Environment variable holding the field conditions an event must match to be kept.
+pub(crate) const LOG_FIELD_INCLUDE_ENV_KEY: &str = "LOG_FIELD_INCLUDE";
+
+/// Environment variable holding the field conditions that cause an event to be dropped.
+pub(crate) const LOG_FIELD_EXCLUDE_ENV_KEY: &str = "LOG_FIELD_EXCLUDE";
+
+/// Environment variable holding the patterns of the messages that cause an event to be
+/// dropped.
+pub(crate) const LOG_MESSAGE_DENYLIST_ENV_KEY: &str = "LOG_MESSAGE_DENYLIST";
+
+/// Environment variable holding the targets allowed to log in allowlist mode.
+pub(crate) const LOG_TARGETS_ALLOWLIST_ENV_KEY: &str = "LOG_TARGETS_ALLOWLIST";
+
+/// Environment variable holding the filter presets of the dependencies to quiet.
+pub(crate) const LOG_FILTER_PRESETS_ENV_KEY: &str = "LOG_FILTER_PRESETS";
+
+/// Environment variable holding the targets that never log.
+pub(crate) const LOG_SILENCED_TARGETS_ENV_KEY: &str = "LOG_SILENCED_TARGETS";
+
+/// Environment variable holding the target that identifies audit events.
+pub(crate) const LOG_AUDIT_TARGET_ENV_KEY: &str = "LOG_AUDIT_TARGET";
+
+/// Environment variable holding the destination of the audit stream.
+pub(crate) const LOG_AUDIT_OUTPUT_ENV_KEY: &str = "LOG_AUDIT_OUTPUT";
+
+/// Environment variable holding the delay, in milliseconds, between two batch exports.
+pub(crate) const OTEL_BLRP_SCHEDULE_DELAY_ENV_KEY: &str = "OTEL_BLRP_SCHEDULE_DELAY";
+
+/// Environment variable holding the maximum number of records buffered for export.
+pub(crate) const OTEL_BLRP_MAX_QUEUE_SIZE_ENV_KEY: &str = "OTEL_BLRP_MAX_QUEUE_SIZE";
+
+/// Environment variable holding the maximum number of records sent in a single export.
+pub(crate) const OTEL_BLRP_MAX_EXPORT_BATCH_SIZE_ENV_KEY: &str = "OTEL_BLRP_MAX_EXPORT_BATCH_SIZE";
+
+/// Environment variable holding the maximum duration, in milliseconds, of a single export.
+pub(crate) const OTEL_BLRP_EXPORT_TIMEOUT_ENV_KEY: &str = "OTEL_BLRP_EXPORT_TIMEOUT";
+
+/// Environment variable holding the `key=value` headers sent with every OTLP export.
+#[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+pub(crate) const OTEL_EXPORTER_OTLP_HEADERS_ENV_KEY: &str = "OTEL_EXPORTER_OTLP_HEADERS";
+
+/// Environment variable holding the headers sent with every OTLP logs export, over
+/// `OTEL_EXPORTER_OTLP_HEADERS`.
+#[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+pub(crate) const OTEL_EXPORTER_OTLP_LOGS_HEADERS_ENV_KEY: &str = "OTEL_EXPORTER_OTLP_LOGS_HEADERS";
+
+/// Environment variable holding the behavior of the batch queue when it's full.
+pub(crate) const LOG_OTLP_BACKPRESSURE_ENV_KEY: &str = "LOG_OTLP_BACKPRESSURE";
+
+/// Environment variable holding the secondary OTLP endpoints, in failover order.
+pub(crate) const LOG_OTLP_FAILOVER_ENDPOINTS_ENV_KEY: &str = "LOG_OTLP_FAILOVER_ENDPOINTS";
+
+/// Environment variable holding the number of consecutive failed exports before failing
+/// over to the next OTLP endpoint.
+pub(crate) const LOG_OTLP_FAILOVER_THRESHOLD_ENV_KEY: &str = "LOG_OTLP_FAILOVER_THRESHOLD";
+
+/// Environment variable holding the delay, in milliseconds, before the primary OTLP
+/// endpoint is tried again after a failover.
+pub(crate) const LOG_OTLP_FAILOVER_RECOVERY_ENV_KEY: &str = "LOG_OTLP_FAILOVER_RECOVERY";
+
+/// Environment variable holding the interval, in milliseconds, of the OTLP HTTP/2
+/// keepalive pings.
+pub(crate) const LOG_OTLP_KEEPALIVE_INTERVAL_ENV_KEY: &str = "LOG_OTLP_KEEPALIVE_INTERVAL";
+
+/// Environment variable holding the timeout, in milliseconds, of the OTLP keepalive pings.
+pub(crate) const LOG_OTLP_KEEPALIVE_TIMEOUT_ENV_KEY: &str = "LOG_OTLP_KEEPALIVE_TIMEOUT";
+
+/// Environment variable holding the timeout, in milliseconds, of the connections to the
+/// OTLP collectors.
+pub(crate) const LOG_OTLP_CONNECT_TIMEOUT_ENV_KEY: &str = "LOG_OTLP_CONNECT_TIMEOUT";
+
+/// Environment variable toggling the connection check of the OTLP collectors at
+/// installation.
+pub(crate) const LOG_OTLP_PREFLIGHT_ENV_KEY: &str = "LOG_OTLP_PREFLIGHT";
+
+/// Environment variable holding the compression of the OTLP export requests.
+pub(crate) const LOG_OTLP_COMPRESSION_ENV_KEY: &str = "LOG_OTLP_COMPRESSION";
+
+/// Environment variable making the body of the OTLP records a map of the event fields.
+pub(crate) const LOG_OTLP_STRUCTURED_BODY_ENV_KEY: &str = "LOG_OTLP_STRUCTURED_BODY";
+
+/// Environment variable toggling the OTLP keepalive pings on idle connections.
+pub(crate) const LOG_OTLP_KEEPALIVE_WHILE_IDLE_ENV_KEY: &str = "LOG_OTLP_KEEPALIVE_WHILE_IDLE";
+
+/// Environment variable holding the path of the PEM certificate of the CA verifying the
+/// OTLP collectors.
+pub(crate) const OTEL_EXPORTER_OTLP_CERTIFICATE_ENV_KEY: &str = "OTEL_EXPORTER_OTLP_CERTIFICATE";
+
+/// Environment variable holding the path of the PEM client certificate presented to the
+/// OTLP collectors.
+pub(crate) const OTEL_EXPORTER_OTLP_CLIENT_CERTIFICATE_ENV_KEY: &str =
+    "OTEL_EXPORTER_OTLP_CLIENT_CERTIFICATE";
+
+/// Environment variable holding the path of the PEM private key of the OTLP client
+/// certificate.
+pub(crate) const OTEL_EXPORTER_OTLP_CLIENT_KEY_ENV_KEY: &str = "OTEL_EXPORTER_OTLP_CLIENT_KEY";
+
+/// Environment variable holding the domain name the OTLP collector certificates are
+/// verified against.
+pub(crate) const LOG_OTLP_TLS_DOMAIN_ENV_KEY: &str = "LOG_OTLP_TLS_DOMAIN";
+
+/// Environment variable holding the `host:port` address of the Graylog GELF input.
+pub(crate) const LOG_GELF_ADDRESS_ENV_KEY: &str = "LOG_GELF_ADDRESS";
+
+/// Environment variable holding the transport of the GELF messages, `udp` or `tcp`.
+pub(crate) const LOG_GELF_TRANSPORT_ENV_KEY: &str = "LOG_GELF_TRANSPORT";
+
+/// Environment variable overriding the `host` field of the GELF messages.
+pub(crate) const LOG_GELF_HOST_ENV_KEY: &str = "LOG_GELF_HOST";
+
+/// Environment variable holding the maximum size, in bytes, of a GELF UDP datagram.
+pub(crate) const LOG_GELF_CHUNK_SIZE_ENV_KEY: &str = "LOG_GELF_CHUNK_SIZE";
+
+/// Environment variable holding the format of the base console layer.
+pub(crate) const LOG_BASE_FORMAT_ENV_KEY: &str = "LOG_BASE_FORMAT";
+
+/// Environment variable holding the format of the structured console layer.
+pub(crate) const LOG_STRUCTURED_FORMAT_ENV_KEY: &str = "LOG_STRUCTURED_FORMAT";
+
+/// Environment variable holding the stream the base console layer writes to.
+pub(crate) const LOG_BASE_FMT_STREAM_ENV_KEY: &str = "LOG_BASE_FMT_STREAM";
+
+/// Environment variable holding the stream the structured console layer writes to.
+pub(crate) const LOG_STRUCTURED_STREAM_ENV_KEY: &str = "LOG_STRUCTURED_STREAM";
+
+/// Environment variable holding the render mode of the console output.
+pub(crate) const LOG_RENDER_MODE_ENV_KEY: &str = "LOG_RENDER_MODE";
+
+/// Environment variable toggling the switch to the piped render mode without terminal.
+pub(crate) const LOG_TTY_DETECTION_ENV_KEY: &str = "LOG_TTY_DETECTION";
+
+/// Environment variable holding the per-level formats of the structured console layer.
+pub(crate) const LOG_STRUCTURED_LEVEL_FORMATS_ENV_KEY: &str = "LOG_STRUCTURED_LEVEL_FORMATS";
+
+/// Environment variable holding the colors of the level token in the colored console.
+pub(crate) const LOG_LEVEL_COLORS_ENV_KEY: &str = "LOG_LEVEL_COLORS";
+
+/// Environment variable holding the exporter to install.
+pub(crate) const LOG_EXPORTER_ENV_KEY: &str = "LOG_EXPORTER";
+
+/// Environment variable toggling thread ids in the base console layer.
+pub(crate) const LOG_WITH_THREAD_IDS_ENV_KEY: &str = "LOG_WITH_THREAD_IDS";
+
+/// Environment variable toggling thread names in the base console layer.
+pub(crate) const LOG_WITH_THREAD_NAMES_ENV_KEY: &str = "LOG_WITH_THREAD_NAMES";
+
+/// Environment variable toggling the source file in the base console layer.
+pub(crate) const LOG_WITH_FILE_ENV_KEY: &str = "LOG_WITH_FILE";
+
+/// Environment variable toggling the source line number in the base console layer.
+pub(crate) const LOG_WITH_LINE_NUMBER_ENV_KEY: &str = "LOG_WITH_LINE_NUMBER";
+
+/// Environment variable toggling source locations altogether in the base console layer.
+pub(crate) const LOG_WITH_SOURCE_LOCATION_ENV_KEY: &str = "LOG_WITH_SOURCE_LOCATION";
+
+/// Environment variable restricting source locations to the events at or above a level.
+pub(crate) const LOG_SOURCE_LOCATION_LEVEL_ENV_KEY: &str = "LOG_SOURCE_LOCATION_LEVEL";
+
+/// Environment variable enabling nested JSON objects built from dotted field names.
+pub(crate) const LOG_JSON_NESTED_FIELDS_ENV_KEY: &str = "LOG_JSON_NESTED_FIELDS";
+
+/// Environment variable holding the names given to the fields of the JSON output.
+pub(crate) const LOG_JSON_FIELD_NAMES_ENV_KEY: &str = "LOG_JSON_FIELD_NAMES";
+
+/// Environment variable holding the standard Bunyan fields left out of the JSON output.
+pub(crate) const LOG_JSON_OMITTED_FIELDS_ENV_KEY: &str = "LOG_JSON_OMITTED_FIELDS";
+
+/// Environment variable holding the prefix of the JSON lines of the dual format.
+pub(crate) const LOG_DUAL_MARKER_ENV_KEY: &str = "LOG_DUAL_MARKER";
+
+/// Environment variable holding the OTLP severity overrides per tracing level.
+pub(crate) const LOG_OTLP_SEVERITY_MAP_ENV_KEY: &str = "LOG_OTLP_SEVERITY_MAP";
+
+/// Environment variable holding the rewrite rules of exported event names.
+pub(crate) const LOG_EVENT_NAME_RULES_ENV_KEY: &str = "LOG_EVENT_NAME_RULES";
+
+/// Environment variable listing the baggage keys attached to exported records.
+pub(crate) const LOG_BAGGAGE_KEYS_ENV_KEY: &str = "LOG_BAGGAGE_KEYS";
+
+/// Environment variable enabling the console output next to the exporters.
+pub(crate) const LOG_CONSOLE_OUTPUT_ENV_KEY: &str = "LOG_CONSOLE_OUTPUT";
+
+/// Environment variable holding the policy applied when the exporter can't be built.
+pub(crate) const LOG_ON_EXPORTER_FAILURE_ENV_KEY: &str = "LOG_ON_EXPORTER_FAILURE";
+
+/// Environment variable holding the sample rates of the chatty targets.
+pub(crate) const LOG_TARGET_SAMPLE_RATES_ENV_KEY: &str = "LOG_TARGET_SAMPLE_RATES";
+
+/// Environment variable serializing the writes of the console layers.
+pub(crate) const LOG_CONSOLE_SERIALIZED_ENV_KEY: &str = "LOG_CONSOLE_SERIALIZED";
+
+/// Environment variable holding the size, in bytes, of the console write buffers.
+pub(crate) const LOG_CONSOLE_BUFFER_ENV_KEY: &str = "LOG_CONSOLE_BUFFER";
+
+/// Environment variable holding the interval, in milliseconds, between two flushes of
+/// the console write buffers.
+pub(crate) const LOG_CONSOLE_FLUSH_INTERVAL_ENV_KEY: &str = "LOG_CONSOLE_FLUSH_INTERVAL";
+
+/// Environment variable enabling the console stream of WARN and ERROR events.
+pub(crate) const LOG_WARN_STREAM_ENV_KEY: &str = "LOG_WARN_STREAM";
+
+/// Environment variable holding the number of console lines withheld until an error.
+pub(crate) const LOG_ERROR_BUFFER_ENV_KEY: &str = "LOG_ERROR_BUFFER";
+
+/// Environment variable holding the level of the events flushing the error buffer.
+pub(crate) const LOG_ERROR_BUFFER_TRIGGER_ENV_KEY: &str = "LOG_ERROR_BUFFER_TRIGGER";
+
+/// Environment variable holding the level of the console output.
+pub(crate) const LOG_CONSOLE_LEVEL_ENV_KEY: &str = "LOG_CONSOLE_LEVEL";
+
+/// Environment variable holding the level of the events handed to the export bridge.
+pub(crate) const LOG_EXPORT_LEVEL_ENV_KEY: &str = "LOG_EXPORT_LEVEL";
+
+/// Environment variable making the export follow the sampling decision of traces.
+pub(crate) const LOG_FOLLOW_TRACE_SAMPLING_ENV_KEY: &str = "LOG_FOLLOW_TRACE_SAMPLING";
+
+/// Environment variable toggling the event summarizing the configuration at installation.
+pub(crate) const LOG_STARTUP_BANNER_ENV_KEY: &str = "LOG_STARTUP_BANNER";
+
+/// Environment variable holding the name of the event field with the event timestamp.
+pub(crate) const LOG_TIMESTAMP_FIELD_ENV_KEY: &str = "LOG_TIMESTAMP_FIELD";
+
+/// Environment variable holding the number of recent log lines kept in memory.
+pub(crate) const LOG_RECENT_LOGS_ENV_KEY: &str = "LOG_RECENT_LOGS";
+
+/// Environment variable holding the process and runtime fields added to every event.
+pub(crate) const LOG_PROCESS_FIELDS_ENV_KEY: &str = "LOG_PROCESS_FIELDS";
+
+/// Environment variable enabling the W3C `traceparent` field on the events of a trace.
+pub(crate) const LOG_TRACEPARENT_ENV_KEY: &str = "LOG_TRACEPARENT";
+
+/// Environment variable enabling the `crate` and `module` fields derived from the target.
+pub(crate) const LOG_TARGET_FIELDS_ENV_KEY: &str = "LOG_TARGET_FIELDS";
+
+/// Environment variable enabling the counters of events by level and target.
+pub(crate) const LOG_VOLUME_COUNTERS_ENV_KEY: &str = "LOG_VOLUME_COUNTERS";
+
+/// Environment variable enabling the histogram of event sizes.
+pub(crate) const LOG_EVENT_SIZE_HISTOGRAM_ENV_KEY: &str = "LOG_EVENT_SIZE_HISTOGRAM";
+
+/// Environment variable holding the bucket bounds of the event size histogram, in bytes.
+pub(crate) const LOG_EVENT_SIZE_BUCKETS_ENV_KEY: &str = "LOG_EVENT_SIZE_BUCKETS";
+
+/// Environment variable holding the interval, in milliseconds, between two heartbeats.
+pub(crate) const LOG_HEARTBEAT_INTERVAL_ENV_KEY: &str = "LOG_HEARTBEAT_INTERVAL";
+
+/// Environment variable holding the level of the heartbeat events.
+pub(crate) const LOG_HEARTBEAT_LEVEL_ENV_KEY: &str = "LOG_HEARTBEAT_LEVEL";
+
+/// Environment variable enabling the DEBUG event with the resolved configuration.
+pub(crate) const LOG_RESOLVED_CONFIG_ENV_KEY: &str = "LOG_RESOLVED_CONFIG";
+
+/// Environment variable enabling span timing summaries at the given level.
+pub(crate) const LOG_SPAN_TIMING_ENV_KEY: &str = "LOG_SPAN_TIMING";
+
+/// Environment variable holding the hostname reported instead of the detected one.
+pub(crate) const LOG_HOSTNAME_ENV_KEY: &str = "LOG_HOSTNAME";
+
+/// Environment variable holding the `service.instance.id` of the exported resource.
+pub(crate) const LOG_SERVICE_INSTANCE_ID_ENV_KEY: &str = "LOG_SERVICE_INSTANCE_ID";
+
+/// Environment variable naming the variable the `service.instance.id` is read from.
+pub(crate) const LOG_SERVICE_INSTANCE_ID_ENV_ENV_KEY: &str = "LOG_SERVICE_INSTANCE_ID_ENV";
+
+/// Environment variable holding the maximum size, in bytes, of an OTLP export request.
+pub(crate) const LOG_OTLP_MAX_ENCODING_MESSAGE_SIZE_ENV_KEY: &str =
+    "LOG_OTLP_MAX_ENCODING_MESSAGE_SIZE";
+
+/// Environment variable holding the maximum size, in bytes, of an OTLP export response.
+pub(crate) const LOG_OTLP_MAX_DECODING_MESSAGE_SIZE_ENV_KEY: &str =
+    "LOG_OTLP_MAX_DECODING_MESSAGE_SIZE";
+
+/// Environment variable holding the key of the deployment environment resource attribute.
+pub(crate) const LOG_ENVIRONMENT_ATTRIBUTE_KEY_ENV_KEY: &str = "LOG_ENVIRONMENT_ATTRIBUTE_KEY";
+
+/// Environment variable holding the timeout, in milliseconds, of the cloud detection.
+pub(crate) const LOG_CLOUD_DETECT_TIMEOUT_ENV_KEY: &str = "LOG_CLOUD_DETECT_TIMEOUT";
+
+/// Environment variable holding the timeout, in milliseconds, of the provider shutdown.
+pub(crate) const LOG_SHUTDOWN_TIMEOUT_ENV_KEY: &str = "LOG_SHUTDOWN_TIMEOUT";
+
+/// Environment variable toggling the kebab-case normalization of the service name.
+pub(crate) const LOG_SERVICE_NAME_KEBAB_CASE_ENV_KEY: &str = "LOG_SERVICE_NAME_KEBAB_CASE";
+
+/// Environment variable holding the interval, in milliseconds, between two flushes of
+/// the stdout exporter.
+pub(crate) const LOG_STDOUT_FLUSH_INTERVAL_ENV_KEY: &str = "LOG_STDOUT_FLUSH_INTERVAL";
+
+/// Environment variable enabling the flush of the exporter after each ERROR event.
+pub(crate) const LOG_FLUSH_ON_ERROR_ENV_KEY: &str = "LOG_FLUSH_ON_ERROR";
+
+/// Environment variable holding the path of the JSON file every event is written to.
+pub(crate) const LOG_JSON_FILE_ENV_KEY: &str = "LOG_JSON_FILE";
+
+/// Environment variable holding the maximum length, in bytes, of event messages.
+pub(crate) const LOG_MAX_MESSAGE_LENGTH_ENV_KEY: &str = "LOG_MAX_MESSAGE_LENGTH";
+
+/// Environment variable holding the maximum length, in bytes, of event field values.
+pub(crate) const LOG_MAX_FIELD_LENGTH_ENV_KEY: &str = "LOG_MAX_FIELD_LENGTH";
+
+/// Environment variable holding the level of the OpenTelemetry internal logs.
+pub(crate) const LOG_OTEL_INTERNAL_LEVEL_ENV_KEY: &str = "LOG_OTEL_INTERNAL_LEVEL";
+
+/// Environment variable toggling the capture of the `log` crate records.
+pub(crate) const LOG_CAPTURE_LOG_CRATE_ENV_KEY: &str = "LOG_CAPTURE_LOG_CRATE";
+
+/// Reads a comma-separated list from the given environment variable.
+///
+/// Entries are trimmed and empty entries are skipped. An unset variable yields an
+/// empty list.
+pub(crate) fn list(key: &str) -> Vec<String> {
+    split(key, ',')
+}
+
+/// Reads a list of entries delimited by `separator` from the given environment variable.
+///
+/// Like [`list`], for entries that may contain commas, e.g. regular expressions.
+pub(crate) fn split(key: &str, separator: char) -> Vec<String> {
+    std::env::var(key)
+        .map(|value| {
+            value
+                .split(separator)
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Reads a comma-separated list of `key=value` entries from the given environment
+/// variable.
+///
+/// Keys and values are trimmed, and entries without `=` or with an empty key are
+/// skipped. An unset variable yields an empty map.
+pub(crate) fn map(key: &str) -> std::collections::BTreeMap<String, String> {
+    list(key)
+        .iter()
+        .filter_map(|entry| entry.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+/// Reads and parses the given environment variable.
+///
+/// Returns `None` when the variable is unset or its value cannot be parsed.
+pub(crate) fn parse<T: std::str::FromStr>(key: &str) -> Option<T> {
+    std::env::var(key).ok()?.trim().parse().ok()
+}
+
+/// Reads a duration expressed in milliseconds from the given environment variable.
+pub(crate) fn millis(key: &str) -> Option<std::time::Duration> {
+    parse::<u64>(key).map(std::time::Duration::from_millis)
+}
+
+/// Reads a boolean flag from the given environment variable.
+///
+/// Accepts `true`/`false`, `1`/`0`, `yes`/`no` and `on`/`off`, case-insensitively.
+/// Returns `None` when the variable is unset or holds another value.
+pub(crate) fn flag(key: &str) -> Option<bool> {
+    match std::env::var(key).ok()?.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Some(true),
+        "false" | "0" | "no" | "off" => Some(false),
+        _ => None,
     }
 }
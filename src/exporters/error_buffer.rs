@@ -0,0 +1,188 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Error Buffer
+//!
+//! This module keeps the console silent until something goes wrong, for CLI tools that
+//! shouldn't print logs during normal operation but need the context of a failure. It's
+//! enabled by setting `LOG_ERROR_BUFFER` to the number of console lines to hold.
+//!
+//! The formatted lines are held in memory instead of being written. When an event at or
+//! above the trigger level, ERROR by default or `LOG_ERROR_BUFFER_TRIGGER`, is logged,
+//! the held lines are written to their destination, oldest first, followed by the
+//! triggering event, and buffering resumes. Once full, every new line evicts the oldest
+//! one, so only the lines leading up to the failure are kept.
+
+use crate::exporters::writer::SharedWriter;
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    sync::{Arc, Mutex, PoisonError},
+};
+use tracing::{Level, Metadata};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// A bounded buffer of console lines, written out when a triggering event is logged.
+///
+/// The buffer is shared by its clones and by the writers it creates, so the lines of
+/// several destinations, e.g. stdout and stderr, are flushed together and in order.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::error_buffer::ErrorBuffer;
+/// use tracing::Level;
+///
+/// let errors = ErrorBuffer::new(100, Level::ERROR);
+/// let subscriber = tracing_subscriber::fmt()
+///     .with_writer(errors.writer(std::io::stderr))
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ErrorBuffer {
+    lines: Arc<Mutex<VecDeque<HeldLine>>>,
+    capacity: usize,
+    trigger: Level,
+}
+
+impl ErrorBuffer {
+    /// Creates an empty buffer holding at most `capacity` lines, flushed by the events
+    /// at or above the `trigger` level.
+    pub fn new(capacity: usize, trigger: Level) -> Self {
+        ErrorBuffer {
+            lines: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+            trigger,
+        }
+    }
+
+    /// Wraps a destination, so the lines written to it go through this buffer.
+    pub fn writer<M>(&self, make_writer: M) -> BufferedWriter
+    where
+        M: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+    {
+        BufferedWriter {
+            buffer: self.clone(),
+            destination: SharedWriter::new(make_writer),
+        }
+    }
+
+    /// Holds a line, or writes out the held lines and this one if `level` triggers.
+    fn push(&self, destination: &SharedWriter, level: Level, line: Vec<u8>) {
+        let mut lines = self.lines.lock().unwrap_or_else(PoisonError::into_inner);
+        if level > self.trigger {
+            if self.capacity == 0 {
+                return;
+            }
+            if lines.len() == self.capacity {
+                lines.pop_front();
+            }
+            lines.push_back((destination.clone(), line));
+            return;
+        }
+
+        // Written under the lock, so concurrent triggers don't interleave
+        for (destination, line) in lines.drain(..) {
+            let _ = destination.make_writer().write_all(&line);
+        }
+        let _ = destination.make_writer().write_all(&line);
+    }
+}
+
+/// A line held by the buffer, with the destination it's written to.
+type HeldLine = (SharedWriter, Vec<u8>);
+
+/// A [`MakeWriter`] sending the lines of one destination through an [`ErrorBuffer`].
+#[derive(Debug, Clone)]
+pub struct BufferedWriter {
+    buffer: ErrorBuffer,
+    destination: SharedWriter,
+}
+
+impl<'a> MakeWriter<'a> for BufferedWriter {
+    type Writer = EventWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        // Without metadata, the line is written as if it triggered
+        self.writer(self.buffer.trigger)
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        self.writer(*meta.level())
+    }
+}
+
+impl BufferedWriter {
+    fn writer(&self, level: Level) -> EventWriter {
+        EventWriter {
+            writer: self.clone(),
+            level,
+            line: Vec::new(),
+        }
+    }
+}
+
+/// The writer of one event, handing its line to the buffer when dropped.
+#[derive(Debug)]
+pub struct EventWriter {
+    writer: BufferedWriter,
+    level: Level,
+    line: Vec<u8>,
+}
+
+impl Write for EventWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.line.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for EventWriter {
+    fn drop(&mut self) {
+        if !self.line.is_empty() {
+            let line = std::mem::take(&mut self.line);
+            self.writer
+                .buffer
+                .push(&self.writer.destination, self.level, line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::Capture;
+
+    #[test]
+    fn holds_the_latest_lines_until_an_error() {
+        let capture = Capture::default();
+        let errors = ErrorBuffer::new(2, Level::ERROR);
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(errors.writer(capture.clone()))
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("reading the manifest");
+            tracing::info!("resolving dependencies");
+            tracing::info!("downloading packages");
+            // Withheld until an error occurs
+            assert!(capture.output().is_empty());
+
+            tracing::error!("download failed");
+        });
+
+        // The oldest line was evicted, the others come before the error
+        let output = capture.output();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains("resolving dependencies"));
+        assert!(lines[1].contains("downloading packages"));
+        assert!(lines[2].contains("download failed"));
+    }
+}
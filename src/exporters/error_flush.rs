@@ -0,0 +1,126 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Error Flushing
+//!
+//! This module provides a processor wrapper that flushes another processor right after
+//! an ERROR record. On crash-prone paths, an error logged just before the process dies
+//! can otherwise sit in the batch queue and never reach the collector.
+//!
+//! The flush happens on the thread that logged the error, which waits for the export to
+//! complete, or time out, before going on. Every ERROR event pays that latency, so the
+//! wrapper is only enabled with `LOG_FLUSH_ON_ERROR`.
+
+use opentelemetry::{InstrumentationScope, logs::Severity};
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    logs::{LogProcessor, SdkLogRecord},
+};
+use std::time::Duration;
+
+/// A [`LogProcessor`] that calls `force_flush` on another processor after each record
+/// of ERROR severity or above.
+///
+/// The records of other severities are only delegated. When disabled, the processor
+/// never flushes.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::error_flush::ErrorFlushProcessor;
+/// use opentelemetry::InstrumentationScope;
+/// use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
+/// use opentelemetry_sdk::{
+///     error::OTelSdkResult,
+///     logs::{LogProcessor, SdkLogRecord, SdkLoggerProvider},
+/// };
+/// use std::{
+///     sync::{
+///         Arc,
+///         atomic::{AtomicUsize, Ordering},
+///     },
+///     time::Duration,
+/// };
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// // A stub counting the records and the flushes
+/// #[derive(Debug, Clone, Default)]
+/// struct Stub {
+///     emitted: Arc<AtomicUsize>,
+///     flushed: Arc<AtomicUsize>,
+/// }
+///
+/// impl LogProcessor for Stub {
+///     fn emit(&self, _: &mut SdkLogRecord, _: &InstrumentationScope) {
+///         self.emitted.fetch_add(1, Ordering::SeqCst);
+///     }
+///     fn force_flush(&self) -> OTelSdkResult {
+///         self.flushed.fetch_add(1, Ordering::SeqCst);
+///         Ok(())
+///     }
+///     fn shutdown_with_timeout(&self, _: Duration) -> OTelSdkResult {
+///         Ok(())
+///     }
+/// }
+///
+/// let stub = Stub::default();
+/// let provider = SdkLoggerProvider::builder()
+///     .with_log_processor(ErrorFlushProcessor::new(stub.clone(), true))
+///     .build();
+/// let subscriber =
+///     tracing_subscriber::registry().with(OpenTelemetryTracingBridge::new(&provider));
+///
+/// tracing::subscriber::with_default(subscriber, || {
+///     tracing::info!("charging");
+///     assert_eq!(stub.emitted.load(Ordering::SeqCst), 1);
+///     assert_eq!(stub.flushed.load(Ordering::SeqCst), 0);
+///
+///     // Flushed before the macro returns
+///     tracing::error!("charge failed");
+///     assert_eq!(stub.flushed.load(Ordering::SeqCst), 1);
+///
+///     tracing::warn!("retrying");
+///     assert_eq!(stub.emitted.load(Ordering::SeqCst), 3);
+///     assert_eq!(stub.flushed.load(Ordering::SeqCst), 1);
+/// });
+/// ```
+#[derive(Debug)]
+pub struct ErrorFlushProcessor<P> {
+    inner: P,
+    enabled: bool,
+}
+
+impl<P> ErrorFlushProcessor<P> {
+    /// Wraps `inner`, flushing it after each ERROR record if `enabled`.
+    pub fn new(inner: P, enabled: bool) -> Self {
+        ErrorFlushProcessor { inner, enabled }
+    }
+}
+
+impl<P: LogProcessor> LogProcessor for ErrorFlushProcessor<P> {
+    fn emit(&self, data: &mut SdkLogRecord, instrumentation: &InstrumentationScope) {
+        let flush = self.enabled
+            && data
+                .severity_number()
+                .is_some_and(|severity| severity >= Severity::Error);
+        self.inner.emit(data, instrumentation);
+        if flush {
+            // Best effort, the record is already handed over
+            let _ = self.inner.force_flush();
+        }
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
@@ -0,0 +1,275 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Event Names
+//!
+//! This module sets the `event_name` of exported log records, which newer OTLP schemas
+//! carry apart from the body, so backends can index events by category, e.g.
+//! `http.request.completed`. The tracing bridge names every record after its callsite,
+//! e.g. `event src/orders.rs:42`, which changes with the code and can't be queried.
+//!
+//! An event names itself with an `event.name` field:
+//!
+//! ```rust
+//! tracing::info!(event.name = "order.shipped", order_id = 42, "order shipped");
+//! ```
+//!
+//! The field stays on the record as an attribute. Names are kept for the lifetime of the
+//! process, so they're meant to be a small set of categories: past
+//! [`MAX_EVENT_NAMES`] distinct names, new ones are ignored and the records keep the
+//! name of their callsite. Names built from request data, e.g. `GET /users/12345`, are
+//! normalized with [`NameRules`] to stay within that set.
+
+use crate::errors::LoggingError;
+use opentelemetry::{InstrumentationScope, Key, logs::AnyValue, logs::LogRecord};
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    logs::{LogProcessor, SdkLogRecord},
+};
+use regex::Regex;
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    sync::{Mutex, OnceLock, PoisonError},
+    time::Duration,
+};
+
+/// The field naming an event.
+pub const EVENT_NAME_FIELD: &str = "event.name";
+
+/// The maximum number of distinct event names.
+pub const MAX_EVENT_NAMES: usize = 1024;
+
+/// The event names seen so far.
+static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+/// A [`LogProcessor`] that sets the event name of records from their `event.name`
+/// attribute before delegating to another processor.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::event_name::EventNameProcessor;
+/// use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
+/// use opentelemetry_sdk::logs::{InMemoryLogExporter, SdkLoggerProvider, SimpleLogProcessor};
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let exporter = InMemoryLogExporter::default();
+/// let provider = SdkLoggerProvider::builder()
+///     .with_log_processor(EventNameProcessor::new(SimpleLogProcessor::new(
+///         exporter.clone(),
+///     )))
+///     .build();
+/// let subscriber =
+///     tracing_subscriber::registry().with(OpenTelemetryTracingBridge::new(&provider));
+///
+/// tracing::subscriber::with_default(subscriber, || {
+///     tracing::info!(event.name = "http.request.completed", status = 200, "request done");
+///     tracing::info!("unnamed");
+/// });
+///
+/// let logs = exporter.get_emitted_logs().unwrap();
+/// assert_eq!(logs[0].record.event_name(), Some("http.request.completed"));
+/// // Named after the callsite by the bridge
+/// assert!(logs[1].record.event_name().unwrap().starts_with("event "));
+/// ```
+#[derive(Debug)]
+pub struct EventNameProcessor<P> {
+    inner: P,
+    field: Key,
+    rules: NameRules,
+}
+
+impl<P> EventNameProcessor<P> {
+    /// Wraps `inner`, reading the event names from the `event.name` attribute.
+    pub fn new(inner: P) -> Self {
+        EventNameProcessor {
+            inner,
+            field: Key::from_static_str(EVENT_NAME_FIELD),
+            rules: NameRules::default(),
+        }
+    }
+
+    /// Normalizes the event names with the given rules before setting them.
+    ///
+    /// Only the event name of the record is rewritten: the `event.name` attribute, like
+    /// the console output, keeps the original name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logging::exporters::event_name::{EventNameProcessor, NameRules};
+    /// use opentelemetry_sdk::logs::{InMemoryLogExporter, SimpleLogProcessor};
+    ///
+    /// let rules = NameRules::from_entries(&[r"/\d+=>/{id}".to_string()]).unwrap();
+    /// let processor = EventNameProcessor::new(SimpleLogProcessor::new(InMemoryLogExporter::default()))
+    ///     .with_rules(rules);
+    /// ```
+    pub fn with_rules(mut self, rules: NameRules) -> Self {
+        self.rules = rules;
+        self
+    }
+}
+
+impl<P: LogProcessor> LogProcessor for EventNameProcessor<P> {
+    fn emit(&self, data: &mut SdkLogRecord, instrumentation: &InstrumentationScope) {
+        let name = data
+            .attributes_iter()
+            .find(|(key, _)| *key == self.field)
+            .and_then(|(_, value)| match value {
+                AnyValue::String(name) => intern(&self.rules.normalize(name.as_str())),
+                _ => None,
+            });
+        if let Some(name) = name {
+            data.set_event_name(name);
+        }
+        self.inner.emit(data, instrumentation);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+/// Regular expression rules rewriting event names, applied in order.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::event_name::NameRules;
+///
+/// let rules = NameRules::from_entries(&[
+///     r"/[0-9a-f]{8}(-[0-9a-f]{4}){3}-[0-9a-f]{12}=>/{uuid}".to_string(),
+///     r"/\d+=>/{id}".to_string(),
+///     r"\?.*=>".to_string(),
+/// ])
+/// .unwrap();
+///
+/// assert_eq!(rules.normalize("GET /users/12345"), "GET /users/{id}");
+/// assert_eq!(
+///     rules.normalize("GET /orders/42/items/7"),
+///     "GET /orders/{id}/items/{id}"
+/// );
+/// assert_eq!(
+///     rules.normalize("DELETE /carts/0b7e8f4a-1c2d-4e5f-8a9b-0c1d2e3f4a5b"),
+///     "DELETE /carts/{uuid}"
+/// );
+/// assert_eq!(rules.normalize("GET /search?q=shoes"), "GET /search");
+/// assert_eq!(rules.normalize("order.shipped"), "order.shipped");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct NameRules {
+    rules: Vec<(Regex, String)>,
+}
+
+impl NameRules {
+    /// Parses rules from `pattern=>replacement` entries.
+    ///
+    /// The replacement may refer to the groups of the pattern, e.g. `$1`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LoggingError::InvalidConfigError` if an entry has no `=>` or its pattern
+    /// isn't a valid regular expression.
+    pub fn from_entries(entries: &[String]) -> Result<Self, LoggingError> {
+        let rules = entries
+            .iter()
+            .map(|entry| {
+                let (pattern, replacement) = entry.split_once("=>").ok_or_else(|| {
+                    LoggingError::InvalidConfigError(format!(
+                        "invalid event name rule `{entry}`, expected pattern=>replacement"
+                    ))
+                })?;
+                let pattern = Regex::new(pattern.trim()).map_err(|err| {
+                    LoggingError::InvalidConfigError(format!(
+                        "invalid pattern in event name rule `{entry}`: {err}"
+                    ))
+                })?;
+                Ok((pattern, replacement.trim().to_string()))
+            })
+            .collect::<Result<_, LoggingError>>()?;
+        Ok(NameRules { rules })
+    }
+
+    /// Returns the name rewritten by every rule, borrowed if no rule matched.
+    pub fn normalize<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        let mut name = Cow::Borrowed(name);
+        for (pattern, replacement) in &self.rules {
+            let replaced = match pattern.replace_all(&name, replacement.as_str()) {
+                Cow::Borrowed(_) => continue,
+                Cow::Owned(replaced) => replaced,
+            };
+            name = Cow::Owned(replaced);
+        }
+        name
+    }
+}
+
+/// Returns the static copy of an event name, unless too many names were seen.
+fn intern(name: &str) -> Option<&'static str> {
+    let mut names = NAMES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(name) = names.get(name) {
+        return Some(name);
+    }
+    if names.len() >= MAX_EVENT_NAMES {
+        return None;
+    }
+
+    // Records only take static names, which are shared by every record of the event
+    let name: &'static str = Box::leak(name.to_owned().into_boxed_str());
+    names.insert(name);
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::Capture;
+    use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
+    use opentelemetry_sdk::logs::{InMemoryLogExporter, SdkLoggerProvider, SimpleLogProcessor};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn only_the_record_name_is_normalized() {
+        let rules = NameRules::from_entries(&[r"/\d+=>/{id}".to_string()]).unwrap();
+        let exporter = InMemoryLogExporter::default();
+        let provider = SdkLoggerProvider::builder()
+            .with_log_processor(
+                EventNameProcessor::new(SimpleLogProcessor::new(exporter.clone()))
+                    .with_rules(rules),
+            )
+            .build();
+        let console = Capture::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(OpenTelemetryTracingBridge::new(&provider))
+            .with(tracing_subscriber::fmt::layer().with_writer(console.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(event.name = "GET /users/12345", "request done");
+        });
+
+        let logs = exporter.get_emitted_logs().unwrap();
+        assert_eq!(logs[0].record.event_name(), Some("GET /users/{id}"));
+        let attribute = logs[0]
+            .record
+            .attributes_iter()
+            .find(|(key, _)| *key == Key::from_static_str(EVENT_NAME_FIELD))
+            .map(|(_, value)| value.clone());
+        assert_eq!(attribute, Some(AnyValue::from("GET /users/12345")));
+        assert!(console.contains("GET /users/12345"));
+        assert!(!console.contains("{id}"));
+    }
+}
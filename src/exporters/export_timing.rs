@@ -0,0 +1,140 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Export Timing
+//!
+//! This module reports how long the OTLP exports take, so latency spikes can be
+//! correlated with the export pressure. Each export is reported by a DEBUG event with
+//! the number of records it carried (`export.records`), its duration
+//! (`export.duration_ms`) and its outcome (`export.result`, `ok` or `error`).
+//!
+//! Exports happen every second under load, so at most one event is emitted per
+//! interval, and the event target is one of the
+//! [`crate::exporters::filters::OTEL_INTERNAL_TARGETS`]: the events only reach the
+//! console, can't trigger another export, and follow `LOG_OTEL_INTERNAL_LEVEL`, which
+//! must be set to `debug` to see them.
+
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    logs::{LogBatch, LogExporter},
+};
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+use tracing::debug;
+
+/// A [`LogExporter`] timing the exports of another exporter.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::export_timing::TimedExporter;
+/// use opentelemetry_sdk::logs::{InMemoryLogExporter, SdkLoggerProvider};
+/// use std::time::Duration;
+///
+/// let provider = SdkLoggerProvider::builder()
+///     .with_simple_exporter(TimedExporter::new(
+///         InMemoryLogExporter::default(),
+///         Duration::from_secs(1),
+///     ))
+///     .build();
+/// ```
+#[derive(Debug)]
+pub struct TimedExporter<E> {
+    inner: E,
+    interval: Duration,
+    started: Instant,
+    /// Milliseconds between `started` and the last report, plus one, or zero.
+    last_report: AtomicU64,
+}
+
+impl<E> TimedExporter<E> {
+    /// Wraps `inner`, reporting at most one export per `interval`.
+    pub fn new(inner: E, interval: Duration) -> Self {
+        TimedExporter {
+            inner,
+            interval,
+            started: Instant::now(),
+            last_report: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns `true` if no export was reported within the interval.
+    fn should_report(&self) -> bool {
+        let now = self.started.elapsed().as_millis() as u64 + 1;
+        let last = self.last_report.load(Ordering::Relaxed);
+        if last != 0 && now.saturating_sub(last) < self.interval.as_millis() as u64 {
+            return false;
+        }
+        self.last_report
+            .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+    }
+}
+
+impl<E: LogExporter> LogExporter for TimedExporter<E> {
+    async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
+        let records = batch.iter().count();
+        let start = Instant::now();
+        let result = self.inner.export(batch).await;
+
+        if self.should_report() {
+            debug!(
+                export.records = records,
+                export.duration_ms = start.elapsed().as_secs_f64() * 1000.0,
+                export.result = if result.is_ok() { "ok" } else { "error" },
+                "log export completed"
+            );
+        }
+        result
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::Capture;
+    use opentelemetry::logs::{Logger, LoggerProvider};
+    use opentelemetry_sdk::logs::{InMemoryLogExporter, SdkLoggerProvider};
+
+    #[test]
+    fn reports_one_export_per_interval() {
+        let capture = Capture::default();
+        let console = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_max_level(tracing::Level::DEBUG)
+            .with_writer(capture.clone())
+            .finish();
+
+        let provider = SdkLoggerProvider::builder()
+            .with_simple_exporter(TimedExporter::new(
+                InMemoryLogExporter::default(),
+                Duration::from_secs(1),
+            ))
+            .build();
+        let logger = provider.logger("app");
+
+        tracing::subscriber::with_default(console, || {
+            logger.emit(logger.create_log_record());
+            // Within the interval of the previous one, not reported
+            logger.emit(logger.create_log_record());
+        });
+
+        let output = capture.output();
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.contains("export.records=1"));
+        assert!(output.contains("export.duration_ms="));
+        assert!(output.contains("export.result=\"ok\""));
+    }
+}
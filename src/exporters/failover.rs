@@ -0,0 +1,253 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # OTLP Endpoint Failover
+//!
+//! This module provides an exporter wrapper that spreads exports over an ordered list
+//! of exporters, one per collector endpoint. It's used by the OTLP exporter in high
+//! availability deployments running several collectors, so records fail over to a
+//! secondary collector instead of being dropped when the primary is unreachable. See
+//! [`crate::config::FailoverConfigs`] for the failover and recovery semantics.
+
+use crate::config::FailoverConfigs;
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    logs::{LogBatch, LogExporter},
+};
+use std::{
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicU32, AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+/// A [`LogExporter`] exporting through the first healthy exporter of a list.
+#[derive(Debug)]
+pub(crate) struct FailoverExporter<E> {
+    exporters: Vec<E>,
+    threshold: u32,
+    recovery: Duration,
+    active: AtomicUsize,
+    failures: AtomicU32,
+    failed_over_at: Mutex<Option<Instant>>,
+}
+
+impl<E> FailoverExporter<E> {
+    /// Wraps `exporters`, the first one being the primary.
+    pub(crate) fn new(exporters: Vec<E>, cfgs: &FailoverConfigs) -> Self {
+        FailoverExporter {
+            exporters,
+            threshold: cfgs.threshold.max(1),
+            recovery: cfgs.recovery,
+            active: AtomicUsize::new(0),
+            failures: AtomicU32::new(0),
+            failed_over_at: Mutex::new(None),
+        }
+    }
+
+    /// Returns the index of the exporter to use, going back to the primary once the
+    /// recovery delay has elapsed.
+    fn select(&self) -> usize {
+        let mut failed_over_at = self
+            .failed_over_at
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if failed_over_at.is_some_and(|at| at.elapsed() >= self.recovery) {
+            *failed_over_at = None;
+            self.active.store(0, Ordering::Release);
+            self.failures.store(0, Ordering::Release);
+        }
+        self.active.load(Ordering::Acquire)
+    }
+
+    /// Records a failed export on `index`, returning the exporter failed over to if the
+    /// threshold is reached.
+    fn fail(&self, index: usize) -> Option<usize> {
+        if self.failures.fetch_add(1, Ordering::AcqRel) + 1 < self.threshold {
+            return None;
+        }
+
+        let next = (index + 1) % self.exporters.len();
+        self.active.store(next, Ordering::Release);
+        self.failures.store(0, Ordering::Release);
+        *self
+            .failed_over_at
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = (next != 0).then(Instant::now);
+        Some(next)
+    }
+}
+
+impl<E: LogExporter> LogExporter for FailoverExporter<E> {
+    async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
+        if self.exporters.len() == 1 {
+            return self.exporters[0].export(batch).await;
+        }
+
+        // The batch is rebuilt from its records, so it can be retried on the next exporter
+        let records: Vec<_> = batch.iter().collect();
+        let index = self.select();
+
+        let result = self.exporters[index].export(LogBatch::new(&records)).await;
+        if result.is_ok() {
+            self.failures.store(0, Ordering::Release);
+            return result;
+        }
+
+        match self.fail(index) {
+            Some(next) => {
+                let retry = self.exporters[next].export(LogBatch::new(&records)).await;
+                if retry.is_err() {
+                    self.failures.fetch_add(1, Ordering::AcqRel);
+                }
+                retry
+            }
+            None => result,
+        }
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        // Every exporter is shut down, even after a failure
+        let results: Vec<_> = self
+            .exporters
+            .iter()
+            .map(|exporter| exporter.shutdown_with_timeout(timeout))
+            .collect();
+        results.into_iter().collect()
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        for exporter in &mut self.exporters {
+            exporter.set_resource(resource);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use opentelemetry::{
+        InstrumentationScope,
+        logs::{AnyValue, LogRecord, Logger, LoggerProvider},
+    };
+    use opentelemetry_sdk::{
+        error::OTelSdkError,
+        logs::{SdkLogRecord, SdkLoggerProvider},
+    };
+    use std::sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize},
+    };
+
+    /// A collector endpoint counting the records it received, while up.
+    #[derive(Debug, Clone, Default)]
+    struct Endpoint {
+        down: Arc<AtomicBool>,
+        attempts: Arc<AtomicUsize>,
+        received: Arc<AtomicUsize>,
+    }
+
+    impl Endpoint {
+        fn down() -> Self {
+            let endpoint = Endpoint::default();
+            endpoint.down.store(true, Ordering::SeqCst);
+            endpoint
+        }
+
+        fn received(&self) -> usize {
+            self.received.load(Ordering::SeqCst)
+        }
+    }
+
+    impl LogExporter for Endpoint {
+        async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            if self.down.load(Ordering::SeqCst) {
+                return Err(OTelSdkError::InternalFailure("unreachable".to_string()));
+            }
+            self.received
+                .fetch_add(batch.iter().count(), Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn records(count: usize) -> Vec<(SdkLogRecord, InstrumentationScope)> {
+        let logger = SdkLoggerProvider::builder().build().logger("orders");
+        (0..count)
+            .map(|seq| {
+                let mut record = logger.create_log_record();
+                record.set_body(AnyValue::Int(seq as i64));
+                (record, InstrumentationScope::builder("orders").build())
+            })
+            .collect()
+    }
+
+    async fn export<E: LogExporter>(exporter: &FailoverExporter<E>, count: usize) -> OTelSdkResult {
+        let records = records(count);
+        let batch: Vec<_> = records
+            .iter()
+            .map(|(record, scope)| (record, scope))
+            .collect();
+        exporter.export(LogBatch::new(&batch)).await
+    }
+
+    fn cfgs(threshold: u32, recovery: Duration) -> FailoverConfigs {
+        FailoverConfigs {
+            threshold,
+            recovery,
+            ..FailoverConfigs::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn secondary_gets_the_batch_the_primary_failed() {
+        let (primary, secondary) = (Endpoint::down(), Endpoint::default());
+        let exporter = FailoverExporter::new(
+            vec![primary.clone(), secondary.clone()],
+            &cfgs(1, Duration::from_secs(3600)),
+        );
+
+        export(&exporter, 3).await.unwrap();
+        assert_eq!(secondary.received(), 3);
+
+        // The next batches skip the primary until the recovery delay elapses
+        export(&exporter, 2).await.unwrap();
+        assert_eq!(primary.attempts.load(Ordering::SeqCst), 1);
+        assert_eq!(secondary.received(), 5);
+    }
+
+    #[tokio::test]
+    async fn fails_over_once_the_threshold_is_reached() {
+        let (primary, secondary) = (Endpoint::down(), Endpoint::default());
+        let exporter = FailoverExporter::new(
+            vec![primary.clone(), secondary.clone()],
+            &cfgs(2, Duration::from_secs(3600)),
+        );
+
+        assert!(export(&exporter, 1).await.is_err());
+        assert_eq!(secondary.received(), 0);
+        export(&exporter, 1).await.unwrap();
+        assert_eq!(secondary.received(), 1);
+    }
+
+    #[tokio::test]
+    async fn primary_is_tried_again_after_the_recovery_delay() {
+        let (primary, secondary) = (Endpoint::down(), Endpoint::default());
+        let exporter = FailoverExporter::new(
+            vec![primary.clone(), secondary.clone()],
+            &cfgs(1, Duration::ZERO),
+        );
+
+        export(&exporter, 1).await.unwrap();
+        assert_eq!(secondary.received(), 1);
+        primary.down.store(false, Ordering::SeqCst);
+        export(&exporter, 1).await.unwrap();
+        assert_eq!(primary.received(), 1);
+        assert_eq!(secondary.received(), 1);
+    }
+}
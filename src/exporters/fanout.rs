@@ -0,0 +1,204 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Output Fan-Out
+//!
+//! This module installs a logging system sending records to several outputs at once,
+//! for example OTLP for central aggregation and a JSON file for local retention, next
+//! to the usual console output.
+//!
+//! The OpenTelemetry outputs share a single logger provider, with one processor chain
+//! per output, so shutting the provider down flushes and stops all of them. OTLP outputs
+//! with their own endpoint, timeout, compression and TLS settings each export every
+//! record, e.g. to a vendor backend and an internal collector for redundancy. File
+//! outputs are formatting layers added to the same subscriber. Each output only
+//! receives the records within its own level range, from its `level` up to, and
+//! excluding, its `below` level: errors can page through one collector while the other
+//! levels stay local.
+
+use crate::{
+    config::{LoggingConfigs, OutputConfig},
+    errors::LoggingError,
+    exporters::{
+        envs::log_level,
+        layers::{ConsoleOptions, console_layers, export_layer, file_layer, install_subscriber},
+        resource,
+    },
+};
+use configs::{app::AppConfigs, otlp::OTLPConfigs};
+use opentelemetry::InstrumentationScope;
+use opentelemetry_appender_tracing::layer;
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    logs::{LogProcessor, SdkLogRecord, SdkLoggerProvider},
+};
+use std::time::Duration;
+use tracing::{Level, level_filters::LevelFilter};
+
+/// Installs the logging system sending records to every configured output, with the
+/// `custom` resource if any.
+///
+/// # Errors
+///
+/// Returns `LoggingError::InvalidFeaturesError` if an output's feature isn't enabled,
+/// `LoggingError::InvalidConfigError` if a file output can't be opened, and otherwise
+/// the errors of the exporters.
+#[cfg_attr(
+    not(all(feature = "otlp", not(target_arch = "wasm32"))),
+    allow(unused_variables)
+)]
+pub(crate) fn install_with(
+    app_cfgs: &AppConfigs,
+    otlp_cfgs: &OTLPConfigs,
+    log_cfgs: &LoggingConfigs,
+    outputs: &[OutputConfig],
+    console: ConsoleOptions,
+    custom: Option<Resource>,
+) -> Result<SdkLoggerProvider, LoggingError> {
+    let mut builder =
+        SdkLoggerProvider::builder().with_resource(resource::build(app_cfgs, log_cfgs, custom)?);
+    let mut exports = false;
+    let mut layers = Vec::new();
+
+    for output in outputs {
+        match output {
+            OutputConfig::Otlp {
+                level,
+                below,
+                endpoint,
+                timeout,
+                compression,
+                tls,
+            } => {
+                #[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+                {
+                    // An output with its own endpoint doesn't fail over to the others
+                    let mut output_otlp_cfgs = otlp_cfgs.clone();
+                    let mut output_log_cfgs = log_cfgs.clone();
+                    if let Some(endpoint) = endpoint {
+                        output_otlp_cfgs.endpoint = endpoint.clone();
+                        output_log_cfgs.otlp_failover.endpoints.clear();
+                    }
+                    if let Some(timeout) = timeout {
+                        output_otlp_cfgs.exporter_timeout = *timeout;
+                        output_log_cfgs.otlp_batch.max_export_timeout = None;
+                    }
+                    if let Some(compression) = compression {
+                        output_log_cfgs.otlp_compression = *compression;
+                    }
+                    if let Some(tls) = tls {
+                        output_log_cfgs.otlp_tls = tls.clone();
+                    }
+
+                    let processor =
+                        super::otlp_grpc::processor(&output_otlp_cfgs, &output_log_cfgs)?;
+                    builder =
+                        builder.with_log_processor(LevelProcessor::new(processor, level, below));
+                    exports = true;
+                }
+                #[cfg(not(all(feature = "otlp", not(target_arch = "wasm32"))))]
+                {
+                    let _ = (level, below, endpoint, timeout, compression, tls);
+                    return Err(crate::config::ExporterKind::Otlp.unavailable());
+                }
+            }
+            OutputConfig::Stdout { level, below } => {
+                #[cfg(feature = "stdout")]
+                {
+                    let processor = super::stdout::processor(log_cfgs);
+                    builder =
+                        builder.with_log_processor(LevelProcessor::new(processor, level, below));
+                    exports = true;
+                }
+                #[cfg(not(feature = "stdout"))]
+                {
+                    let _ = (level, below);
+                    return Err(crate::config::ExporterKind::Stdout.unavailable());
+                }
+            }
+            OutputConfig::File {
+                path,
+                level,
+                below,
+                format,
+            } => layers.push(file_layer(
+                app_cfgs,
+                log_cfgs,
+                path,
+                level.as_deref(),
+                below.as_deref(),
+                *format,
+            )?),
+        }
+    }
+
+    let provider = builder.build();
+    if exports {
+        layers.push(export_layer(
+            layer::OpenTelemetryTracingBridge::new(&provider),
+            log_cfgs,
+        ));
+    }
+    layers.extend(console_layers(app_cfgs, log_cfgs, console)?);
+
+    install_subscriber(app_cfgs, log_cfgs, layers)?;
+    #[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+    if outputs
+        .iter()
+        .any(|output| matches!(output, OutputConfig::Otlp { .. }))
+    {
+        super::otlp_grpc::register(super::otlp_grpc::Reconnectable::Outputs);
+    }
+
+    Ok(provider)
+}
+
+/// A [`LogProcessor`] that only delegates the records at or above a level, and below
+/// an upper bound if any.
+///
+/// The level of a record is recovered from the severity text set by the tracing
+/// bridge. Records without one are always delegated.
+#[derive(Debug)]
+struct LevelProcessor<P> {
+    inner: P,
+    level: LevelFilter,
+    below: Option<LevelFilter>,
+}
+
+impl<P> LevelProcessor<P> {
+    fn new(inner: P, level: &Option<String>, below: &Option<String>) -> Self {
+        LevelProcessor {
+            inner,
+            level: level.as_deref().map_or(LevelFilter::TRACE, log_level),
+            below: below.as_deref().map(log_level),
+        }
+    }
+}
+
+impl<P: LogProcessor> LogProcessor for LevelProcessor<P> {
+    fn emit(&self, data: &mut SdkLogRecord, instrumentation: &InstrumentationScope) {
+        let enabled = data
+            .severity_text()
+            .and_then(|text| text.parse::<Level>().ok())
+            .is_none_or(|level| {
+                self.level >= level && self.below.is_none_or(|below| below < level)
+            });
+        if enabled {
+            self.inner.emit(data, instrumentation);
+        }
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Field Value Filtering
+//!
+//! This module provides filtering of events based on the values of their fields,
+//! complementing the target and level filtering in [`super::filters`]. Conditions are
+//! written as `field op value`, for example `http.status<500` or `audit=true`.
+
+use crate::errors::LoggingError;
+use std::{fmt, str::FromStr};
+use tracing::{
+    Event, Metadata, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::layer::{Context, Filter, Layer};
+
+/// A comparison operator used by a [`FieldCondition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// The value side of a [`FieldCondition`], or a value recorded on an event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Bool(bool),
+    Number(f64),
+    Str(String),
+}
+
+/// A single `field op value` condition evaluated against event fields.
+///
+/// Supported operators are `=` (or `==`), `!=`, `<`, `<=`, `>` and `>=`. The value is
+/// interpreted as a boolean (`true`/`false`), then as a number, and otherwise as a
+/// string, optionally surrounded by double quotes. Ordering operators only match
+/// numeric values.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::field_filter::FieldCondition;
+///
+/// let condition: FieldCondition = "http.status<500".parse().unwrap();
+/// assert_eq!(condition.field(), "http.status");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldCondition {
+    field: String,
+    op: FieldOp,
+    value: FieldValue,
+}
+
+impl FieldCondition {
+    /// Returns the name of the field this condition inspects.
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Evaluates the condition against a value recorded on an event.
+    pub fn matches(&self, value: &FieldValue) -> bool {
+        match (&self.value, value) {
+            (FieldValue::Number(expected), actual) => match actual.as_number() {
+                Some(actual) => self.compare(actual.partial_cmp(expected)),
+                None => false,
+            },
+            (FieldValue::Bool(expected), FieldValue::Bool(actual)) => match self.op {
+                FieldOp::Eq => actual == expected,
+                FieldOp::Ne => actual != expected,
+                _ => false,
+            },
+            (expected, actual) => match self.op {
+                FieldOp::Eq => actual.as_str() == expected.as_str(),
+                FieldOp::Ne => actual.as_str() != expected.as_str(),
+                _ => false,
+            },
+        }
+    }
+
+    fn compare(&self, ordering: Option<std::cmp::Ordering>) -> bool {
+        use std::cmp::Ordering::*;
+
+        match (self.op, ordering) {
+            (_, None) => false,
+            (FieldOp::Eq, Some(ord)) => ord == Equal,
+            (FieldOp::Ne, Some(ord)) => ord != Equal,
+            (FieldOp::Lt, Some(ord)) => ord == Less,
+            (FieldOp::Le, Some(ord)) => ord != Greater,
+            (FieldOp::Gt, Some(ord)) => ord == Greater,
+            (FieldOp::Ge, Some(ord)) => ord != Less,
+        }
+    }
+}
+
+impl FieldValue {
+    fn parse(raw: &str) -> Self {
+        match raw {
+            "true" => FieldValue::Bool(true),
+            "false" => FieldValue::Bool(false),
+            _ => match raw.parse::<f64>() {
+                Ok(number) => FieldValue::Number(number),
+                Err(_) => FieldValue::Str(raw.trim_matches('"').to_string()),
+            },
+        }
+    }
+
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            FieldValue::Number(number) => Some(*number),
+            FieldValue::Str(value) => value.parse().ok(),
+            FieldValue::Bool(_) => None,
+        }
+    }
+
+    fn as_str(&self) -> String {
+        match self {
+            FieldValue::Bool(value) => value.to_string(),
+            FieldValue::Number(value) => value.to_string(),
+            FieldValue::Str(value) => value.clone(),
+        }
+    }
+}
+
+impl FromStr for FieldCondition {
+    type Err = LoggingError;
+
+    fn from_str(condition: &str) -> Result<Self, Self::Err> {
+        // Two-character operators must be matched before their one-character prefixes
+        const OPERATORS: [(&str, FieldOp); 7] = [
+            ("==", FieldOp::Eq),
+            ("!=", FieldOp::Ne),
+            ("<=", FieldOp::Le),
+            (">=", FieldOp::Ge),
+            ("=", FieldOp::Eq),
+            ("<", FieldOp::Lt),
+            (">", FieldOp::Gt),
+        ];
+
+        let invalid = || LoggingError::InvalidFieldFilterError(condition.to_string());
+
+        let (index, token, op) = OPERATORS
+            .iter()
+            .filter_map(|(token, op)| condition.find(token).map(|index| (index, *token, *op)))
+            .min_by_key(|(index, token, _)| (*index, std::cmp::Reverse(token.len())))
+            .ok_or_else(invalid)?;
+
+        let field = condition[..index].trim();
+        let value = condition[index + token.len()..].trim();
+        if field.is_empty() || value.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(FieldCondition {
+            field: field.to_string(),
+            op,
+            value: FieldValue::parse(value),
+        })
+    }
+}
+
+/// Filters events by the values of their fields.
+///
+/// An event is kept when it matches at least one include condition (or no include
+/// conditions are configured) and matches none of the exclude conditions. A condition
+/// on a field that the event does not carry never matches.
+///
+/// `FieldFilter` can be used both as a [`Layer`], where it disables matching events
+/// for the whole subscriber (console and export alike), and as a per-layer [`Filter`].
+#[derive(Debug, Clone, Default)]
+pub struct FieldFilter {
+    include: Vec<FieldCondition>,
+    exclude: Vec<FieldCondition>,
+}
+
+impl FieldFilter {
+    /// Creates a field filter from textual include and exclude conditions.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LoggingError::InvalidFieldFilterError` if any condition cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logging::exporters::field_filter::FieldFilter;
+    ///
+    /// let filter = FieldFilter::new(&["audit=true".to_string()], &["http.status<500".to_string()])
+    ///     .expect("valid conditions");
+    /// assert!(!filter.is_empty());
+    /// ```
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self, LoggingError> {
+        Ok(FieldFilter {
+            include: include
+                .iter()
+                .map(|c| c.parse())
+                .collect::<Result<_, _>>()?,
+            exclude: exclude
+                .iter()
+                .map(|c| c.parse())
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Returns `true` when no conditions are configured, meaning every event is kept.
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Decides whether the given event should be kept.
+    pub fn allows(&self, event: &Event<'_>) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+
+        let mut visitor = ConditionVisitor {
+            filter: self,
+            included: self.include.is_empty(),
+            excluded: false,
+        };
+        event.record(&mut visitor);
+
+        visitor.included && !visitor.excluded
+    }
+}
+
+struct ConditionVisitor<'a> {
+    filter: &'a FieldFilter,
+    included: bool,
+    excluded: bool,
+}
+
+impl ConditionVisitor<'_> {
+    fn check(&mut self, field: &Field, value: FieldValue) {
+        let name = field.name();
+
+        if !self.included {
+            self.included = self
+                .filter
+                .include
+                .iter()
+                .any(|c| c.field == name && c.matches(&value));
+        }
+
+        if !self.excluded {
+            self.excluded = self
+                .filter
+                .exclude
+                .iter()
+                .any(|c| c.field == name && c.matches(&value));
+        }
+    }
+}
+
+impl Visit for ConditionVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.check(field, FieldValue::Number(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.check(field, FieldValue::Number(value as f64));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.check(field, FieldValue::Number(value as f64));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.check(field, FieldValue::Bool(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.check(field, FieldValue::Str(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.check(field, FieldValue::Str(format!("{value:?}")));
+    }
+}
+
+impl<S: Subscriber> Layer<S> for FieldFilter {
+    fn event_enabled(&self, event: &Event<'_>, _ctx: Context<'_, S>) -> bool {
+        self.allows(event)
+    }
+}
+
+impl<S: Subscriber> Filter<S> for FieldFilter {
+    fn enabled(&self, _meta: &Metadata<'_>, _cx: &Context<'_, S>) -> bool {
+        true
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, _cx: &Context<'_, S>) -> bool {
+        self.allows(event)
+    }
+}
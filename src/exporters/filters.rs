@@ -6,10 +6,22 @@
 //!
 //! This module provides functionality for filtering log messages based on their
 //! target and level, allowing for fine-grained control over what gets logged.
+//!
+//! The filter types and the level parser used by the crate are re-exported, so that
+//! downstream crates can build on the same filters and combine them with their own.
+//!
+//! ```
+//! use logging::exporters::filters::{self, LevelFilter, log_level};
+//!
+//! let targets = filters::target_filters("info").with_target("my_app::db", LevelFilter::DEBUG);
+//! assert_eq!(log_level("WARN"), LevelFilter::WARN);
+//! ```
 
-use super::envs::log_level;
-use tracing::level_filters::LevelFilter;
-use tracing_subscriber::filter::Targets;
+pub use super::envs::log_level;
+pub use crate::minimal::FilterPreset;
+pub use tracing_subscriber::filter::{LevelFilter, Targets};
+
+use crate::{errors::LoggingError, minimal::FilterBuilder};
 
 /// Creates a configured target filter for controlling log output verbosity.
 ///
@@ -38,20 +50,271 @@ use tracing_subscriber::filter::Targets;
 /// ```
 #[allow(dead_code)]
 pub fn target_filters(level: &str) -> Targets {
-    let level_filter = log_level(level);
-
-    Targets::new()
-        .with_default(level_filter)
-        .with_target("lapin", LevelFilter::WARN)
-        .with_target("tower", LevelFilter::WARN)
-        .with_target("h2", LevelFilter::WARN)
-        .with_target("hyper", LevelFilter::WARN)
-        .with_target("rustls", LevelFilter::WARN)
-        .with_target("paho_mqtt", LevelFilter::WARN)
-        .with_target("c_trace", LevelFilter::WARN)
-        .with_target("aws_smithy_runtime", LevelFilter::WARN)
-        .with_target("aws_config", LevelFilter::WARN)
-        .with_target("aws_sdk_secretsmanager", LevelFilter::WARN)
-        .with_target("aws_runtime", LevelFilter::WARN)
-        .with_target("opentelemetry_sdk", LevelFilter::WARN)
+    FilterBuilder::new()
+        .with_default(log_level(level))
+        .with_quiet_dependencies()
+        .build()
+}
+
+/// Creates a target filter with `level` as default level, quieting only the
+/// dependencies of the given presets.
+///
+/// [`target_filters`] quiets the dependencies of every preset. Picking the presets of
+/// the subsystems a service actually uses keeps the other targets at `level`.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::filters::{self, FilterPreset, LevelFilter};
+/// use tracing::Level;
+///
+/// let filter = filters::preset_filters("info", &[FilterPreset::Web])
+///     .with_target("my_app::db", LevelFilter::DEBUG);
+/// assert!(!filter.would_enable("hyper", &Level::INFO));
+/// assert!(filter.would_enable("lapin", &Level::INFO));
+/// assert!(filter.would_enable("my_app::db", &Level::DEBUG));
+///
+/// // Every preset makes the default filter
+/// let all = filters::preset_filters("info", &FilterPreset::ALL);
+/// assert_eq!(all.to_string(), filters::target_filters("info").to_string());
+/// ```
+pub fn preset_filters(level: &str, presets: &[FilterPreset]) -> Targets {
+    FilterBuilder::new()
+        .with_default(log_level(level))
+        .with_presets(presets)
+        .build()
+}
+
+/// Creates an allowlist target filter where nothing is logged unless explicitly allowed.
+///
+/// This is the inverse composition of [`target_filters`]: the default level is
+/// `LevelFilter::OFF`, and only the allowlisted targets are enabled. It's intended for
+/// security-sensitive setups such as audit logging, where unexpected targets must not
+/// leak into the output.
+///
+/// # Arguments
+///
+/// * `level` - The level used for allowlisted targets that don't specify their own.
+/// * `allowlist` - Entries of the form `target` or `target=level`.
+///
+/// # Returns
+///
+/// A `Targets` filter that only enables the allowlisted targets.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::filters;
+///
+/// let filter = filters::allowlist_filters(
+///     "info",
+///     &["audit".to_string(), "my_app::billing=debug".to_string()],
+/// );
+/// // Only `audit` (at INFO) and `my_app::billing` (at DEBUG) are logged
+/// ```
+pub fn allowlist_filters(level: &str, allowlist: &[String]) -> Targets {
+    allowlist.iter().fold(
+        Targets::new().with_default(LevelFilter::OFF),
+        |targets, entry| match entry.split_once('=') {
+            Some((target, target_level)) => {
+                targets.with_target(target.trim(), log_level(target_level.trim()))
+            }
+            None => targets.with_target(entry.trim(), log_level(level)),
+        },
+    )
+}
+
+/// Sets the given targets to `LevelFilter::OFF`, on top of the filters of `targets`.
+///
+/// Silenced targets override any level previously set for them, such as the WARN
+/// overrides of [`target_filters`], so they must be applied last. Their submodules are
+/// silenced too, unless a more specific target is added afterwards.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::filters;
+/// use tracing::Level;
+///
+/// let filter = filters::silence_targets(
+///     filters::target_filters("trace"),
+///     &["hyper".to_string(), "leaky_dep".to_string()],
+/// );
+/// assert!(!filter.would_enable("hyper", &Level::ERROR));
+/// assert!(!filter.would_enable("leaky_dep::auth", &Level::ERROR));
+/// assert!(filter.would_enable("my_app", &Level::TRACE));
+/// assert!(filter.would_enable("tower", &Level::WARN));
+/// ```
+pub fn silence_targets(targets: Targets, silenced: &[String]) -> Targets {
+    silenced.iter().fold(targets, |targets, target| {
+        targets.with_target(target.trim(), LevelFilter::OFF)
+    })
+}
+
+/// Parses comma-separated filter directives such as `info,sqlx=warn,my::mod=trace`.
+///
+/// A bare level sets the default level, and `target=level` entries set the level of a
+/// target and its submodules, the last one winning for a repeated target. Levels are
+/// those accepted by [`log_level`], plus `off`. Whitespace around the tokens and empty
+/// entries are ignored.
+///
+/// # Errors
+///
+/// Returns `LoggingError::InvalidDirectiveError` naming the offending token for an
+/// unknown level, a bare target without `=level`, or an empty target.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::filters;
+/// use tracing::Level;
+///
+/// let filter = filters::parse_directives(" info, sqlx=warn ,,my::mod=trace,").unwrap();
+/// assert!(filter.would_enable("my_app", &Level::INFO));
+/// assert!(!filter.would_enable("my_app", &Level::DEBUG));
+/// assert!(!filter.would_enable("sqlx::query", &Level::INFO));
+/// assert!(filter.would_enable("my::mod", &Level::TRACE));
+///
+/// // Without a bare level, nothing but the listed targets is enabled
+/// let filter = filters::parse_directives("my_app=debug").unwrap();
+/// assert!(!filter.would_enable("other", &Level::ERROR));
+/// assert!(filters::parse_directives("").is_ok());
+///
+/// for malformed in ["sqlx", "=warn", "sqlx=loud", "verbose", "a=b=c"] {
+///     let err = filters::parse_directives(malformed).unwrap_err();
+///     assert!(err.to_string().contains(malformed), "{err}");
+/// }
+/// ```
+pub fn parse_directives(directives: &str) -> Result<Targets, LoggingError> {
+    FilterBuilder::new()
+        .with_directives(directives)
+        .map(FilterBuilder::build)
+}
+
+/// Targets of the internal logs emitted by the OpenTelemetry SDK and its transport,
+/// including the OTLP client of this crate and its export timings.
+pub const OTEL_INTERNAL_TARGETS: [&str; 6] = [
+    "opentelemetry",
+    "opentelemetry_sdk",
+    "opentelemetry_otlp",
+    "tonic",
+    "logging::exporters::otlp_client",
+    "logging::exporters::export_timing",
+];
+
+/// Returns `true` if `target` is one of the [`OTEL_INTERNAL_TARGETS`] or a submodule.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::filters::is_otel_internal;
+///
+/// assert!(is_otel_internal("opentelemetry_sdk::logs"));
+/// assert!(!is_otel_internal("my_app"));
+/// ```
+pub fn is_otel_internal(target: &str) -> bool {
+    OTEL_INTERNAL_TARGETS.iter().any(|internal| {
+        target
+            .strip_prefix(internal)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+    })
+}
+
+/// Creates the target filter selected by the logging configuration.
+///
+/// Uses [`allowlist_filters`] when a target allowlist is configured, and the denylist
+/// style [`preset_filters`] of the configured presets otherwise, with the
+/// [`OTEL_INTERNAL_TARGETS`] set to the configured internal level. The silenced targets are applied last, so they win.
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub(crate) fn configured_filters(level: &str, cfgs: &crate::config::LoggingConfigs) -> Targets {
+    let targets = if cfgs.target_allowlist.is_empty() {
+        OTEL_INTERNAL_TARGETS.iter().fold(
+            preset_filters(level, &cfgs.filter_presets),
+            |targets, target| targets.with_target(*target, cfgs.otel_internal_level),
+        )
+    } else {
+        allowlist_filters(level, &cfgs.target_allowlist)
+    };
+    silence_targets(targets, &cfgs.silenced_targets)
+}
+
+/// Creates the filter enforcing the silenced targets on their own, if any.
+///
+/// Scoped levels relax [`configured_filters`]; this filter is combined with the relaxed
+/// filters so that the silenced targets stay silent.
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub(crate) fn silenced_filter(cfgs: &crate::config::LoggingConfigs) -> Option<Targets> {
+    (!cfgs.silenced_targets.is_empty()).then(|| {
+        silence_targets(
+            Targets::new().with_default(LevelFilter::TRACE),
+            &cfgs.silenced_targets,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing::Level;
+
+    #[test]
+    fn allowlist_suppresses_the_other_targets() {
+        let filter = allowlist_filters(
+            "info",
+            &["audit".to_string(), "my_app::billing=debug".to_string()],
+        );
+
+        assert!(filter.would_enable("audit", &Level::INFO));
+        assert!(!filter.would_enable("audit", &Level::DEBUG));
+        assert!(filter.would_enable("my_app::billing::invoices", &Level::DEBUG));
+        assert!(!filter.would_enable("my_app::orders", &Level::ERROR));
+        assert!(!filter.would_enable("hyper", &Level::ERROR));
+    }
+
+    #[cfg(any(feature = "sdk", feature = "bare"))]
+    #[test]
+    fn allowlisted_targets_are_the_only_ones_logged() {
+        use crate::{
+            config::{LoggingConfig, LoggingConfigs},
+            test::init_with,
+        };
+
+        let logs = init_with(LoggingConfig {
+            level: Some("info".to_string()),
+            logging: LoggingConfigs {
+                target_allowlist: vec!["audit".to_string()],
+                ..LoggingConfigs::default()
+            },
+            ..LoggingConfig::default()
+        });
+
+        tracing::info!(target: "audit", "invoice deleted");
+        tracing::error!(target: "my_app", "charge failed");
+
+        assert!(logs.contains("invoice deleted"));
+        assert!(!logs.contains("charge failed"));
+    }
+
+    #[cfg(any(feature = "sdk", feature = "bare"))]
+    #[test]
+    fn internal_targets_follow_the_configured_level() {
+        use crate::config::LoggingConfigs;
+        use tracing::level_filters::LevelFilter;
+
+        let default = configured_filters("debug", &LoggingConfigs::default());
+        let silenced = configured_filters(
+            "debug",
+            &LoggingConfigs {
+                otel_internal_level: LevelFilter::OFF,
+                ..LoggingConfigs::default()
+            },
+        );
+
+        for target in OTEL_INTERNAL_TARGETS {
+            assert!(default.would_enable(target, &Level::WARN));
+            assert!(!default.would_enable(target, &Level::INFO));
+            assert!(!silenced.would_enable(target, &Level::ERROR));
+        }
+        assert!(silenced.would_enable("my_app", &Level::DEBUG));
+    }
 }
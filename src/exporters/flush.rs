@@ -0,0 +1,202 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Interval Flushing
+//!
+//! This module provides a processor wrapper that flushes another processor at a fixed
+//! interval from a background thread. It's meant for the simple processor used by the
+//! stdout exporter, which keeps records in order but leaves the flushing of the
+//! underlying writer to the exporter, so the last records can be lost on an abrupt
+//! shutdown.
+
+use opentelemetry::InstrumentationScope;
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    logs::{LogProcessor, SdkLogRecord},
+};
+use std::{
+    sync::{Arc, Condvar, Mutex, OnceLock, PoisonError},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// A [`LogProcessor`] that calls `force_flush` on another processor at a fixed interval.
+///
+/// The flush thread is started with the first record and stopped when the processor is
+/// shut down, before the inner processor. Without an interval, the processor only
+/// delegates to the inner one.
+#[derive(Debug)]
+pub(crate) struct IntervalFlushProcessor<P> {
+    inner: Arc<P>,
+    interval: Option<Duration>,
+    flusher: OnceLock<Option<Flusher>>,
+}
+
+impl<P> IntervalFlushProcessor<P> {
+    /// Wraps `inner`, flushing it every `interval` when one is given.
+    pub(crate) fn new(inner: P, interval: Option<Duration>) -> Self {
+        IntervalFlushProcessor {
+            inner: Arc::new(inner),
+            interval,
+            flusher: OnceLock::new(),
+        }
+    }
+}
+
+impl<P: LogProcessor + 'static> LogProcessor for IntervalFlushProcessor<P> {
+    fn emit(&self, data: &mut SdkLogRecord, instrumentation: &InstrumentationScope) {
+        if let Some(interval) = self.interval {
+            self.flusher
+                .get_or_init(|| Flusher::spawn(self.inner.clone(), interval));
+        }
+        self.inner.emit(data, instrumentation);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        if let Some(Some(flusher)) = self.flusher.get() {
+            flusher.stop();
+        }
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        // The resource is set while the provider is built, before the flush thread
+        // holds a reference to the inner processor
+        if let Some(inner) = Arc::get_mut(&mut self.inner) {
+            inner.set_resource(resource);
+        }
+    }
+}
+
+/// The background thread flushing a processor, with its stop signal.
+#[derive(Debug)]
+struct Flusher {
+    stopped: Arc<(Mutex<bool>, Condvar)>,
+    thread: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Flusher {
+    /// Starts flushing `processor` every `interval`.
+    ///
+    /// Returns `None` if the thread can't be spawned, in which case records are only
+    /// flushed by the processor itself.
+    fn spawn<P: LogProcessor + 'static>(processor: Arc<P>, interval: Duration) -> Option<Self> {
+        let stopped = Arc::new((Mutex::new(false), Condvar::new()));
+
+        let signal = stopped.clone();
+        let thread = thread::Builder::new()
+            .name("logging-flush".to_string())
+            .spawn(move || {
+                let (lock, condvar) = &*signal;
+                let mut stopped = lock.lock().unwrap_or_else(PoisonError::into_inner);
+                while !*stopped {
+                    let (guard, wait) = condvar
+                        .wait_timeout(stopped, interval)
+                        .unwrap_or_else(PoisonError::into_inner);
+                    stopped = guard;
+                    if !*stopped && wait.timed_out() {
+                        let _ = processor.force_flush();
+                    }
+                }
+            })
+            .ok()?;
+
+        Some(Flusher {
+            stopped,
+            thread: Mutex::new(Some(thread)),
+        })
+    }
+
+    /// Stops the flush thread and waits for it to exit.
+    fn stop(&self) {
+        let (lock, condvar) = &*self.stopped;
+        *lock.lock().unwrap_or_else(PoisonError::into_inner) = true;
+        condvar.notify_all();
+
+        let thread = self
+            .thread
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(thread) = thread {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use opentelemetry::logs::{Logger, LoggerProvider};
+    use opentelemetry_sdk::logs::SdkLoggerProvider;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Instant,
+    };
+
+    /// A processor counting its flushes.
+    #[derive(Debug, Clone, Default)]
+    struct Flushes(Arc<AtomicUsize>);
+
+    impl Flushes {
+        fn count(&self) -> usize {
+            self.0.load(Ordering::SeqCst)
+        }
+    }
+
+    impl LogProcessor for Flushes {
+        fn emit(&self, _data: &mut SdkLogRecord, _instrumentation: &InstrumentationScope) {}
+
+        fn force_flush(&self) -> OTelSdkResult {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+            Ok(())
+        }
+    }
+
+    fn provider(flushes: &Flushes, interval: Option<Duration>) -> SdkLoggerProvider {
+        let provider = SdkLoggerProvider::builder()
+            .with_log_processor(IntervalFlushProcessor::new(flushes.clone(), interval))
+            .build();
+        let logger = provider.logger("orders");
+        logger.emit(logger.create_log_record());
+        provider
+    }
+
+    #[test]
+    fn flushes_every_interval_until_shut_down() {
+        let flushes = Flushes::default();
+        let provider = provider(&flushes, Some(Duration::from_millis(10)));
+
+        let deadline = Instant::now() + Duration::from_secs(1);
+        while flushes.count() < 3 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert!(flushes.count() >= 3);
+
+        provider.shutdown().unwrap();
+        let after_shutdown = flushes.count();
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(flushes.count(), after_shutdown);
+    }
+
+    #[test]
+    fn never_flushes_without_an_interval() {
+        let flushes = Flushes::default();
+        let provider = provider(&flushes, None);
+
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(flushes.count(), 0);
+        provider.shutdown().unwrap();
+    }
+}
@@ -0,0 +1,427 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # GELF Exporter
+//!
+//! This module provides a logging exporter for Graylog, sending events as GELF (Graylog
+//! Extended Log Format) 1.1 messages to a GELF UDP or TCP input instead of writing them
+//! to the console. It's available with the `gelf` feature.
+//!
+//! Each event becomes a JSON message with the `version`, `host`, `short_message`,
+//! `timestamp` and `level` fields. The host defaults to the machine hostname, the short
+//! message is the event message, and tracing levels are mapped to syslog severities:
+//! `ERROR` to 3, `WARN` to 4, `INFO` to 6, and `DEBUG` and `TRACE` to 7. Event fields,
+//! the event target and the service name are sent as additional fields, prefixed with
+//! an underscore (see [`field_name`]).
+//!
+//! Over UDP, messages larger than the chunk size are split into GELF chunks (see
+//! [`chunks`]). Over TCP, messages are delimited by a null byte and the connection is
+//! reopened after a failed write. Messages that can't be sent are dropped.
+//!
+//! Like the bare exporter, this exporter doesn't depend on OpenTelemetry.
+
+use crate::{
+    config::{GelfConfigs, GelfTransport, LoggingConfigs},
+    correlation::{self, CORRELATION_ID_FIELD},
+    errors::LoggingError,
+    exporters::{layers::install_subscriber, service},
+};
+use configs::app::AppConfigs;
+use serde_json::{Map, Value};
+use std::{
+    fmt,
+    io::Write,
+    net::{TcpStream, ToSocketAddrs, UdpSocket},
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::{
+    Event, Level, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::{Layer, layer::Context};
+
+/// Size of the header starting each chunk: magic bytes, message id, sequence number
+/// and sequence count.
+const CHUNK_HEADER_LEN: usize = 12;
+
+/// Maximum number of chunks of a message accepted by Graylog.
+const MAX_CHUNKS: usize = 128;
+
+/// Magic bytes identifying a chunked GELF message.
+const CHUNK_MAGIC: [u8; 2] = [0x1e, 0x0f];
+
+/// Timeout of the TCP connection and writes, so an unresponsive input can't stall the
+/// application for long.
+const TCP_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Installs and configures the GELF exporter.
+///
+/// # Errors
+///
+/// Returns `LoggingError::GelfUnavailableError` if the Graylog input can't be resolved
+/// or, over TCP, connected to, and `LoggingError::SubscriberInstallError` if the tracing
+/// subscriber can't be set.
+///
+/// Returns `LoggingError::AlreadyInitializedError` if logging is already installed, by
+/// this function, [`crate::provider::install`] or the exporter of another module.
+///
+/// # Examples
+///
+/// ```no_run
+/// use logging::exporters::gelf;
+///
+/// fn main() {
+///     gelf::install().expect("Failed to set up logging");
+///     // Now logs will be sent to the Graylog input of LOG_GELF_ADDRESS
+///     tracing::info!("Application started");
+/// }
+/// ```
+pub fn install() -> Result<(), LoggingError> {
+    crate::provider::install_exclusive("GELF", || {
+        install_with(&AppConfigs::new(), &LoggingConfigs::new())
+    })
+}
+
+/// Installs the GELF exporter with the given settings.
+pub(crate) fn install_with(
+    app_cfgs: &AppConfigs,
+    log_cfgs: &LoggingConfigs,
+) -> Result<(), LoggingError> {
+    let layer = GelfLayer::new(
+        service::name(app_cfgs, log_cfgs)?,
+        service::hostname(log_cfgs),
+        &log_cfgs.gelf,
+    )?;
+
+    install_subscriber(app_cfgs, log_cfgs, vec![layer.boxed()])
+}
+
+/// Returns the name of the GELF additional field carrying the event field `name`.
+///
+/// Additional fields are prefixed with an underscore, and characters other than
+/// letters, digits, `_`, `.` and `-` are replaced with `_`. The `id` field is sent as
+/// `_event_id`, since `_id` is reserved by Graylog.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::gelf::field_name;
+///
+/// assert_eq!(field_name("user_id"), "_user_id");
+/// assert_eq!(field_name("http.status"), "_http.status");
+/// assert_eq!(field_name("request path"), "_request_path");
+/// assert_eq!(field_name("id"), "_event_id");
+/// ```
+pub fn field_name(name: &str) -> String {
+    if name == "id" {
+        return "_event_id".to_string();
+    }
+
+    let mut field = String::with_capacity(name.len() + 1);
+    field.push('_');
+    field.extend(name.chars().map(|c| {
+        if c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-') {
+            c
+        } else {
+            '_'
+        }
+    }));
+    field
+}
+
+/// Splits a GELF message into the UDP datagrams sending it.
+///
+/// A message fitting in `chunk_size` bytes is sent as a single datagram, as is. Larger
+/// messages are split into chunks of at most `chunk_size` bytes, each starting with
+/// the chunk header: the `0x1e 0x0f` magic bytes, `message_id`, the sequence number
+/// and the sequence count. Returns `None` when the message would need more than the
+/// 128 chunks Graylog accepts.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::gelf::chunks;
+///
+/// let id = [1, 2, 3, 4, 5, 6, 7, 8];
+///
+/// // A message at the limit is sent unchunked
+/// let datagrams = chunks(&[b'x'; 100], 100, id).unwrap();
+/// assert_eq!(datagrams, vec![vec![b'x'; 100]]);
+///
+/// // One more byte needs two chunks, each with a 12-byte header
+/// let datagrams = chunks(&[b'x'; 101], 100, id).unwrap();
+/// assert_eq!(datagrams.len(), 2);
+/// assert_eq!(datagrams[0][..12], [0x1e, 0x0f, 1, 2, 3, 4, 5, 6, 7, 8, 0, 2]);
+/// assert_eq!(datagrams[0].len(), 100);
+/// assert_eq!(datagrams[1][..12], [0x1e, 0x0f, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2]);
+/// assert_eq!(datagrams[1].len(), 12 + 13);
+///
+/// // Messages over 128 chunks are rejected
+/// assert!(chunks(&[b'x'; 128 * 88], 100, id).is_some());
+/// assert!(chunks(&[b'x'; 128 * 88 + 1], 100, id).is_none());
+/// ```
+pub fn chunks(message: &[u8], chunk_size: usize, message_id: [u8; 8]) -> Option<Vec<Vec<u8>>> {
+    if message.len() <= chunk_size {
+        return Some(vec![message.to_vec()]);
+    }
+
+    let data_len = chunk_size.saturating_sub(CHUNK_HEADER_LEN).max(1);
+    let count = message.len().div_ceil(data_len);
+    if count > MAX_CHUNKS {
+        return None;
+    }
+
+    let datagrams = message
+        .chunks(data_len)
+        .enumerate()
+        .map(|(sequence, data)| {
+            let mut datagram = Vec::with_capacity(CHUNK_HEADER_LEN + data.len());
+            datagram.extend_from_slice(&CHUNK_MAGIC);
+            datagram.extend_from_slice(&message_id);
+            datagram.push(sequence as u8);
+            datagram.push(count as u8);
+            datagram.extend_from_slice(data);
+            datagram
+        })
+        .collect();
+    Some(datagrams)
+}
+
+/// Returns the syslog severity of a tracing level.
+fn syslog_level(level: &Level) -> u8 {
+    match *level {
+        Level::ERROR => 3,
+        Level::WARN => 4,
+        Level::INFO => 6,
+        Level::DEBUG | Level::TRACE => 7,
+    }
+}
+
+/// Returns a new chunked message id, unique enough to tell concurrent messages apart.
+fn message_id() -> [u8; 8] {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+    (nanos ^ COUNTER.fetch_add(1, Ordering::Relaxed).rotate_left(32)).to_be_bytes()
+}
+
+/// Destination of the GELF messages.
+#[derive(Debug)]
+enum Transport {
+    Udp {
+        socket: UdpSocket,
+        chunk_size: usize,
+    },
+    Tcp {
+        address: String,
+        stream: Mutex<Option<TcpStream>>,
+    },
+}
+
+impl Transport {
+    /// Opens the transport to the input at `cfgs.address`.
+    fn open(cfgs: &GelfConfigs) -> Result<Self, LoggingError> {
+        let unavailable = |err: std::io::Error| {
+            LoggingError::GelfUnavailableError(format!("{}: {err}", cfgs.address))
+        };
+
+        match cfgs.transport {
+            GelfTransport::Udp => {
+                let address = cfgs
+                    .address
+                    .to_socket_addrs()
+                    .map_err(unavailable)?
+                    .next()
+                    .ok_or_else(|| {
+                        LoggingError::GelfUnavailableError(format!(
+                            "{}: no address found",
+                            cfgs.address
+                        ))
+                    })?;
+                let local = if address.is_ipv4() {
+                    "0.0.0.0:0"
+                } else {
+                    "[::]:0"
+                };
+
+                let socket = UdpSocket::bind(local).map_err(unavailable)?;
+                socket.connect(address).map_err(unavailable)?;
+                Ok(Transport::Udp {
+                    socket,
+                    chunk_size: cfgs.chunk_size,
+                })
+            }
+            GelfTransport::Tcp => Ok(Transport::Tcp {
+                stream: Mutex::new(Some(connect(&cfgs.address).map_err(unavailable)?)),
+                address: cfgs.address.clone(),
+            }),
+        }
+    }
+
+    /// Sends a message, dropping it if it can't be sent.
+    fn send(&self, message: &[u8]) {
+        match self {
+            Transport::Udp { socket, chunk_size } => {
+                for datagram in chunks(message, *chunk_size, message_id()).unwrap_or_default() {
+                    if socket.send(&datagram).is_err() {
+                        return;
+                    }
+                }
+            }
+            Transport::Tcp { address, stream } => {
+                let mut stream = stream.lock().unwrap_or_else(PoisonError::into_inner);
+
+                // A failed write drops the connection, reopened for the next message
+                if stream.is_none() {
+                    *stream = connect(address).ok();
+                }
+                if let Some(connection) = stream.as_mut()
+                    && (connection.write_all(message).is_err()
+                        || connection.write_all(&[0]).is_err())
+                {
+                    *stream = None;
+                }
+            }
+        }
+    }
+}
+
+/// Connects to the TCP input at `address`.
+fn connect(address: &str) -> std::io::Result<TcpStream> {
+    let mut last_err = None;
+    for address in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, TCP_TIMEOUT) {
+            Ok(stream) => {
+                stream.set_write_timeout(Some(TCP_TIMEOUT))?;
+                return Ok(stream);
+            }
+            Err(err) => last_err = Some(err),
+        }
+    }
+    Err(last_err.unwrap_or_else(|| std::io::Error::other("no address found")))
+}
+
+/// A layer sending events as GELF messages.
+#[derive(Debug)]
+struct GelfLayer {
+    host: String,
+    service_name: String,
+    transport: Transport,
+}
+
+impl GelfLayer {
+    fn new(
+        service_name: String,
+        hostname: Option<String>,
+        cfgs: &GelfConfigs,
+    ) -> Result<Self, LoggingError> {
+        Ok(GelfLayer {
+            host: cfgs
+                .host
+                .clone()
+                .or(hostname)
+                .unwrap_or_else(|| "localhost".to_string()),
+            service_name,
+            transport: Transport::open(cfgs)?,
+        })
+    }
+}
+
+/// Collects the fields of an event into GELF fields.
+struct Collector {
+    message: Option<String>,
+    fields: Map<String, Value>,
+}
+
+impl Collector {
+    fn insert(&mut self, field: &Field, value: Value) {
+        if field.name() == "message" {
+            self.message = Some(match value {
+                Value::String(message) => message,
+                value => value.to_string(),
+            });
+        } else {
+            self.fields.insert(field_name(field.name()), value);
+        }
+    }
+}
+
+impl Visit for Collector {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, Value::from(format!("{value:?}")));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, Value::from(value));
+    }
+
+    // GELF values are strings or numbers
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, Value::from(value.to_string()));
+    }
+}
+
+impl<S: Subscriber> Layer<S> for GelfLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut collector = Collector {
+            message: None,
+            fields: Map::new(),
+        };
+        event.record(&mut collector);
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0.0, |elapsed| elapsed.as_millis() as f64 / 1000.0);
+
+        let mut message = Map::new();
+        message.insert("version".to_string(), Value::from("1.1"));
+        message.insert("host".to_string(), Value::from(self.host.as_str()));
+        message.insert(
+            "short_message".to_string(),
+            Value::from(
+                collector
+                    .message
+                    .filter(|message| !message.is_empty())
+                    .unwrap_or_else(|| metadata.name().to_string()),
+            ),
+        );
+        message.insert("timestamp".to_string(), Value::from(timestamp));
+        message.insert(
+            "level".to_string(),
+            Value::from(syslog_level(metadata.level())),
+        );
+        message.insert(
+            "_service".to_string(),
+            Value::from(self.service_name.as_str()),
+        );
+        message.insert("_target".to_string(), Value::from(metadata.target()));
+        if let Some(id) = correlation::current() {
+            message.insert(field_name(CORRELATION_ID_FIELD), Value::from(&*id));
+        }
+        message.extend(collector.fields);
+
+        if let Ok(message) = serde_json::to_vec(&message) {
+            self.transport.send(&message);
+        }
+    }
+}
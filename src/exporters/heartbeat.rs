@@ -0,0 +1,113 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Heartbeat
+//!
+//! This module emits a periodic "still alive" event from a background thread, for
+//! liveness monitoring in environments without a proper health check: an alert fires
+//! when the heartbeats of a service stop reaching the backend.
+//!
+//! Each heartbeat goes through the installed subscriber like any other event, with the
+//! milliseconds elapsed since logging was installed (`uptime_ms`) and its sequence
+//! number (`beat`). With a logger provider, the export counters of
+//! [`crate::provider::export_stats`] are reported too (`exported` and `lost`).
+
+use std::{
+    sync::{Arc, Condvar, Mutex, PoisonError},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+use tracing::Level;
+
+/// The heartbeat thread of the installed logging system, if any.
+static HEARTBEAT: Mutex<Option<Heartbeat>> = Mutex::new(None);
+
+/// The background thread emitting the heartbeats, with its stop signal.
+///
+/// Only the shutdown of the logger provider stops it; without one, it runs until the
+/// process exits.
+#[derive(Debug)]
+#[cfg_attr(not(feature = "sdk"), allow(dead_code))]
+struct Heartbeat {
+    stopped: Arc<(Mutex<bool>, Condvar)>,
+    thread: JoinHandle<()>,
+}
+
+/// Starts emitting a heartbeat at `level` every `interval`.
+///
+/// Nothing is emitted if the thread can't be spawned, e.g. on WebAssembly.
+pub(crate) fn start(interval: Duration, level: Level) {
+    let mut heartbeat = HEARTBEAT.lock().unwrap_or_else(PoisonError::into_inner);
+    if heartbeat.is_some() {
+        return;
+    }
+
+    let stopped = Arc::new((Mutex::new(false), Condvar::new()));
+    let signal = stopped.clone();
+    let started = Instant::now();
+    let thread = thread::Builder::new()
+        .name("logging-heartbeat".to_string())
+        .spawn(move || {
+            let (lock, condvar) = &*signal;
+            let mut stopped = lock.lock().unwrap_or_else(PoisonError::into_inner);
+            let mut beat: u64 = 0;
+            while !*stopped {
+                let (guard, wait) = condvar
+                    .wait_timeout(stopped, interval)
+                    .unwrap_or_else(PoisonError::into_inner);
+                stopped = guard;
+                if !*stopped && wait.timed_out() {
+                    beat += 1;
+                    emit(level, started.elapsed(), beat);
+                }
+            }
+        });
+
+    if let Ok(thread) = thread {
+        *heartbeat = Some(Heartbeat { stopped, thread });
+    }
+}
+
+/// Stops the heartbeat thread, if started, and waits for it to exit.
+#[cfg(feature = "sdk")]
+pub(crate) fn stop() {
+    let heartbeat = HEARTBEAT
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    let Some(Heartbeat { stopped, thread }) = heartbeat else {
+        return;
+    };
+
+    let (lock, condvar) = &*stopped;
+    *lock.lock().unwrap_or_else(PoisonError::into_inner) = true;
+    condvar.notify_all();
+    let _ = thread.join();
+}
+
+/// Emits the heartbeat event of the given sequence number.
+fn emit(level: Level, uptime: Duration, beat: u64) {
+    let uptime_ms = uptime.as_millis() as u64;
+    #[cfg(feature = "sdk")]
+    let (exported, lost) = (
+        Some(super::stats::snapshot().exported),
+        Some(super::stats::lost()),
+    );
+    #[cfg(not(feature = "sdk"))]
+    let (exported, lost): (Option<u64>, Option<u64>) = (None, None);
+
+    macro_rules! heartbeat {
+        ($level:expr) => {
+            tracing::event!($level, uptime_ms, beat, exported, lost, "heartbeat")
+        };
+    }
+
+    match level {
+        Level::TRACE => heartbeat!(Level::TRACE),
+        Level::DEBUG => heartbeat!(Level::DEBUG),
+        Level::INFO => heartbeat!(Level::INFO),
+        Level::WARN => heartbeat!(Level::WARN),
+        Level::ERROR => heartbeat!(Level::ERROR),
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Journald Exporter
+//!
+//! This module provides a logging exporter for systemd hosts, writing events to the
+//! journal instead of the console so that `journalctl` and its field matches work on
+//! them. It's only available on Linux, with the `journald` feature.
+//!
+//! Tracing levels are mapped to journal priorities: `ERROR` to `err` (3), `WARN` to
+//! `warning` (4), `INFO` to `notice` (5), `DEBUG` to `info` (6) and `TRACE` to
+//! `debug` (7). Event fields are forwarded as journal fields, upper-cased and prefixed
+//! with `F` so they can't clash with the journal's own fields (`http.status` becomes
+//! `FHTTP_STATUS`). The service name is used as the syslog identifier.
+//!
+//! Like the bare exporter, this exporter doesn't depend on OpenTelemetry.
+
+use crate::{
+    config::LoggingConfigs,
+    errors::LoggingError,
+    exporters::{layers::install_subscriber, service},
+};
+use configs::app::AppConfigs;
+use tracing_subscriber::Layer;
+
+/// Installs and configures the journald exporter.
+///
+/// # Errors
+///
+/// Returns `LoggingError::JournaldUnavailableError` if the journal socket can't be
+/// reached, e.g. in a container without systemd, and
+/// `LoggingError::SubscriberInstallError` if the tracing subscriber can't be set.
+///
+/// Returns `LoggingError::AlreadyInitializedError` if logging is already installed, by
+/// this function, [`crate::provider::install`] or the exporter of another module.
+///
+/// # Examples
+///
+/// ```no_run
+/// use logging::exporters::journald;
+///
+/// fn main() {
+///     journald::install().expect("Failed to set up logging");
+///     // Now logs will be written to the systemd journal
+///     tracing::info!("Application started");
+/// }
+/// ```
+pub fn install() -> Result<(), LoggingError> {
+    crate::provider::install_exclusive("journald", || {
+        install_with(&AppConfigs::new(), &LoggingConfigs::new())
+    })
+}
+
+/// Installs the journald exporter with the given settings.
+pub(crate) fn install_with(
+    app_cfgs: &AppConfigs,
+    log_cfgs: &LoggingConfigs,
+) -> Result<(), LoggingError> {
+    let layer = tracing_journald::layer()
+        .map_err(|err| LoggingError::JournaldUnavailableError(err.to_string()))?
+        .with_syslog_identifier(service::name(app_cfgs, log_cfgs)?);
+
+    install_subscriber(app_cfgs, log_cfgs, vec![layer.boxed()])
+}
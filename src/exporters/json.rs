@@ -0,0 +1,492 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # JSON Output Shaping
+//!
+//! This module provides a writer wrapper that reshapes the JSON records produced by the
+//! Bunyan formatting layer before they reach their destination. It's used by the
+//! exporters for non-local environments, where logs are emitted as JSON.
+//!
+//! Each record is written as a single JSON object followed by a newline. The wrapper
+//! buffers bytes until a full line is available, applies the configured transformations
+//! and writes the result. Lines that aren't valid JSON objects are passed through
+//! unchanged, and when no transformation is configured bytes are written straight
+//! through without being parsed.
+//!
+//! Records written within [`crate::correlation::with_correlation_id`] also get a
+//! `correlation_id` field, since the Bunyan layer doesn't carry the span fields.
+//!
+//! Events carrying their own time in a timestamp field, such as replayed events, have
+//! the time of their record taken from it, see [`JsonWriter::with_timestamp_field`].
+
+use crate::correlation::{self, CORRELATION_ID_FIELD};
+use serde_json::{Map, Value};
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    sync::Arc,
+};
+use time::{OffsetDateTime, UtcOffset, format_description::well_known::Rfc3339};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// The field of the Bunyan records holding the hostname.
+const HOSTNAME_FIELD: &str = "hostname";
+
+/// The fields holding the time of the records, in the Bunyan and plain JSON layouts.
+const TIME_FIELDS: [&str; 2] = ["time", "timestamp"];
+
+/// The standard fields of the Bunyan records that can be omitted.
+pub const BUNYAN_STANDARD_FIELDS: [&str; 9] = [
+    "v", "pid", "hostname", "name", "level", "time", "target", "line", "file",
+];
+
+/// A [`MakeWriter`] that reshapes JSON records written through it.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::json::JsonWriter;
+/// use tracing_bunyan_formatter::BunyanFormattingLayer;
+///
+/// let writer = JsonWriter::new(std::io::stdout).with_nested_fields(true);
+/// let layer = BunyanFormattingLayer::new("my-service".to_string(), writer);
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonWriter<M> {
+    make_writer: M,
+    nested_fields: bool,
+    field_names: HashMap<String, String>,
+    hostname: Option<String>,
+    omitted_fields: Vec<String>,
+    indented: bool,
+    marker: Option<String>,
+    timestamp_field: Option<String>,
+}
+
+impl<M> JsonWriter<M> {
+    /// Wraps the given writer factory without any transformation enabled.
+    pub fn new(make_writer: M) -> Self {
+        JsonWriter {
+            make_writer,
+            nested_fields: false,
+            field_names: HashMap::new(),
+            hostname: None,
+            omitted_fields: Vec::new(),
+            indented: false,
+            marker: None,
+            timestamp_field: None,
+        }
+    }
+
+    /// Reconstructs nested JSON objects from dotted field names.
+    ///
+    /// With this enabled, a record containing `http.method` and `http.status` is written
+    /// with an `http` object holding `method` and `status`. Fields without dots stay at
+    /// the top level. When a dotted name collides with an existing non-object value (a
+    /// field named both `a` and `a.b`), the plain field wins and the dotted field is kept
+    /// flat under its original name, so no data is lost. Between two colliding dotted
+    /// names, such as `a.b` and `a.b.c`, the first one in the record is nested.
+    pub fn with_nested_fields(mut self, nested_fields: bool) -> Self {
+        self.nested_fields = nested_fields;
+        self
+    }
+
+    /// Renames the top-level fields of the records, from their emitted name to a new one.
+    ///
+    /// This adapts the Bunyan records to the schema expected by a log pipeline, e.g.
+    /// `time` to `@timestamp`. Fields without an entry keep their name and their place.
+    /// A field renamed to the name of another field replaces it. Renaming happens before
+    /// nesting, so a field renamed to `log.level` is nested with nested fields enabled.
+    pub fn with_field_names(mut self, field_names: HashMap<String, String>) -> Self {
+        self.field_names = field_names;
+        self
+    }
+
+    /// Replaces the `hostname` field of the records, when they have one.
+    ///
+    /// The Bunyan records carry the hostname detected by the formatter, which is
+    /// overridden with the configured one, e.g. in containers.
+    pub fn with_hostname(mut self, hostname: Option<String>) -> Self {
+        self.hostname = hostname;
+        self
+    }
+
+    /// Removes top-level fields from the records, by their emitted name.
+    ///
+    /// This drops the standard Bunyan fields that are redundant in a given environment,
+    /// such as `pid` and `hostname` in containers, whose resource attributes already
+    /// identify the instance. Fields are removed before renaming.
+    pub fn with_omitted_fields(mut self, omitted_fields: Vec<String>) -> Self {
+        self.omitted_fields = omitted_fields;
+        self
+    }
+
+    /// Writes each record indented over several lines, for reading in a terminal.
+    ///
+    /// Records stay followed by a newline, so they remain delimited.
+    pub fn with_indentation(mut self, indented: bool) -> Self {
+        self.indented = indented;
+        self
+    }
+
+    /// Prefixes each record with a marker, telling it apart from the other lines of a
+    /// shared stream.
+    pub fn with_marker(mut self, marker: Option<String>) -> Self {
+        self.marker = marker;
+        self
+    }
+
+    /// Sets the time of the records from the event field named `timestamp_field`.
+    ///
+    /// The field holds an RFC 3339 string or an integer number of milliseconds since the
+    /// Unix epoch, and replaces the `time` of the Bunyan records, or the `timestamp` of
+    /// the plain JSON ones, written in RFC 3339 in UTC. Records without the field, or with
+    /// a value that can't be read, keep the time they were formatted at. The field itself
+    /// is left on the record.
+    pub fn with_timestamp_field(mut self, timestamp_field: Option<String>) -> Self {
+        self.timestamp_field = timestamp_field;
+        self
+    }
+
+    fn is_passthrough(&self) -> bool {
+        !self.nested_fields
+            && self.field_names.is_empty()
+            && self.hostname.is_none()
+            && self.omitted_fields.is_empty()
+            && !self.indented
+            && self.marker.is_none()
+            && self.timestamp_field.is_none()
+    }
+
+    fn transform(&self, line: &[u8], correlation_id: Option<&str>) -> Option<Vec<u8>> {
+        let Ok(Value::Object(mut record)) = serde_json::from_slice::<Value>(line) else {
+            return None;
+        };
+
+        if let Some(id) = correlation_id
+            && !record.contains_key(CORRELATION_ID_FIELD)
+        {
+            record.insert(CORRELATION_ID_FIELD.to_string(), Value::from(id));
+        }
+        if let Some(time) = self
+            .timestamp_field
+            .as_ref()
+            .and_then(|field| record.get(field))
+            .and_then(parse_timestamp)
+        {
+            for field in TIME_FIELDS {
+                if let Some(value) = record.get_mut(field) {
+                    *value = Value::from(time.as_str());
+                }
+            }
+        }
+        for field in &self.omitted_fields {
+            record.remove(field);
+        }
+        if let Some(hostname) = &self.hostname
+            && let Some(value) = record.get_mut(HOSTNAME_FIELD)
+        {
+            *value = Value::from(hostname.as_str());
+        }
+        if !self.field_names.is_empty() {
+            record = rename_fields(record, &self.field_names);
+        }
+        if self.nested_fields {
+            record = nest_fields(record);
+        }
+
+        let record = Value::Object(record);
+        let mut output = self.marker.clone().unwrap_or_default().into_bytes();
+        if self.indented {
+            serde_json::to_writer_pretty(&mut output, &record).ok()?;
+        } else {
+            serde_json::to_writer(&mut output, &record).ok()?;
+        }
+        output.push(b'\n');
+        Some(output)
+    }
+}
+
+impl<'a, M: MakeWriter<'a> + 'a> MakeWriter<'a> for JsonWriter<M> {
+    type Writer = JsonLineWriter<'a, M, M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        JsonLineWriter {
+            config: self,
+            inner: self.make_writer.make_writer(),
+            buffer: Vec::new(),
+            correlation_id: correlation::current(),
+        }
+    }
+}
+
+/// The writer produced by [`JsonWriter`], reshaping complete lines as they are written.
+pub struct JsonLineWriter<'a, M, W: Write> {
+    config: &'a JsonWriter<M>,
+    inner: W,
+    buffer: Vec<u8>,
+    correlation_id: Option<Arc<str>>,
+}
+
+impl<M, W: Write> JsonLineWriter<'_, M, W> {
+    fn write_lines(&mut self) -> io::Result<()> {
+        while let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            match self.config.transform(&line, self.correlation_id.as_deref()) {
+                Some(output) => self.inner.write_all(&output)?,
+                None => self.inner.write_all(&line)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<M, W: Write> Write for JsonLineWriter<'_, M, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.config.is_passthrough() && self.correlation_id.is_none() {
+            return self.inner.write(buf);
+        }
+
+        self.buffer.extend_from_slice(buf);
+        self.write_lines()?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_lines()?;
+        self.inner.flush()
+    }
+}
+
+impl<M, W: Write> Drop for JsonLineWriter<'_, M, W> {
+    fn drop(&mut self) {
+        let _ = self.write_lines();
+        // A trailing record without newline is still written, untransformed
+        if !self.buffer.is_empty() {
+            let _ = self.inner.write_all(&self.buffer);
+        }
+        let _ = self.inner.flush();
+    }
+}
+
+/// Reads a timestamp from an RFC 3339 string or a number of milliseconds since the
+/// Unix epoch, and formats it in RFC 3339 in UTC.
+fn parse_timestamp(value: &Value) -> Option<String> {
+    let time = match value {
+        Value::String(value) => OffsetDateTime::parse(value, &Rfc3339).ok()?,
+        Value::Number(millis) => {
+            let nanos = i128::from(millis.as_u64()?) * 1_000_000;
+            OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()?
+        }
+        _ => return None,
+    };
+    time.to_offset(UtcOffset::UTC).format(&Rfc3339).ok()
+}
+
+/// Checks that the fields to omit are standard Bunyan fields.
+///
+/// # Errors
+///
+/// Returns `LoggingError::InvalidConfigError` naming the first field that isn't one of
+/// the [`BUNYAN_STANDARD_FIELDS`].
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub(crate) fn check_omitted_fields(fields: &[String]) -> Result<(), crate::errors::LoggingError> {
+    match fields
+        .iter()
+        .find(|field| !BUNYAN_STANDARD_FIELDS.contains(&field.as_str()))
+    {
+        Some(field) => Err(crate::errors::LoggingError::InvalidConfigError(format!(
+            "cannot omit `{field}`, expected one of {}",
+            BUNYAN_STANDARD_FIELDS.join(", ")
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Renames the keys of a JSON object found in `field_names`, keeping their order.
+fn rename_fields(
+    record: Map<String, Value>,
+    field_names: &HashMap<String, String>,
+) -> Map<String, Value> {
+    let mut renamed = Map::with_capacity(record.len());
+    for (key, value) in record {
+        match field_names.get(&key) {
+            Some(name) => {
+                renamed.insert(name.clone(), value);
+            }
+            // A field already replaced by a renamed one is dropped
+            None => {
+                renamed.entry(key).or_insert(value);
+            }
+        }
+    }
+    renamed
+}
+
+/// Rebuilds nested objects from the dotted keys of a JSON object.
+fn nest_fields(record: Map<String, Value>) -> Map<String, Value> {
+    let (dotted, plain): (Vec<_>, Vec<_>) =
+        record.into_iter().partition(|(key, _)| key.contains('.'));
+
+    let mut nested: Map<String, Value> = plain.into_iter().collect();
+    for (key, value) in dotted {
+        if key.split('.').any(str::is_empty) {
+            nested.insert(key, value);
+            continue;
+        }
+
+        if let Err(value) = insert_path(&mut nested, &key, value) {
+            nested.insert(key, value);
+        }
+    }
+    nested
+}
+
+/// Inserts `value` at the dotted `path`, handing it back when the path collides.
+fn insert_path(object: &mut Map<String, Value>, path: &str, value: Value) -> Result<(), Value> {
+    let Some((head, rest)) = path.split_once('.') else {
+        if object.contains_key(path) {
+            return Err(value);
+        }
+        object.insert(path.to_string(), value);
+        return Ok(());
+    };
+
+    match object
+        .entry(head.to_string())
+        .or_insert_with(|| Value::Object(Map::new()))
+    {
+        Value::Object(child) => insert_path(child, rest, value),
+        _ => Err(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::Capture;
+    use tracing_bunyan_formatter::BunyanFormattingLayer;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// Writes `record` as a line through `writer`.
+    fn write(writer: &JsonWriter<Capture>, record: &str) {
+        writeln!(writer.make_writer(), "{record}").unwrap();
+    }
+
+    #[test]
+    fn renames_fields_in_place() {
+        let capture = Capture::default();
+        let writer = JsonWriter::new(capture.clone()).with_field_names(HashMap::from([
+            ("time".to_string(), "@timestamp".to_string()),
+            ("level".to_string(), "log.level".to_string()),
+            ("msg".to_string(), "message".to_string()),
+        ]));
+
+        write(
+            &writer,
+            r#"{"time":"2025-01-01T00:00:00Z","level":30,"msg":"hi","user":"ann"}"#,
+        );
+
+        assert_eq!(
+            capture.output(),
+            "{\"@timestamp\":\"2025-01-01T00:00:00Z\",\"log.level\":30,\"message\":\"hi\",\"user\":\"ann\"}\n"
+        );
+    }
+
+    #[test]
+    fn omitted_fields_are_removed_from_the_records() {
+        let capture = Capture::default();
+        let writer = JsonWriter::new(capture.clone())
+            .with_omitted_fields(vec!["pid".to_string(), "hostname".to_string()]);
+        let subscriber = tracing_subscriber::registry()
+            .with(BunyanFormattingLayer::new("my-service".to_string(), writer));
+
+        tracing::subscriber::with_default(subscriber, || tracing::info!("order charged"));
+
+        let record: Value = serde_json::from_str(capture.output().trim()).unwrap();
+        assert!(record.get("pid").is_none());
+        assert!(record.get("hostname").is_none());
+        for field in ["v", "name", "msg", "level", "time", "target"] {
+            assert!(record.get(field).is_some(), "missing {field}");
+        }
+    }
+
+    #[test]
+    fn timestamp_field_sets_the_time_of_the_records() {
+        let capture = Capture::default();
+        let writer =
+            JsonWriter::new(capture.clone()).with_timestamp_field(Some("event_time".to_string()));
+        let subscriber = tracing_subscriber::registry()
+            .with(BunyanFormattingLayer::new("replay".to_string(), writer));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(event_time = "2024-05-01T14:00:00+02:00", "order shipped");
+            tracing::info!(event_time = 1_714_564_800_000u64, "order delivered");
+            tracing::info!(event_time = "yesterday", "order returned");
+            tracing::info!("order refunded");
+        });
+
+        let records: Vec<Value> = capture
+            .output()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records[0]["time"], "2024-05-01T12:00:00Z");
+        assert_eq!(records[0]["event_time"], "2024-05-01T14:00:00+02:00");
+        assert_eq!(records[1]["time"], "2024-05-01T12:00:00Z");
+
+        // Unreadable or missing, the time is the one the record was formatted at
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[2]["msg"], "order returned");
+        assert!(records[2]["time"].as_str().unwrap() > "2025");
+        assert!(records[3]["time"].as_str().unwrap() > "2025");
+    }
+
+    /// Returns the record written through a nesting writer for `record`.
+    fn nested(record: &str) -> String {
+        let capture = Capture::default();
+        write(
+            &JsonWriter::new(capture.clone()).with_nested_fields(true),
+            record,
+        );
+        capture.output().trim_end().to_string()
+    }
+
+    #[test]
+    fn nests_dotted_fields_at_any_depth() {
+        assert_eq!(
+            nested(
+                r#"{"msg":"hi","http.method":"GET","http.response.status":200,"http.response.body.size":12}"#
+            ),
+            r#"{"msg":"hi","http":{"method":"GET","response":{"status":200,"body":{"size":12}}}}"#
+        );
+    }
+
+    #[test]
+    fn plain_fields_win_over_dotted_ones() {
+        // Whatever their order in the record
+        assert_eq!(nested(r#"{"a":1,"a.b":2}"#), r#"{"a":1,"a.b":2}"#);
+        assert_eq!(nested(r#"{"a.b":2,"a":1}"#), r#"{"a":1,"a.b":2}"#);
+    }
+
+    #[test]
+    fn first_dotted_field_wins_over_a_colliding_one() {
+        assert_eq!(
+            nested(r#"{"a.b":1,"a.b.c":2}"#),
+            r#"{"a":{"b":1},"a.b.c":2}"#
+        );
+        assert_eq!(
+            nested(r#"{"a.b.c":2,"a.b":1}"#),
+            r#"{"a":{"b":{"c":2}},"a.b":1}"#
+        );
+    }
+
+    #[test]
+    fn keeps_malformed_dotted_names_flat() {
+        assert_eq!(
+            nested(r#"{"a.":1,".b":2,"c..d":3}"#),
+            r#"{"a.":1,".b":2,"c..d":3}"#
+        );
+    }
+}
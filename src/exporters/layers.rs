@@ -0,0 +1,1372 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Subscriber Assembly
+//!
+//! This module contains the pieces shared by every exporter to build and install the
+//! global tracing subscriber: the console formatting layers and the registry assembly
+//! that applies the configured filters.
+//!
+//! Filters are applied per layer rather than globally. The exporter layers (console
+//! output and OpenTelemetry bridge) share the target and field filters, while the audit
+//! stream keeps its own always-on filter, so audit events are never suppressed by the
+//! configured log level. The console and the export bridge can each narrow the shared
+//! level further with their own target filter, see [`signal_layer`].
+
+use crate::{
+    config::{
+        BaseFormat, ConsoleStream, LoggingConfigs, RenderMode, StructuredFormat, resolve_format,
+    },
+    errors::LoggingError,
+    exporters::{
+        audit,
+        colors::{Colored, LevelColors},
+        combined_filter::CombinedFilter,
+        envs::{self, log_level},
+        error_buffer::ErrorBuffer,
+        field_filter::FieldFilter,
+        filters::{configured_filters, silenced_filter},
+        heartbeat,
+        json::{self, JsonWriter},
+        log_bridge,
+        message_filter::MessageFilter,
+        process_fields::{self, ProcessFieldsLayer},
+        recent,
+        sampling::TargetSamplingFilter,
+        service, size,
+        timing::SpanTimingLayer,
+        truncate::TruncateLayer,
+        volume,
+        writer::{BrokenPipeGuard, SharedWriter, WeakWriteBuffer, WriteBuffer, WriteLock},
+    },
+    minimal::resolve_level,
+    profile::Profile,
+};
+use configs::app::AppConfigs;
+#[cfg(feature = "sdk")]
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    fs::OpenOptions,
+    io::IsTerminal,
+    path::Path,
+    sync::{Arc, Mutex, OnceLock, PoisonError},
+};
+use tracing::{Event, Level, level_filters::LevelFilter};
+use tracing_bunyan_formatter::BunyanFormattingLayer;
+use tracing_subscriber::{
+    Layer, Registry,
+    filter::filter_fn,
+    fmt::{
+        FmtContext, FormatEvent, Layer as FmtLayer, MakeWriter,
+        format::{DefaultFields, Writer},
+    },
+    layer::{Filter, Layered, SubscriberExt},
+    reload,
+};
+
+/// A type-erased layer that can be added to the subscriber registry.
+pub(crate) type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// The write buffers of the console destinations, flushed on demand.
+///
+/// They're held weakly, so the buffers of a dropped subscriber are released along with
+/// their flush threads.
+static CONSOLE_BUFFERS: Mutex<Vec<WeakWriteBuffer<SharedWriter>>> = Mutex::new(Vec::new());
+
+/// A type-erased per-layer filter.
+type BoxedFilter = Box<dyn Filter<Registry> + Send + Sync>;
+
+/// The handle replacing the filter of the exporter layers, once installed.
+static MAIN_FILTER: OnceLock<FilterHandle> = OnceLock::new();
+
+/// The default level and settings the filter of the exporter layers was last built from.
+static FILTER_SETTINGS: Mutex<Option<(LevelFilter, LoggingConfigs)>> = Mutex::new(None);
+
+/// The levels of targets set at runtime, with the generation of each override.
+static TARGET_OVERRIDES: Mutex<BTreeMap<String, (LevelFilter, u64)>> = Mutex::new(BTreeMap::new());
+
+/// The generation of the last target override.
+#[cfg(feature = "sdk")]
+static OVERRIDE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// A user-supplied event format for the base console layer.
+pub type CustomFormat = Box<dyn FormatEvent<Registry, DefaultFields> + Send + Sync>;
+
+/// Console settings that can't be expressed in [`LoggingConfigs`], such as trait
+/// objects supplied through [`crate::builder::LoggingBuilder`].
+#[derive(Default)]
+pub(crate) struct ConsoleOptions {
+    /// Format replacing the built-in [`BaseFormat`] of the base console layer.
+    pub(crate) custom_format: Option<CustomFormat>,
+    /// Destination replacing stdout and stderr for every console layer.
+    pub(crate) writer: Option<SharedWriter>,
+    /// Destination replacing stdout only.
+    pub(crate) stdout_writer: Option<SharedWriter>,
+    /// Destination replacing stderr only.
+    pub(crate) stderr_writer: Option<SharedWriter>,
+    /// Destination of the WARN and ERROR stream, enabling it.
+    pub(crate) warn_writer: Option<SharedWriter>,
+    /// Whether the console output is colored, whatever the render mode and destination.
+    pub(crate) ansi: Option<bool>,
+    /// Format of the structured console layer, whatever the environment and the config.
+    pub(crate) structured_format: Option<StructuredFormat>,
+}
+
+/// Adapts a [`CustomFormat`] to the fmt layer, which requires a sized formatter.
+struct BoxedFormat(CustomFormat);
+
+impl FormatEvent<Registry, DefaultFields> for BoxedFormat {
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, Registry, DefaultFields>,
+        writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        self.0.format_event(ctx, writer, event)
+    }
+}
+
+/// Formats the events below a level without their source location.
+#[derive(Clone)]
+struct LeveledLocation<F> {
+    /// Format of the events at or above `level`.
+    located: F,
+    /// Format of the less severe events.
+    unlocated: F,
+    level: Level,
+}
+
+impl<F> LeveledLocation<F> {
+    fn map<G>(self, f: impl Fn(F) -> G) -> LeveledLocation<G> {
+        LeveledLocation {
+            located: f(self.located),
+            unlocated: f(self.unlocated),
+            level: self.level,
+        }
+    }
+}
+
+impl<F: FormatEvent<Registry, DefaultFields>> FormatEvent<Registry, DefaultFields>
+    for LeveledLocation<F>
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, Registry, DefaultFields>,
+        writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        if *event.metadata().level() <= self.level {
+            self.located.format_event(ctx, writer, event)
+        } else {
+            self.unlocated.format_event(ctx, writer, event)
+        }
+    }
+}
+
+/// Filters an export layer so that it never receives OpenTelemetry's own events.
+///
+/// The SDK and its transport log their failures through `tracing`, into the same
+/// subscriber as the export bridge. Exporting those events could fail in turn and
+/// amplify under failure, so they are kept on the console only.
+///
+/// The bridge follows `LoggingConfigs::export_level` when set, and the sampling decision
+/// of the current trace with `LoggingConfigs::follow_trace_sampling`.
+#[cfg(any(feature = "stdout", feature = "otlp"))]
+pub(crate) fn export_layer<L>(layer: L, log_cfgs: &LoggingConfigs) -> BoxedLayer
+where
+    L: Layer<Registry> + Send + Sync,
+{
+    use crate::exporters::{filters::is_otel_internal, sampling::TraceSamplingFilter};
+
+    let layer = layer.with_filter(filter_fn(|meta| !is_otel_internal(meta.target())));
+    let layer = if log_cfgs.follow_trace_sampling {
+        layer.with_filter(TraceSamplingFilter).boxed()
+    } else {
+        layer.boxed()
+    };
+    signal_layer(layer, log_cfgs.export_level, log_cfgs)
+}
+
+/// Restricts a layer of one output, the console or the export bridge, to its own level.
+///
+/// The layer gets the configured target filter built with `level` as default, so the
+/// per-target overrides and the allowlist still apply. The level only narrows the one
+/// of the subscriber: an event filtered out by `LOG_LEVEL` reaches no layer. Without a
+/// level, the layer is returned as is.
+fn signal_layer(layer: BoxedLayer, level: Option<Level>, log_cfgs: &LoggingConfigs) -> BoxedLayer {
+    match level {
+        Some(level) => layer
+            .with_filter(configured_filters(&level.to_string(), log_cfgs))
+            .boxed(),
+        None => layer,
+    }
+}
+
+/// Applies [`signal_layer`] with the console level to every console layer.
+fn console_level(layers: Vec<BoxedLayer>, log_cfgs: &LoggingConfigs) -> Vec<BoxedLayer> {
+    layers
+        .into_iter()
+        .map(|layer| signal_layer(layer, log_cfgs.console_level, log_cfgs))
+        .collect()
+}
+
+/// Builds the console layers shared by the exporters.
+///
+/// The console output consists of:
+/// - A base layer writing to stderr with detailed metadata, compact by default (see
+///   [`BaseFormat`])
+/// - A pretty, human-readable layer or a JSON layer in Bunyan format, as set by
+///   `LOG_STRUCTURED_FORMAT` or the [`RenderMode`], writing to stdout
+///
+/// Both destinations are guarded against broken pipes, so a closed stdout or stderr
+/// silently stops the console output instead of failing on every event. With
+/// `LOG_ERROR_BUFFER`, both go through an [`ErrorBuffer`] holding their lines until an
+/// error, and with `LOG_CONSOLE_SERIALIZED`, every console writer shares a
+/// [`WriteLock`].
+///
+/// The structured format can also be chosen per level with
+/// `LOG_STRUCTURED_LEVEL_FORMATS`, in which case one stdout layer is built per format in
+/// use, each receiving the events of the levels mapped to it.
+///
+/// A custom format supplied in `console` replaces the [`BaseFormat`] of the base layer,
+/// along with its metadata toggles, and a custom writer replaces both stdout and stderr,
+/// without colors. No layer is returned when console output is
+/// disabled with `LOG_CONSOLE_OUTPUT`.
+///
+/// # Errors
+///
+/// Returns `LoggingError::InvalidConfigError` if a per-level format or color entry is
+/// malformed,
+/// and `LoggingError::InvalidServiceNameError` if the service name is empty.
+pub(crate) fn console_layers(
+    app_cfgs: &AppConfigs,
+    log_cfgs: &LoggingConfigs,
+    console: ConsoleOptions,
+) -> Result<Vec<BoxedLayer>, LoggingError> {
+    if !log_cfgs.console_output {
+        return Ok(Vec::new());
+    }
+
+    // Colors are meant for terminals, not for custom destinations, unless forced
+    let render_mode = render_mode(app_cfgs, log_cfgs);
+    let ansi = console.ansi.unwrap_or_else(|| {
+        render_mode.ansi()
+            && console.writer.is_none()
+            && console.stdout_writer.is_none()
+            && console.stderr_writer.is_none()
+    });
+    let (stdout, stderr) = match console.writer {
+        Some(writer) => (writer.clone(), writer),
+        None => (
+            console
+                .stdout_writer
+                .unwrap_or_else(|| SharedWriter::new(BrokenPipeGuard::new(std::io::stdout))),
+            console
+                .stderr_writer
+                .unwrap_or_else(|| SharedWriter::new(BrokenPipeGuard::new(std::io::stderr))),
+        ),
+    };
+    // Buffered at the destination, so every line goes through the write buffer
+    let buffered = |writer: SharedWriter| match log_cfgs.console_buffer {
+        Some(capacity) => {
+            let buffer = WriteBuffer::new(writer, capacity)
+                .with_flush_interval(log_cfgs.console_flush_interval);
+            let mut buffers = CONSOLE_BUFFERS
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            buffers.retain(|buffer| buffer.upgrade().is_some());
+            buffers.push(buffer.downgrade());
+            SharedWriter::new(buffer)
+        }
+        None => writer,
+    };
+    let (stdout, stderr) = (buffered(stdout), buffered(stderr));
+    let warn_writer = console.warn_writer.map(buffered);
+
+    // Serialized at the destination, below the error buffer
+    let lock = log_cfgs.console_serialized.then(WriteLock::new);
+    let serialized = |writer: SharedWriter| match &lock {
+        Some(lock) => SharedWriter::new(lock.writer(writer)),
+        None => writer,
+    };
+    let (stdout, stderr) = (serialized(stdout), serialized(stderr));
+    let warn_writer = warn_writer.map(serialized);
+    let (stdout, stderr) = match log_cfgs.error_buffer {
+        Some(capacity) => {
+            let buffer = ErrorBuffer::new(capacity, log_cfgs.error_buffer_trigger);
+            (
+                SharedWriter::new(buffer.writer(stdout)),
+                SharedWriter::new(buffer.writer(stderr)),
+            )
+        }
+        None => (stdout, stderr),
+    };
+
+    let colors = LevelColors::from_entries(&log_cfgs.level_colors)?;
+    json::check_omitted_fields(&log_cfgs.json_omitted_fields)?;
+    let metadata = &log_cfgs.base_metadata;
+    let base_format = tracing_subscriber::fmt::format()
+        .with_thread_ids(metadata.thread_ids)
+        .with_thread_names(metadata.thread_names)
+        .with_ansi(ansi)
+        .with_level(true)
+        .with_target(true)
+        .with_file(metadata.source_location && metadata.file)
+        .with_line_number(metadata.source_location && metadata.line_number);
+    // Every level is located unless a least severe located level is configured
+    let base_format = LeveledLocation {
+        unlocated: match metadata.source_location_level {
+            Some(_) => base_format.clone().with_file(false).with_line_number(false),
+            None => base_format.clone(),
+        },
+        located: base_format,
+        level: metadata.source_location_level.unwrap_or(Level::TRACE),
+    };
+
+    // The problems only stream is a second, filtered base layer
+    let warn_layer = match warn_writer {
+        Some(writer) => Some((writer, console.ansi.unwrap_or(false))),
+        None => log_cfgs.warn_stream.then(|| (stderr.clone(), ansi)),
+    }
+    .map(|(writer, ansi)| {
+        tracing_subscriber::fmt::layer()
+            .with_ansi(ansi)
+            .with_writer(writer)
+            .event_format(Colored::new(
+                base_format
+                    .clone()
+                    .map(|format| format.with_ansi(ansi).compact()),
+                colors.clone(),
+            ))
+            .with_filter(LevelFilter::WARN)
+            .boxed()
+    });
+
+    let stream = |stream: ConsoleStream| match stream {
+        ConsoleStream::Stdout => stdout.clone(),
+        ConsoleStream::Stderr => stderr.clone(),
+    };
+    let base_fmt_layer = tracing_subscriber::fmt::layer()
+        .with_ansi(ansi)
+        .with_writer(stream(log_cfgs.base_fmt_stream));
+
+    let base_fmt_layer = match (console.custom_format, log_cfgs.base_format) {
+        (Some(custom_format), _) => base_fmt_layer
+            .event_format(BoxedFormat(custom_format))
+            .boxed(),
+        (None, BaseFormat::Compact) => base_fmt_layer
+            .event_format(Colored::new(
+                base_format.map(|format| format.compact()),
+                colors.clone(),
+            ))
+            .boxed(),
+        (None, BaseFormat::Full) => base_fmt_layer
+            .event_format(Colored::new(base_format, colors.clone()))
+            .boxed(),
+        (None, BaseFormat::Pretty) => base_fmt_layer
+            .event_format(Colored::new(
+                base_format.map(|format| format.pretty()),
+                colors.clone(),
+            ))
+            .boxed(),
+    };
+
+    let structured_format = structured_format(
+        console.structured_format,
+        log_cfgs,
+        render_mode.structured_format(),
+    );
+    let level_formats = level_formats(&log_cfgs.structured_level_formats)?;
+    let service_name = service::name(app_cfgs, log_cfgs)?;
+
+    let structured = stream(log_cfgs.structured_stream);
+
+    let mut layers = vec![base_fmt_layer];
+    layers.extend(warn_layer);
+    if level_formats.is_empty() {
+        layers.push(structured_layer(
+            &service_name,
+            log_cfgs,
+            structured_format,
+            structured,
+            ansi,
+            &colors,
+        ));
+        return Ok(console_level(layers, log_cfgs));
+    }
+
+    // One structured layer per format in use, each receiving the levels mapped to it
+    for format in [
+        StructuredFormat::Pretty,
+        StructuredFormat::Json,
+        StructuredFormat::Ndjson,
+        StructuredFormat::PrettyJson,
+        StructuredFormat::Dual,
+    ] {
+        let format_of = {
+            let level_formats = level_formats.clone();
+            move |level: &Level| {
+                level_formats
+                    .get(level)
+                    .copied()
+                    .unwrap_or(structured_format)
+            }
+        };
+        if !LEVELS.iter().any(|level| format_of(level) == format) {
+            continue;
+        }
+
+        layers.push(
+            structured_layer(
+                &service_name,
+                log_cfgs,
+                format,
+                structured.clone(),
+                ansi,
+                &colors,
+            )
+            .with_filter(filter_fn(move |meta| format_of(meta.level()) == format))
+            .boxed(),
+        );
+    }
+    Ok(console_level(layers, log_cfgs))
+}
+
+/// Builds the filter of the exporter layers, returning it with the default level.
+///
+/// The default level is the one of the configuration, `AppConfigs::log_level`, or the
+/// first level found in the environment by [`resolve_level`], or the level of the
+/// environment [`Profile`].
+///
+/// Events must pass the target filters, relaxed by scoped levels, the silenced targets,
+/// the field filters and the target sampling.
+fn main_filter(
+    app_cfgs: &AppConfigs,
+    log_cfgs: &LoggingConfigs,
+) -> Result<(BoxedFilter, LevelFilter), LoggingError> {
+    let var = |key| std::env::var(key).ok();
+    let level = resolve_level(
+        Some(&app_cfgs.log_level),
+        var(envs::APP_LOG_LEVEL_ENV_KEY).as_deref(),
+        var(envs::LOG_LEVEL_ENV_KEY).as_deref(),
+        var(envs::RUST_LOG_ENV_KEY).as_deref(),
+        Profile::for_env(&app_cfgs.env).level(),
+    );
+    Ok((level_filter(level, log_cfgs)?, level))
+}
+
+/// Records the settings the filter of the installed exporter layers was built from.
+fn remember_filter(level: LevelFilter, log_cfgs: &LoggingConfigs) {
+    *FILTER_SETTINGS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = Some((level, log_cfgs.clone()));
+}
+
+/// Builds the filter of the exporter layers for the given default level.
+///
+/// The target overrides set at runtime are merged over the configured target filters,
+/// but not over the silenced targets.
+fn level_filter(
+    level: LevelFilter,
+    log_cfgs: &LoggingConfigs,
+) -> Result<BoxedFilter, LoggingError> {
+    let filters = TARGET_OVERRIDES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .fold(
+            configured_filters(&level.to_string(), log_cfgs),
+            |filters, (target, (level, _))| filters.with_target(target.clone(), *level),
+        );
+    let field_filter = FieldFilter::new(&log_cfgs.field_include, &log_cfgs.field_exclude)?;
+    let message_filter = MessageFilter::new(&log_cfgs.message_denylist)?;
+    let sampling = TargetSamplingFilter::new(&log_cfgs.target_sample_rates)?;
+
+    let filters = CombinedFilter::new(filters)
+        .with_silenced(silenced_filter(log_cfgs))
+        .with_messages(message_filter)
+        .with_fields(field_filter)
+        .with_sampling(sampling);
+    Ok(Box::new(filters))
+}
+
+/// Replaces the filter of the installed exporter layers with the one of the given
+/// settings.
+///
+/// The level, target, silenced target, field filter and target sampling settings take
+/// effect for the next events.
+///
+/// # Errors
+///
+/// Returns `LoggingError::ReconfigureError` if logging isn't installed by this crate,
+/// and the errors of [`install_subscriber`] for invalid settings.
+#[cfg(feature = "sdk")]
+pub(crate) fn reload_filter(
+    app_cfgs: &AppConfigs,
+    log_cfgs: &LoggingConfigs,
+) -> Result<(), LoggingError> {
+    let handle = MAIN_FILTER
+        .get()
+        .ok_or_else(|| LoggingError::ReconfigureError("logging is not installed".to_string()))?;
+    let (filters, level) = main_filter(app_cfgs, log_cfgs)?;
+    handle
+        .reload(filters)
+        .map_err(|err| LoggingError::ReconfigureError(err.to_string()))?;
+    remember_filter(level, log_cfgs);
+    crate::level::set(level);
+    Ok(())
+}
+
+/// Sets the level of `target` and its submodules, or removes its override with `None`,
+/// and rebuilds the filter of the installed exporter layers.
+///
+/// Returns the generation of the new override, which [`revert_target`] checks so that a
+/// later override of the same target is kept.
+///
+/// # Errors
+///
+/// Returns `LoggingError::ReconfigureError` if logging isn't installed by this crate or
+/// if the target is empty.
+#[cfg(feature = "sdk")]
+pub(crate) fn override_target(
+    target: &str,
+    level: Option<LevelFilter>,
+) -> Result<u64, LoggingError> {
+    let target = target.trim();
+    if target.is_empty() {
+        return Err(LoggingError::ReconfigureError(
+            "cannot override the level of an empty target".to_string(),
+        ));
+    }
+
+    let generation = OVERRIDE_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    {
+        let mut overrides = TARGET_OVERRIDES
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match level {
+            Some(level) => overrides.insert(target.to_string(), (level, generation)),
+            None => overrides.remove(target),
+        };
+    }
+    rebuild_filter()?;
+    Ok(generation)
+}
+
+/// Removes the override of `target` if it's still the one of `generation`.
+///
+/// # Errors
+///
+/// Returns `LoggingError::ReconfigureError` if logging isn't installed by this crate.
+#[cfg(feature = "sdk")]
+pub(crate) fn revert_target(target: &str, generation: u64) -> Result<(), LoggingError> {
+    let removed = {
+        let mut overrides = TARGET_OVERRIDES
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let current = overrides
+            .get(target)
+            .is_some_and(|(_, id)| *id == generation);
+        current && overrides.remove(target).is_some()
+    };
+    if removed {
+        rebuild_filter()?;
+    }
+    Ok(())
+}
+
+/// Rebuilds the filter of the installed exporter layers from its last settings.
+#[cfg(feature = "sdk")]
+fn rebuild_filter() -> Result<(), LoggingError> {
+    let handle = MAIN_FILTER
+        .get()
+        .ok_or_else(|| LoggingError::ReconfigureError("logging is not installed".to_string()))?;
+    let settings = FILTER_SETTINGS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let Some((level, log_cfgs)) = settings.as_ref() else {
+        return Err(LoggingError::ReconfigureError(
+            "logging is not installed".to_string(),
+        ));
+    };
+    handle
+        .reload(level_filter(*level, log_cfgs)?)
+        .map_err(|err| LoggingError::ReconfigureError(err.to_string()))
+}
+
+/// Writes the lines held by the console write buffers.
+pub(crate) fn flush_console() {
+    for buffer in CONSOLE_BUFFERS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .filter_map(WeakWriteBuffer::upgrade)
+    {
+        let _ = buffer.flush();
+    }
+}
+
+/// Returns the configured render mode of the console, or the one of the environment
+/// [`Profile`].
+///
+/// With `LOG_TTY_DETECTION`, the mode of the environment follows whether the stream of
+/// the structured console layer is a terminal.
+pub(crate) fn render_mode(app_cfgs: &AppConfigs, log_cfgs: &LoggingConfigs) -> RenderMode {
+    log_cfgs.render_mode.unwrap_or_else(|| {
+        let mode = Profile::for_env(&app_cfgs.env).render_mode();
+        if !log_cfgs.tty_detection {
+            return mode;
+        }
+        mode.for_output(match log_cfgs.structured_stream {
+            ConsoleStream::Stdout => std::io::stdout().is_terminal(),
+            ConsoleStream::Stderr => std::io::stderr().is_terminal(),
+        })
+    })
+}
+
+/// Returns the format of the structured console layer, resolved by
+/// [`crate::config::resolve_format`].
+pub(crate) fn structured_format(
+    explicit: Option<StructuredFormat>,
+    log_cfgs: &LoggingConfigs,
+    default: StructuredFormat,
+) -> StructuredFormat {
+    resolve_format(
+        explicit,
+        envs::parse(envs::LOG_STRUCTURED_FORMAT_ENV_KEY),
+        log_cfgs.structured_format,
+        default,
+    )
+}
+
+/// Every tracing level, from the most to the least verbose.
+const LEVELS: [Level; 5] = [
+    Level::TRACE,
+    Level::DEBUG,
+    Level::INFO,
+    Level::WARN,
+    Level::ERROR,
+];
+
+/// Builds the structured console layer writing to `stdout` in the given format, with
+/// colors if `ansi` and the format is pretty, its level token painted with `colors`.
+fn structured_layer(
+    service_name: &str,
+    log_cfgs: &LoggingConfigs,
+    format: StructuredFormat,
+    stdout: SharedWriter,
+    ansi: bool,
+    colors: &LevelColors,
+) -> BoxedLayer {
+    match format {
+        StructuredFormat::Pretty => FmtLayer::new()
+            .pretty()
+            .with_ansi(ansi)
+            .with_writer(stdout)
+            .map_event_format(|format| Colored::new(format.with_ansi(ansi), colors.clone()))
+            .boxed(),
+        StructuredFormat::Json => BunyanFormattingLayer::new(
+            service_name.to_string(),
+            JsonWriter::new(stdout)
+                .with_nested_fields(log_cfgs.json_nested_fields)
+                .with_field_names(json_field_names(log_cfgs))
+                .with_hostname(service::configured_hostname(log_cfgs))
+                .with_omitted_fields(log_cfgs.json_omitted_fields.clone())
+                .with_timestamp_field(Some(log_cfgs.timestamp_field.clone())),
+        )
+        .boxed(),
+        StructuredFormat::Ndjson => ndjson_layer(log_cfgs, stdout, false, None),
+        StructuredFormat::PrettyJson => ndjson_layer(log_cfgs, stdout, true, None),
+        StructuredFormat::Dual => {
+            let marker = Some(log_cfgs.dual_marker.clone());
+            vec![
+                structured_layer(
+                    service_name,
+                    log_cfgs,
+                    StructuredFormat::Pretty,
+                    stdout.clone(),
+                    ansi,
+                    colors,
+                ),
+                ndjson_layer(log_cfgs, stdout, false, marker),
+            ]
+            .boxed()
+        }
+    }
+}
+
+/// Builds a layer writing one plain JSON object per line, without the Bunyan schema.
+///
+/// Records carry the `timestamp`, the `level` name, the `target`, the `message` and the
+/// event fields at the top level, and go through the same [`JsonWriter`] shaping as the
+/// Bunyan records. They are spread over several indented lines if `indented`, and
+/// prefixed with `marker` when given.
+fn ndjson_layer<M>(
+    log_cfgs: &LoggingConfigs,
+    make_writer: M,
+    indented: bool,
+    marker: Option<String>,
+) -> BoxedLayer
+where
+    M: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    FmtLayer::new()
+        .json()
+        .flatten_event(true)
+        .with_current_span(false)
+        .with_span_list(false)
+        .with_ansi(false)
+        .with_writer(
+            JsonWriter::new(make_writer)
+                .with_nested_fields(log_cfgs.json_nested_fields)
+                .with_field_names(json_field_names(log_cfgs))
+                .with_indentation(indented)
+                .with_marker(marker)
+                .with_timestamp_field(Some(log_cfgs.timestamp_field.clone())),
+        )
+        .boxed()
+}
+
+/// Returns the configured names of the JSON output fields.
+fn json_field_names(log_cfgs: &LoggingConfigs) -> HashMap<String, String> {
+    log_cfgs
+        .json_field_names
+        .iter()
+        .map(|(field, name)| (field.clone(), name.clone()))
+        .collect()
+}
+
+/// Builds the layer appending records to a file, created if missing.
+///
+/// The file receives the records at or above `level`, when given, in the given format,
+/// JSON by default.
+///
+/// # Errors
+///
+/// Returns `LoggingError::InvalidConfigError` if the file can't be opened, and
+/// `LoggingError::InvalidServiceNameError` if the service name is empty.
+pub(crate) fn file_layer(
+    app_cfgs: &AppConfigs,
+    log_cfgs: &LoggingConfigs,
+    path: &Path,
+    level: Option<&str>,
+    below: Option<&str>,
+    format: Option<StructuredFormat>,
+) -> Result<BoxedLayer, LoggingError> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map(Arc::new)
+        .map_err(|err| {
+            LoggingError::InvalidConfigError(format!(
+                "cannot open log file {}: {err}",
+                path.display()
+            ))
+        })?;
+
+    let layer = match format.unwrap_or(StructuredFormat::Json) {
+        StructuredFormat::Pretty => FmtLayer::new()
+            .pretty()
+            .with_ansi(false)
+            .with_writer(file)
+            .boxed(),
+        StructuredFormat::Json => BunyanFormattingLayer::new(
+            service::name(app_cfgs, log_cfgs)?,
+            JsonWriter::new(file)
+                .with_nested_fields(log_cfgs.json_nested_fields)
+                .with_field_names(json_field_names(log_cfgs))
+                .with_hostname(service::configured_hostname(log_cfgs))
+                .with_omitted_fields(log_cfgs.json_omitted_fields.clone())
+                .with_timestamp_field(Some(log_cfgs.timestamp_field.clone())),
+        )
+        .boxed(),
+        StructuredFormat::Ndjson => ndjson_layer(log_cfgs, file, false, None),
+        StructuredFormat::PrettyJson => ndjson_layer(log_cfgs, file, true, None),
+        StructuredFormat::Dual => {
+            let marker = Some(log_cfgs.dual_marker.clone());
+            vec![
+                FmtLayer::new()
+                    .pretty()
+                    .with_ansi(false)
+                    .with_writer(file.clone())
+                    .boxed(),
+                ndjson_layer(log_cfgs, file, false, marker),
+            ]
+            .boxed()
+        }
+    };
+
+    let layer = match level {
+        Some(level) => layer.with_filter(log_level(level)).boxed(),
+        None => layer,
+    };
+    Ok(match below.map(log_level) {
+        Some(below) => layer
+            .with_filter(filter_fn(move |meta| below < *meta.level()))
+            .boxed(),
+        None => layer,
+    })
+}
+
+/// Parses `level=format` entries into per-level structured formats.
+pub(crate) fn level_formats(
+    entries: &[String],
+) -> Result<BTreeMap<Level, StructuredFormat>, LoggingError> {
+    entries
+        .iter()
+        .map(|entry| {
+            let invalid = || {
+                LoggingError::InvalidConfigError(format!(
+                    "invalid structured level format `{entry}`, expected level=format"
+                ))
+            };
+
+            let (level, format) = entry.split_once('=').ok_or_else(invalid)?;
+            let level: Level = level.trim().parse().map_err(|_| invalid())?;
+            let format: StructuredFormat = format.trim().parse()?;
+            Ok((level, format))
+        })
+        .collect()
+}
+
+/// The subscriber assembled by [`build_subscriber`].
+pub(crate) type AssembledSubscriber = Layered<Vec<BoxedLayer>, Registry>;
+
+/// The handle replacing the filter of the exporter layers of an [`AssembledSubscriber`].
+pub(crate) type FilterHandle = reload::Handle<BoxedFilter, Registry>;
+
+/// Installs the global subscriber made of the given exporter layers.
+///
+/// The subscriber is assembled by [`build_subscriber`], then set as the global default,
+/// its filter made reloadable and its level published, the `log` crate bridged to it
+/// and the heartbeat started.
+///
+/// # Errors
+///
+/// Returns the errors of [`build_subscriber`], and
+/// `LoggingError::SubscriberInstallError` if the global subscriber can't be set.
+pub(crate) fn install_subscriber(
+    app_cfgs: &AppConfigs,
+    log_cfgs: &LoggingConfigs,
+    layers: Vec<BoxedLayer>,
+) -> Result<(), LoggingError> {
+    let (subscriber, handle, level) = build_subscriber(app_cfgs, log_cfgs, layers)?;
+
+    // Nothing can log the failure yet, so it's only returned
+    tracing::subscriber::set_global_default(subscriber)
+        .map_err(|err| LoggingError::SubscriberInstallError(err.to_string()))?;
+    let _ = MAIN_FILTER.set(handle);
+    remember_filter(level, log_cfgs);
+    crate::level::set(level);
+    log_bridge::init(log_cfgs);
+    if let Some(interval) = log_cfgs.heartbeat_interval {
+        heartbeat::start(interval, log_cfgs.heartbeat_level);
+    }
+    Ok(())
+}
+
+/// Assembles the subscriber made of the given exporter layers, without installing it.
+///
+/// The exporter layers are filtered with the configured target and field filters, and
+/// complemented with the JSON file layer and the span timing layer when enabled. When a
+/// maximum message or field length is configured, they receive events with their
+/// over-length values clipped. The audit stream, when configured, is added alongside
+/// them with its own filter and unclipped values.
+///
+/// Returns the subscriber with the handle of its filter and its default level.
+///
+/// # Errors
+///
+/// Returns `LoggingError::InvalidFieldFilterError`,
+/// `LoggingError::InvalidAuditOutputError` or `LoggingError::InvalidConfigError` if the
+/// configuration is invalid or the JSON file can't be opened.
+pub(crate) fn build_subscriber(
+    app_cfgs: &AppConfigs,
+    log_cfgs: &LoggingConfigs,
+    mut layers: Vec<BoxedLayer>,
+) -> Result<(AssembledSubscriber, FilterHandle, LevelFilter), LoggingError> {
+    if let Some(path) = &log_cfgs.json_file {
+        layers.push(file_layer(
+            app_cfgs,
+            log_cfgs,
+            path,
+            None,
+            None,
+            Some(StructuredFormat::Json),
+        )?);
+    }
+    if let Some(level) = log_cfgs.span_timing {
+        layers.push(SpanTimingLayer::new(level).boxed());
+    }
+    if let Some(layer) = log_cfgs.recent_logs.and_then(recent::install) {
+        layers.push(layer.boxed());
+    }
+    if let Some(layer) = log_cfgs.volume_counters.then(volume::install).flatten() {
+        layers.push(layer.boxed());
+    }
+
+    let (filters, level) = main_filter(app_cfgs, log_cfgs)?;
+    let (filters, handle) = reload::Layer::new(filters);
+    let mut process_fields = process_fields::fields(&log_cfgs.process_fields)?;
+    #[cfg(feature = "sdk")]
+    if log_cfgs.traceparent {
+        process_fields.push(process_fields::ProcessField::TraceParent);
+    }
+    if log_cfgs.target_fields {
+        process_fields.extend([
+            process_fields::ProcessField::Crate,
+            process_fields::ProcessField::Module,
+        ]);
+    }
+    let layers = if process_fields.is_empty() {
+        layers.boxed()
+    } else {
+        ProcessFieldsLayer::new(layers, process_fields).boxed()
+    };
+    let layers = match (log_cfgs.max_message_length, log_cfgs.max_field_length) {
+        (None, None) => layers.boxed(),
+        (max_message, max_field) => TruncateLayer::new(layers, max_message, max_field).boxed(),
+    };
+    // Measured in front of the truncation, so events keep the size they were logged with
+    let layers = match log_cfgs
+        .event_size_histogram
+        .then(|| size::install(&log_cfgs.event_size_buckets))
+        .flatten()
+    {
+        Some(size_layer) => layers.and_then(size_layer).boxed(),
+        None => layers,
+    };
+    let mut all_layers = vec![layers.with_filter(filters).boxed()];
+    if let Some(audit_layer) = audit::layer(app_cfgs, &log_cfgs.audit)? {
+        all_layers.push(audit_layer);
+    }
+    Ok((
+        tracing_subscriber::registry().with(all_layers),
+        handle,
+        level,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "sdk")]
+    use crate::{
+        builder::LoggingBuilder,
+        test::{Capture, init_from},
+    };
+    use crate::{
+        config::{BaseMetadataConfigs, LoggingConfig},
+        test::init_with,
+    };
+
+    /// Returns the config logging at `level` with the given settings.
+    fn config(level: &str, logging: LoggingConfigs) -> LoggingConfig {
+        LoggingConfig {
+            level: Some(level.to_string()),
+            logging,
+            ..LoggingConfig::default()
+        }
+    }
+
+    #[test]
+    fn console_level_narrows_the_console_only() {
+        let logs = init_with(config(
+            "debug",
+            LoggingConfigs {
+                console_level: Some(Level::INFO),
+                ..LoggingConfigs::default()
+            },
+        ));
+
+        tracing::debug!("cache miss");
+        tracing::info!("request served");
+
+        assert!(logs.contains("request served"));
+        assert!(!logs.contains("cache miss"));
+        #[cfg(any(feature = "stdout", feature = "otlp"))]
+        assert_eq!(logs.exported_bodies(), ["cache miss", "request served"]);
+    }
+
+    #[test]
+    fn json_records_carry_the_configured_hostname() {
+        let logs = init_with(config(
+            "info",
+            LoggingConfigs {
+                structured_format: Some(StructuredFormat::Json),
+                hostname: Some("node-7".to_string()),
+                ..LoggingConfigs::default()
+            },
+        ));
+
+        tracing::info!("request served");
+
+        let output = logs.output();
+        let record = output.lines().find(|line| line.starts_with('{')).unwrap();
+        assert!(record.contains(r#""hostname":"node-7""#));
+    }
+
+    /// Writes byte by byte, so that unserialized lines would interleave.
+    #[cfg(feature = "sdk")]
+    #[derive(Clone)]
+    struct ByteByByte(Capture);
+
+    #[cfg(feature = "sdk")]
+    impl std::io::Write for ByteByByte {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let Some(byte) = buf.first() else {
+                return Ok(0);
+            };
+            self.0.write_all(&[*byte])?;
+            std::thread::yield_now();
+            Ok(1)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "sdk")]
+    #[test]
+    fn serialized_console_lines_never_interleave() {
+        let capture = Capture::default();
+        let writer = ByteByByte(capture.clone());
+        let cfg = config(
+            "info",
+            LoggingConfigs {
+                console_serialized: true,
+                ..Default::default()
+            },
+        );
+        let builder = LoggingBuilder::from_config(cfg).with_console_writer(move || writer.clone());
+        let logs = init_from(builder);
+
+        let threads: Vec<_> = (0..4)
+            .map(|thread| {
+                let dispatch = logs.dispatch().clone();
+                std::thread::spawn(move || {
+                    tracing::dispatcher::with_default(&dispatch, || {
+                        for seq in 0..25 {
+                            tracing::info!("event thread={thread} seq={seq} end");
+                        }
+                    })
+                })
+            })
+            .collect();
+        threads
+            .into_iter()
+            .for_each(|thread| thread.join().unwrap());
+
+        // Every event is printed by the base and the structured layers, each line intact
+        let output = capture.output();
+        let events: Vec<&str> = output
+            .lines()
+            .filter(|line| line.contains("event"))
+            .collect();
+        assert_eq!(events.len(), 200);
+        for line in events {
+            assert_eq!(line.matches("event thread=").count(), 1, "{line}");
+            assert_eq!(line.matches(" end").count(), 1, "{line}");
+        }
+    }
+
+    #[test]
+    fn only_the_severe_events_are_located() {
+        let logs = init_with(config(
+            "info",
+            LoggingConfigs {
+                render_mode: Some(RenderMode::Machine),
+                base_metadata: BaseMetadataConfigs {
+                    source_location_level: Some(Level::WARN),
+                    ..BaseMetadataConfigs::default()
+                },
+                ..LoggingConfigs::default()
+            },
+        ));
+
+        tracing::info!("request served");
+        tracing::error!("request failed");
+
+        // The base layer lines, next to the JSON records of the structured layer
+        let output = logs.output();
+        let line = |message| {
+            output
+                .lines()
+                .find(|line| !line.starts_with('{') && line.contains(message))
+                .unwrap()
+                .to_string()
+        };
+        assert!(!line("request served").contains(".rs:"));
+        assert!(line("request failed").contains(".rs:"));
+    }
+
+    #[test]
+    fn buffered_console_lines_wait_for_a_flush() {
+        let logs = init_with(config(
+            "info",
+            LoggingConfigs {
+                console_buffer: Some(64 * 1024),
+                console_flush_interval: std::time::Duration::from_secs(60),
+                ..LoggingConfigs::default()
+            },
+        ));
+
+        tracing::info!("request served");
+        assert!(logs.output().is_empty());
+
+        flush_console();
+        assert!(logs.contains("request served"));
+    }
+
+    #[test]
+    fn dropped_console_buffers_are_released() {
+        // The destination is alive as long as a buffer writing to it
+        let destination = Arc::new(());
+        let held = destination.clone();
+        let console = ConsoleOptions {
+            writer: Some(SharedWriter::new(move || {
+                let _ = &held;
+                std::io::sink()
+            })),
+            ..ConsoleOptions::default()
+        };
+        let log_cfgs = LoggingConfigs {
+            console_buffer: Some(64 * 1024),
+            console_flush_interval: std::time::Duration::from_secs(60),
+            ..LoggingConfigs::default()
+        };
+
+        let layers = console_layers(&AppConfigs::new(), &log_cfgs, console).unwrap();
+        assert!(Arc::strong_count(&destination) > 1);
+        drop(layers);
+
+        assert_eq!(Arc::strong_count(&destination), 1);
+        flush_console();
+    }
+
+    #[test]
+    fn silenced_targets_never_log() {
+        let logs = init_with(config(
+            "trace",
+            LoggingConfigs {
+                silenced_targets: vec!["leaky_dep".to_string(), "hyper".to_string()],
+                ..LoggingConfigs::default()
+            },
+        ));
+
+        tracing::error!(target: "leaky_dep::auth", "token=secret");
+        tracing::warn!(target: "hyper", "connection reset");
+        tracing::trace!(target: "my_app", "polling");
+
+        assert!(!logs.contains("token=secret"));
+        assert!(!logs.contains("connection reset"));
+        assert!(logs.contains("polling"));
+    }
+
+    #[test]
+    fn disabled_console_leaves_every_console_layer_out() {
+        let logs = init_with(config(
+            "info",
+            LoggingConfigs {
+                console_output: false,
+                warn_stream: true,
+                ..LoggingConfigs::default()
+            },
+        ));
+
+        tracing::info!("request served");
+        tracing::warn!("slow request");
+
+        assert!(logs.output().is_empty());
+    }
+
+    /// Returns the base layer output of an event in a span, in the given format.
+    fn base_output(base_format: BaseFormat) -> String {
+        let logs = init_with(config(
+            "info",
+            LoggingConfigs {
+                base_format,
+                render_mode: Some(RenderMode::Machine),
+                ..LoggingConfigs::default()
+            },
+        ));
+
+        tracing::info_span!("request", id = 7).in_scope(|| tracing::info!(user = "ann", "served"));
+
+        // Leaves the JSON records of the structured layer out
+        logs.output()
+            .lines()
+            .filter(|line| !line.starts_with('{') && !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn base_format_selects_the_layout_of_the_base_layer() {
+        let compact = base_output(BaseFormat::Compact);
+        assert_eq!(compact.lines().count(), 1);
+        assert!(compact.contains(r#"served user="ann" id=7"#));
+
+        let full = base_output(BaseFormat::Full);
+        assert_eq!(full.lines().count(), 1);
+        assert!(full.contains("request{id=7}:"));
+        assert!(full.contains(r#"served user="ann""#));
+
+        let pretty = base_output(BaseFormat::Pretty);
+        assert!(pretty.lines().count() > 1);
+        assert!(pretty.contains(r#"served, user: "ann""#));
+        assert!(pretty.contains("with id=7"));
+    }
+
+    #[test]
+    fn base_metadata_can_be_turned_off() {
+        let base_line = |base_metadata| {
+            let logs = init_with(config(
+                "info",
+                LoggingConfigs {
+                    render_mode: Some(RenderMode::Machine),
+                    base_metadata,
+                    ..LoggingConfigs::default()
+                },
+            ));
+            tracing::info!("request served");
+            logs.output()
+                .lines()
+                .find(|line| !line.starts_with('{') && line.contains("request served"))
+                .unwrap()
+                .to_string()
+        };
+
+        // Every toggle is on by default
+        let line = base_line(BaseMetadataConfigs::default());
+        assert!(line.contains("src/exporters/layers.rs:"));
+        assert!(line.contains("ThreadId("));
+
+        let line = base_line(BaseMetadataConfigs {
+            thread_ids: false,
+            thread_names: false,
+            file: false,
+            line_number: false,
+            ..BaseMetadataConfigs::default()
+        });
+        assert!(!line.contains("layers.rs"));
+        assert!(!line.contains("ThreadId("));
+
+        // Turning the source location off wins over the file and line toggles
+        let line = base_line(BaseMetadataConfigs {
+            source_location: false,
+            ..BaseMetadataConfigs::default()
+        });
+        assert!(!line.contains(".rs:"));
+    }
+
+    #[cfg(any(feature = "stdout", feature = "otlp"))]
+    #[test]
+    fn export_is_independent_of_the_console_output() {
+        for console_output in [true, false] {
+            let logs = init_with(config(
+                "info",
+                LoggingConfigs {
+                    console_output,
+                    ..LoggingConfigs::default()
+                },
+            ));
+
+            tracing::info!("request served");
+
+            assert_eq!(logs.contains("request served"), console_output);
+            assert_eq!(logs.exported_bodies(), ["request served"]);
+        }
+    }
+
+    /// Returns the JSON records of the output with the given message.
+    fn json_records(output: &str, message: &str) -> Vec<serde_json::Value> {
+        output
+            .lines()
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .filter(|record| record["message"] == message)
+            .collect()
+    }
+
+    #[test]
+    fn errors_can_be_formatted_apart_from_the_other_levels() {
+        let logs = init_with(config(
+            "info",
+            LoggingConfigs {
+                render_mode: Some(RenderMode::Machine),
+                structured_format: Some(StructuredFormat::Ndjson),
+                structured_level_formats: vec!["error=pretty".to_string()],
+                ..LoggingConfigs::default()
+            },
+        ));
+
+        tracing::info!(order_id = 42, "order charged");
+        tracing::error!(order_id = 43, "charge failed");
+
+        let output = logs.output();
+        assert_eq!(json_records(&output, "order charged").len(), 1);
+        assert!(json_records(&output, "charge failed").is_empty());
+        assert!(output.contains("order_id: 43"));
+        assert!(!output.contains("order_id: 42"));
+    }
+
+    #[test]
+    fn every_level_shares_the_format_by_default() {
+        let logs = init_with(config(
+            "info",
+            LoggingConfigs {
+                render_mode: Some(RenderMode::Machine),
+                structured_format: Some(StructuredFormat::Ndjson),
+                ..LoggingConfigs::default()
+            },
+        ));
+
+        tracing::info!("order charged");
+        tracing::error!("charge failed");
+
+        let output = logs.output();
+        assert_eq!(json_records(&output, "order charged").len(), 1);
+        assert_eq!(json_records(&output, "charge failed").len(), 1);
+    }
+
+    #[test]
+    fn malformed_level_formats_are_rejected() {
+        assert!(level_formats(&["error=pretty".to_string()]).is_ok());
+        assert!(level_formats(&["error".to_string()]).is_err());
+        assert!(level_formats(&["loud=pretty".to_string()]).is_err());
+        assert!(level_formats(&["error=fancy".to_string()]).is_err());
+    }
+
+    #[test]
+    fn events_reach_both_the_console_and_the_json_file() {
+        let path = std::env::temp_dir().join(format!(
+            "logging-json-file-{}-{:?}.log",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let logs = init_with(config(
+            "info",
+            LoggingConfigs {
+                render_mode: Some(RenderMode::Interactive),
+                json_file: Some(path.clone()),
+                ..LoggingConfigs::default()
+            },
+        ));
+
+        tracing::info!(order_id = 42, "order charged");
+
+        let file = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(logs.contains("order charged"));
+        assert!(logs.contains("order_id: 42"));
+        let record: serde_json::Value = serde_json::from_str(file.trim()).unwrap();
+        assert_eq!(record["msg"], "order charged");
+        assert_eq!(record["order_id"], 42);
+    }
+}
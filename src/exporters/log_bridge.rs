@@ -0,0 +1,38 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # `log` Crate Bridge
+//!
+//! This module redirects records emitted through the `log` crate facade into the
+//! tracing pipeline, so that dependencies still using `log::info!` and friends are
+//! captured with their original target and level by every exporter.
+//!
+//! Applications managing the `log` facade themselves, e.g. with `env_logger`, turn the
+//! bridge off with `LOG_CAPTURE_LOG_CRATE=false`.
+
+use crate::config::LoggingConfigs;
+use tracing::error;
+use tracing_log::LogTracer;
+
+/// Initializes the `log` to `tracing` bridge.
+///
+/// Installing the subscriber of any exporter calls this once the subscriber is set, so
+/// that `log` records are handled uniformly. `LogTracer` preserves the record's target
+/// and maps the `log` level onto the equivalent tracing level.
+///
+/// A global `log` logger can only be set once per process. If one was already set
+/// (by the application itself), the error is logged through the new subscriber and
+/// otherwise ignored, since the existing logger keeps working. Nothing is set when
+/// `LoggingConfigs::capture_log_crate` is off.
+pub(crate) fn init(log_cfgs: &LoggingConfigs) {
+    if !log_cfgs.capture_log_crate {
+        return;
+    }
+    if let Err(err) = LogTracer::init() {
+        error!(
+            error = ?err,
+            "failure to initialize log bridge, probably the log was already initialized"
+        );
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Message Filtering
+//!
+//! This module drops events by the content of their message, complementing the target
+//! and level filtering in [`super::filters`] and the field filtering in
+//! [`super::field_filter`]. It's meant for benign messages of a dependency, such as a
+//! recurring "connection reset" WARN, that can't be turned off by level without losing
+//! the other events of the same target.
+//!
+//! The denylist is a list of regular expressions, matched against the rendered message
+//! of every event whatever its level and target. Events without a message are kept.
+
+use crate::errors::LoggingError;
+use regex::Regex;
+use std::fmt;
+use tracing::{
+    Event, Metadata, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::layer::{Context, Filter, Layer};
+
+/// Drops the events whose message matches one of the denylisted patterns.
+///
+/// Like [`super::field_filter::FieldFilter`], `MessageFilter` can be used both as a
+/// [`Layer`], disabling matching events for the whole subscriber, and as a per-layer
+/// [`Filter`].
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::message_filter::MessageFilter;
+/// use tracing_subscriber::{Layer, layer::SubscriberExt};
+///
+/// let filter = MessageFilter::new(&["^connection reset".to_string(), r"retry \d+/3".to_string()])
+///     .expect("valid patterns");
+/// let subscriber = tracing_subscriber::registry()
+///     .with(tracing_subscriber::fmt::layer().with_filter(filter));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MessageFilter {
+    denylist: Vec<Regex>,
+}
+
+impl MessageFilter {
+    /// Creates a message filter from the patterns of the denylist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LoggingError::InvalidMessageFilterError` if a pattern isn't a valid
+    /// regular expression.
+    pub fn new(denylist: &[String]) -> Result<Self, LoggingError> {
+        let denylist = denylist
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|err| {
+                    LoggingError::InvalidMessageFilterError(format!("`{pattern}`: {err}"))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(MessageFilter { denylist })
+    }
+
+    /// Returns `true` when the denylist is empty, meaning every event is kept.
+    pub fn is_empty(&self) -> bool {
+        self.denylist.is_empty()
+    }
+
+    /// Decides whether the given event should be kept.
+    pub fn allows(&self, event: &Event<'_>) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+
+        let mut visitor = MessageVisitor {
+            filter: self,
+            denied: false,
+        };
+        event.record(&mut visitor);
+        !visitor.denied
+    }
+}
+
+struct MessageVisitor<'a> {
+    filter: &'a MessageFilter,
+    denied: bool,
+}
+
+impl MessageVisitor<'_> {
+    fn check(&mut self, message: &str) {
+        self.denied = self
+            .filter
+            .denylist
+            .iter()
+            .any(|pattern| pattern.is_match(message));
+    }
+}
+
+impl Visit for MessageVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.check(value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.check(&format!("{value:?}"));
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for MessageFilter {
+    fn event_enabled(&self, event: &Event<'_>, _ctx: Context<'_, S>) -> bool {
+        self.allows(event)
+    }
+}
+
+impl<S: Subscriber> Filter<S> for MessageFilter {
+    fn enabled(&self, _meta: &Metadata<'_>, _cx: &Context<'_, S>) -> bool {
+        true
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, _cx: &Context<'_, S>) -> bool {
+        self.allows(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::Capture;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn drops_the_messages_matching_the_denylist() {
+        let filter =
+            MessageFilter::new(&["^connection reset".to_string(), r"retry \d+/3".to_string()])
+                .unwrap();
+        let console = Capture::default();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(console.clone())
+                .with_filter(filter),
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(target: "hyper", "connection reset by peer");
+            tracing::warn!(target: "hyper", "retry {}/3", 2);
+            tracing::warn!(target: "hyper", "connection pool exhausted");
+            tracing::error!(target: "my_app", "charge failed: connection reset");
+        });
+
+        assert!(!console.contains("connection reset by peer"));
+        assert!(!console.contains("retry 2/3"));
+        assert!(console.contains("connection pool exhausted"));
+        // Anchored, so the pattern doesn't match in the middle of a message
+        assert!(console.contains("charge failed: connection reset"));
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        assert!(MessageFilter::new(&["(unclosed".to_string()]).is_err());
+    }
+}
@@ -13,17 +13,109 @@
 //!
 //! - **stdout**: Exports logs to the standard output
 //! - **otlp**: Exports logs to an OpenTelemetry collector using gRPC
+//! - **wasm**: Exports logs to the browser console on `wasm32` targets
+//! - **bare**: Console-only output without any OpenTelemetry dependency
+//! - **journald**: Writes logs to the systemd journal on Linux
+//! - **gelf**: Sends logs to Graylog as GELF messages over UDP or TCP
 //!
 //! This module also contains utilities for logging configuration, such as
 //! environment variable handling and target filtering.
 
-mod envs;
+#[cfg(any(feature = "sdk", feature = "bare"))]
+mod audit;
+#[cfg(feature = "sdk")]
+pub mod backpressure;
+#[cfg(feature = "sdk")]
+pub mod baggage;
+#[cfg(all(feature = "cloud-detect", not(target_arch = "wasm32")))]
+mod cloud;
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub mod colors;
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub mod combined_filter;
+pub(crate) mod envs;
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub mod error_buffer;
+#[cfg(feature = "sdk")]
+pub mod error_flush;
+#[cfg(feature = "sdk")]
+pub mod export_timing;
+#[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+mod failover;
+#[cfg(any(feature = "stdout", feature = "otlp"))]
+pub(crate) mod fanout;
+pub mod field_filter;
 pub mod filters;
+#[cfg(feature = "stdout")]
+mod flush;
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub(crate) mod heartbeat;
+pub mod json;
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub(crate) mod layers;
+#[cfg(any(feature = "sdk", feature = "bare"))]
+mod log_bridge;
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub mod message_filter;
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub(crate) mod process_fields;
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub mod recent;
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub mod size;
+pub mod timing;
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub mod truncate;
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub mod volume;
+pub mod writer;
 
-#[cfg(feature = "otlp")]
+#[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
 pub mod otlp_grpc;
 
+#[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+mod otlp_client;
+
+#[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+pub mod timestamp;
+
+#[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+pub mod event_name;
+
+#[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+pub mod body;
+
 #[cfg(feature = "stdout")]
 pub mod stdout;
 
+#[cfg(feature = "sdk")]
 pub mod noop;
+
+#[cfg(any(feature = "stdout", feature = "otlp"))]
+pub(crate) mod resource;
+
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub(crate) mod service;
+
+#[cfg(feature = "sdk")]
+pub mod redact;
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub mod sampling;
+
+#[cfg(feature = "sdk")]
+pub mod severity;
+
+#[cfg(feature = "sdk")]
+pub mod stats;
+
+#[cfg(feature = "bare")]
+pub mod bare;
+
+#[cfg(feature = "wasm")]
+pub mod wasm_console;
+
+#[cfg(all(feature = "journald", target_os = "linux"))]
+pub mod journald;
+
+#[cfg(all(feature = "gelf", not(target_arch = "wasm32")))]
+pub mod gelf;
@@ -13,18 +13,13 @@
 //! based on the environment (pretty for local development, JSON/Bunyan for production),
 //! but it doesn't set up any OpenTelemetry export bridges.
 
-use crate::{errors::LoggingError, exporters::filters::target_filters};
+use crate::{
+    config::LoggingConfigs,
+    errors::LoggingError,
+    exporters::layers::{ConsoleOptions, console_layers, install_subscriber},
+};
 use configs::app::AppConfigs;
 use opentelemetry_sdk::logs::{LoggerProviderBuilder, SdkLoggerProvider};
-use tracing::error;
-use tracing_bunyan_formatter::BunyanFormattingLayer;
-use tracing_subscriber::{
-    fmt::{
-        Layer,
-        format::{Format, Pretty},
-    },
-    layer::SubscriberExt,
-};
 
 /// Installs and configures the no-operation (noop) log exporter.
 ///
@@ -41,13 +36,17 @@ use tracing_subscriber::{
 /// # Returns
 ///
 /// * `Result<SdkLoggerProvider, LoggingError>` - On success, returns a default
-///   OpenTelemetry logger provider with no exporters configured. On failure,
-///   returns a `LoggingError`.
+///   OpenTelemetry logger provider with no exporters configured, or a clone of the one
+///   installed by a previous call. On failure, returns a `LoggingError`.
 ///
 /// # Errors
 ///
-/// Returns `LoggingError::InternalError` if there's a problem setting up the
-/// tracing subscriber.
+/// Returns `LoggingError::SubscriberInstallError` if the tracing subscriber can't be
+/// set, e.g. because another global subscriber is already set.
+///
+/// Returns `LoggingError::AlreadyInitializedError` if logging is already installed, by
+/// [`crate::provider::install`] or the exporter of another module. Calling this
+/// function again hands back the provider it installed.
 ///
 /// # Examples
 ///
@@ -60,48 +59,50 @@ use tracing_subscriber::{
 ///     tracing::info!("Application started");
 /// }
 /// ```
+///
+/// Logging is installed once, whichever entry point comes first. Calling the same
+/// entry point again, e.g. from several tests, hands back the installed provider:
+///
+/// ```
+/// use logging::{errors::LoggingError, exporters::noop, provider};
+/// use opentelemetry::logs::{LogRecord, Logger, LoggerProvider};
+///
+/// noop::install().expect("Failed to set up logging");
+/// assert!(provider::is_installed());
+///
+/// let provider = noop::install().expect("Failed to get the installed provider");
+/// let logger = provider.logger("my_app");
+/// let mut record = logger.create_log_record();
+/// record.set_body("still usable".into());
+/// logger.emit(record);
+/// assert!(provider.force_flush().is_ok());
+///
+/// assert!(matches!(
+///     provider::install(),
+///     Err(LoggingError::AlreadyInitializedError(_))
+/// ));
+/// ```
 pub fn install() -> Result<SdkLoggerProvider, LoggingError> {
-    let app_cfgs = AppConfigs::new();
-
-    let base_fmt_layer = tracing_subscriber::fmt::layer()
-        .with_writer(std::io::stderr)
-        .event_format(
-            tracing_subscriber::fmt::format()
-                .with_thread_ids(true)
-                .with_thread_names(true)
-                .with_ansi(app_cfgs.env.is_local())
-                .with_level(true)
-                .with_target(true)
-                .with_file(true)
-                .with_line_number(true)
-                .with_source_location(true)
-                .compact(),
-        );
-
-    let mut fmt_pretty: Option<Layer<_, Pretty, Format<Pretty>>> = None;
-    let mut fmt_json = None;
-    if app_cfgs.env.is_local() {
-        fmt_pretty = Some(Layer::new().pretty());
-    } else {
-        fmt_json = Some(BunyanFormattingLayer::new(
-            app_cfgs.name.clone(),
-            std::io::stdout,
-        ));
-    }
+    crate::provider::install_exclusive_provider("noop", || {
+        install_with(
+            &AppConfigs::new(),
+            &LoggingConfigs::new(),
+            ConsoleOptions::default(),
+        )
+    })
+}
 
-    let filters = target_filters(&app_cfgs.log_level);
+/// Installs the noop log exporter with the given settings.
+pub(crate) fn install_with(
+    app_cfgs: &AppConfigs,
+    log_cfgs: &LoggingConfigs,
+    console: ConsoleOptions,
+) -> Result<SdkLoggerProvider, LoggingError> {
+    install_subscriber(
+        app_cfgs,
+        log_cfgs,
+        console_layers(app_cfgs, log_cfgs, console)?,
+    )?;
 
-    match tracing::subscriber::set_global_default(
-        tracing_subscriber::registry()
-            .with(base_fmt_layer)
-            .with(fmt_json)
-            .with(fmt_pretty)
-            .with(filters),
-    ) {
-        Err(err) => {
-            error!(error = ?err, "failure to set tracing subscribe");
-            return Err(LoggingError::InternalError {});
-        }
-        _ => Ok(LoggerProviderBuilder::default().build()),
-    }
+    Ok(LoggerProviderBuilder::default().build())
 }
@@ -0,0 +1,398 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # OTLP gRPC Client
+//!
+//! This module provides the exporter sending record batches to an OpenTelemetry
+//! collector through the OTLP logs gRPC service. It's used by the OTLP exporter instead
+//! of the `opentelemetry-otlp` client, which discards the collector response.
+//!
+//! A collector may accept a batch while rejecting some of its records, reporting them in
+//! the `partial_success` of the response. Those records are counted as
+//! `LogExportStats::rejected` and reported by a WARN event carrying the rejected count
+//! and the collector message. The event target is one of the
+//! [`crate::exporters::filters::OTEL_INTERNAL_TARGETS`], so it reaches the console only
+//! and can't trigger another export.
+//!
+//! The size of the requests is checked before sending them, so a batch over the
+//! configured maximum message size fails with an error giving its size and the limit,
+//! instead of the opaque status of the gRPC transport.
+//!
+//! The headers of `OTEL_EXPORTER_OTLP_HEADERS` and `OTEL_EXPORTER_OTLP_LOGS_HEADERS` are
+//! read when the client is created and sent as the metadata of every request, e.g. to
+//! authenticate with the collector.
+//!
+//! The channel of a client can be replaced while it's in use through its
+//! [`ChannelHandle`], e.g. to point the exports to another collector at runtime.
+
+use crate::{
+    config::OtlpCompression,
+    exporters::{envs, stats},
+};
+use opentelemetry_proto::{
+    tonic::collector::logs::v1::{
+        ExportLogsServiceRequest, ExportLogsServiceResponse, logs_service_client::LogsServiceClient,
+    },
+    transform::{
+        common::tonic::ResourceAttributesWithSchema, logs::tonic::group_logs_by_resource_and_scope,
+    },
+};
+use opentelemetry_sdk::{
+    Resource,
+    error::{OTelSdkError, OTelSdkResult},
+    logs::{LogBatch, LogExporter},
+};
+use prost::Message;
+use std::{
+    fmt,
+    sync::{Arc, PoisonError, RwLock},
+    time::Duration,
+};
+use tonic::{
+    Request,
+    codec::CompressionEncoding,
+    metadata::{AsciiMetadataKey, AsciiMetadataValue, MetadataMap},
+    transport::Channel,
+};
+use tracing::warn;
+
+/// A [`LogExporter`] calling the OTLP logs gRPC service over a channel.
+pub(crate) struct OtlpClient {
+    channel: ChannelHandle,
+    metadata: MetadataMap,
+    resource: ResourceAttributesWithSchema,
+}
+
+impl OtlpClient {
+    /// Creates an exporter sending batches over `channel` with the given compression and
+    /// maximum sizes of the requests and responses, in bytes, and the headers of the
+    /// environment.
+    pub(crate) fn new(
+        channel: Channel,
+        compression: OtlpCompression,
+        max_request_size: usize,
+        max_response_size: usize,
+    ) -> Self {
+        OtlpClient {
+            channel: ChannelHandle {
+                client: Arc::new(RwLock::new(client(
+                    channel,
+                    compression,
+                    max_request_size,
+                    max_response_size,
+                ))),
+                compression,
+                max_request_size,
+                max_response_size,
+            },
+            metadata: metadata(
+                std::env::var(envs::OTEL_EXPORTER_OTLP_HEADERS_ENV_KEY).ok(),
+                std::env::var(envs::OTEL_EXPORTER_OTLP_LOGS_HEADERS_ENV_KEY).ok(),
+            ),
+            resource: ResourceAttributesWithSchema::default(),
+        }
+    }
+
+    /// Returns the handle replacing the channel of this client.
+    pub(crate) fn channel_handle(&self) -> ChannelHandle {
+        self.channel.clone()
+    }
+}
+
+/// A handle replacing the channel of an [`OtlpClient`], for the next exports.
+#[derive(Clone)]
+pub(crate) struct ChannelHandle {
+    client: Arc<RwLock<LogsServiceClient<Channel>>>,
+    compression: OtlpCompression,
+    max_request_size: usize,
+    max_response_size: usize,
+}
+
+impl ChannelHandle {
+    /// Sends the next exports over `channel`, with the same compression and limits.
+    pub(crate) fn replace(&self, channel: Channel) {
+        *self.client.write().unwrap_or_else(PoisonError::into_inner) = client(
+            channel,
+            self.compression,
+            self.max_request_size,
+            self.max_response_size,
+        );
+    }
+
+    /// Returns a client on the current channel.
+    fn current(&self) -> LogsServiceClient<Channel> {
+        // The client is a cheap handle on the channel
+        self.client
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+/// Creates a client sending requests over `channel`.
+fn client(
+    channel: Channel,
+    compression: OtlpCompression,
+    max_request_size: usize,
+    max_response_size: usize,
+) -> LogsServiceClient<Channel> {
+    let client = LogsServiceClient::new(channel)
+        .max_encoding_message_size(max_request_size)
+        .max_decoding_message_size(max_response_size);
+    match compression {
+        OtlpCompression::Gzip => client
+            .send_compressed(CompressionEncoding::Gzip)
+            .accept_compressed(CompressionEncoding::Gzip),
+        OtlpCompression::Uncompressed => client,
+    }
+}
+
+impl fmt::Debug for OtlpClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OtlpClient")
+    }
+}
+
+impl LogExporter for OtlpClient {
+    async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
+        let records = batch.iter().count();
+        let request = ExportLogsServiceRequest {
+            resource_logs: group_logs_by_resource_and_scope(batch, &self.resource),
+        };
+        check_size(&request, records, self.channel.max_request_size)?;
+        let mut request = Request::new(request);
+        *request.metadata_mut() = self.metadata.clone();
+
+        // Cloned to get a mutable client, without holding the lock while exporting
+        let response = self
+            .channel
+            .current()
+            .export(request)
+            .await
+            .map_err(|status| OTelSdkError::InternalFailure(format!("export error: {status:?}")))?;
+        report_partial_success(response.into_inner());
+        Ok(())
+    }
+
+    fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+        // Nothing is buffered here, and the channel closes when the client is dropped
+        Ok(())
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.resource = resource.into();
+    }
+}
+
+/// Fails with a descriptive error if the request is over the maximum message size.
+fn check_size(request: &ExportLogsServiceRequest, records: usize, max: usize) -> OTelSdkResult {
+    let size = request.encoded_len();
+    if size <= max {
+        return Ok(());
+    }
+
+    Err(OTelSdkError::InternalFailure(if records == 1 {
+        format!(
+            "a log record of {size} bytes exceeds the maximum message size of {max} bytes, \
+             see LOG_OTLP_MAX_ENCODING_MESSAGE_SIZE"
+        )
+    } else {
+        format!(
+            "a batch of {records} log records of {size} bytes exceeds the maximum message \
+             size of {max} bytes, see LOG_OTLP_MAX_ENCODING_MESSAGE_SIZE and \
+             OTEL_BLRP_MAX_EXPORT_BATCH_SIZE"
+        )
+    }))
+}
+
+/// Builds the request metadata from the generic and the logs-specific OTLP headers,
+/// the latter replacing the former.
+///
+/// Headers are comma-separated `key=value` entries with percent-encoded values. Entries
+/// that aren't valid gRPC ASCII metadata are skipped.
+fn metadata(headers: Option<String>, logs_headers: Option<String>) -> MetadataMap {
+    let mut metadata = MetadataMap::new();
+    for entry in headers
+        .iter()
+        .chain(&logs_headers)
+        .flat_map(|value| value.split(','))
+    {
+        let Some((key, value)) = entry.split_once('=') else {
+            continue;
+        };
+        if let (Ok(key), Ok(value)) = (
+            AsciiMetadataKey::from_bytes(key.trim().as_bytes()),
+            AsciiMetadataValue::try_from(percent_decode(value.trim())),
+        ) {
+            metadata.insert(key, value);
+        }
+    }
+    metadata
+}
+
+/// Decodes the `%XX` escapes of `value`, keeping malformed ones as is.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Counts and reports the records rejected by the collector, if any.
+fn report_partial_success(response: ExportLogsServiceResponse) {
+    let Some(partial_success) = response.partial_success else {
+        return;
+    };
+    // A partial success without rejected records only carries a warning
+    if partial_success.rejected_log_records <= 0 {
+        if !partial_success.error_message.is_empty() {
+            warn!(
+                error = %partial_success.error_message,
+                "collector accepted a log export with a warning"
+            );
+        }
+        return;
+    }
+
+    stats::record_rejected(partial_success.rejected_log_records as u64);
+    warn!(
+        rejected = partial_success.rejected_log_records,
+        error = %partial_success.error_message,
+        "collector rejected part of a log export"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::LoggingConfig,
+        test::{collector::StubCollector, init_with},
+    };
+    use opentelemetry::logs::{AnyValue, LogRecord, Logger, LoggerProvider};
+    use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsPartialSuccess;
+    use opentelemetry_sdk::logs::SdkLoggerProvider;
+
+    #[test]
+    fn headers_are_parsed_into_metadata() {
+        let metadata = metadata(
+            Some("Authorization=Bearer%20abc, x-tenant = acme,malformed,=empty".to_string()),
+            None,
+        );
+
+        assert_eq!(metadata.len(), 2);
+        assert_eq!(metadata.get("authorization").unwrap(), "Bearer abc");
+        assert_eq!(metadata.get("x-tenant").unwrap(), "acme");
+    }
+
+    #[test]
+    fn logs_headers_replace_the_generic_ones() {
+        let metadata = metadata(
+            Some("x-tenant=acme,x-region=eu".to_string()),
+            Some("x-tenant=globex".to_string()),
+        );
+
+        assert_eq!(metadata.get("x-tenant").unwrap(), "globex");
+        assert_eq!(metadata.get("x-region").unwrap(), "eu");
+    }
+
+    #[test]
+    fn invalid_headers_are_skipped() {
+        let metadata = metadata(Some("x-key-bin=abc,bad key=1,x-ok=%zz%4".to_string()), None);
+
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(metadata.get("x-ok").unwrap(), "%zz%4");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rejected_records_are_counted_and_reported() {
+        let _lock = stats::TEST_LOCK.lock().await;
+        let logs = init_with(LoggingConfig {
+            level: Some("info".to_string()),
+            ..LoggingConfig::default()
+        });
+        let collector = StubCollector::rejecting(2);
+        let channel = Channel::from_shared(collector.endpoint().to_string())
+            .unwrap()
+            .connect_lazy();
+        let client = OtlpClient::new(
+            channel,
+            OtlpCompression::Gzip,
+            4 * 1024 * 1024,
+            4 * 1024 * 1024,
+        );
+        let provider = SdkLoggerProvider::builder()
+            .with_simple_exporter(client)
+            .build();
+        let before = stats::snapshot();
+
+        // The simple processor exports on the emitting thread, where the WARN is captured
+        let dispatch = logs.dispatch().clone();
+        tokio::task::spawn_blocking(move || {
+            tracing::dispatcher::with_default(&dispatch, || {
+                let logger = provider.logger("orders");
+                let mut record = logger.create_log_record();
+                record.set_body(AnyValue::String("order charged".into()));
+                logger.emit(record);
+            });
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(collector.bodies(), ["order charged"]);
+        assert_eq!(stats::snapshot().rejected - before.rejected, 2);
+        assert!(logs.contains("collector rejected part of a log export"));
+        assert!(logs.contains("rejected=2"));
+        assert!(logs.contains("attribute limit exceeded"));
+    }
+
+    #[test]
+    fn partial_success_without_rejected_records_is_a_warning() {
+        let _lock = stats::TEST_LOCK.blocking_lock();
+        let logs = init_with(LoggingConfig {
+            level: Some("info".to_string()),
+            ..LoggingConfig::default()
+        });
+        let before = stats::snapshot();
+
+        report_partial_success(ExportLogsServiceResponse {
+            partial_success: Some(ExportLogsPartialSuccess {
+                rejected_log_records: 0,
+                error_message: "attribute values truncated".to_string(),
+            }),
+        });
+        report_partial_success(ExportLogsServiceResponse {
+            partial_success: Some(ExportLogsPartialSuccess::default()),
+        });
+
+        assert_eq!(stats::snapshot().rejected - before.rejected, 0);
+        assert!(logs.contains("collector accepted a log export with a warning"));
+        assert!(logs.contains("attribute values truncated"));
+        assert!(!logs.contains("collector rejected"));
+        // A single event, the empty partial success being ignored
+        let records = logs
+            .output()
+            .lines()
+            .filter(|line| line.starts_with('{'))
+            .count();
+        assert_eq!(records, 1);
+    }
+}
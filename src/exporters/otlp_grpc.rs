@@ -12,23 +12,62 @@
 //! The OTLP exporter sends logs in a standardized format, allowing for distributed tracing,
 //! metrics collection, and log correlation across different services and applications.
 //! It also configures local console/terminal output with formatting based on the environment.
+//!
+//! Collectors behind an `https` endpoint are reached over TLS, with their certificate
+//! verified against the native root certificates:
+//!
+//! ```no_run
+//! use logging::{
+//!     config::{ExporterKind, LoggingConfig},
+//!     provider,
+//! };
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let provider = provider::install_from_config(LoggingConfig {
+//!         exporter: Some(ExporterKind::Otlp),
+//!         otlp_endpoint: Some("https://collector.example.com:4317".to_string()),
+//!         ..LoggingConfig::default()
+//!     })
+//!     .expect("Failed to set up OTLP logging");
+//!     tracing::info!("Application started");
+//! }
+//! ```
 
-use crate::{errors::LoggingError, exporters::filters::target_filters};
+use crate::{
+    config::{KeepaliveConfigs, LoggingConfigs, TlsConfigs},
+    correlation::CorrelationProcessor,
+    errors::LoggingError,
+    exporters::{
+        backpressure::BoundedQueue,
+        baggage::BaggageProcessor,
+        body::StructuredBodyProcessor,
+        error_flush::ErrorFlushProcessor,
+        event_name::{EventNameProcessor, NameRules},
+        export_timing::TimedExporter,
+        failover::FailoverExporter,
+        layers::{ConsoleOptions, console_layers, export_layer, install_subscriber},
+        otlp_client::{ChannelHandle, OtlpClient},
+        resource,
+        severity::{SeverityMapping, SeverityProcessor},
+        stats::{self, CountingExporter},
+        timestamp::TimestampProcessor,
+    },
+};
 use configs::{app::AppConfigs, otlp::OTLPConfigs};
-use opentelemetry::KeyValue;
 use opentelemetry_appender_tracing::layer;
-use opentelemetry_otlp::{Compression, LogExporter, Protocol, WithExportConfig, WithTonicConfig};
-use opentelemetry_sdk::{Resource, logs::SdkLoggerProvider};
-use tracing::error;
-use tracing_bunyan_formatter::BunyanFormattingLayer;
-use tracing_subscriber::{
-    fmt::{
-        Layer,
-        format::{Format, Pretty},
-    },
-    layer::SubscriberExt,
-    prelude::*,
+use opentelemetry_sdk::{
+    Resource,
+    logs::{BatchConfigBuilder, BatchLogProcessor, LogProcessor, SdkLoggerProvider},
+};
+use std::{
+    net::{TcpStream, ToSocketAddrs},
+    path::Path,
+    sync::{Mutex, PoisonError},
+    time::Duration,
 };
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity, Uri};
 
 /// Installs and configures the OpenTelemetry OTLP gRPC log exporter.
 ///
@@ -39,112 +78,648 @@ use tracing_subscriber::{
 /// - In non-local environments, logs are formatted as JSON in Bunyan format
 ///
 /// It configures the OpenTelemetry exporter with gRPC protocol, Gzip compression,
-/// and timeout settings from the OTLPConfigs.
+/// and timeout settings from the OTLPConfigs. Records are exported by a batch
+/// processor configured from [`crate::config::BatchConfigs`], after their severity
+/// is remapped according to `LOG_OTLP_SEVERITY_MAP` and the baggage entries listed in
+/// `LOG_BAGGAGE_KEYS` are attached.
+///
+/// ## Tokio Runtime
+///
+/// The gRPC transport runs on the caller's Tokio runtime, so this function must be
+/// called from within one, e.g. from an async `main` or through
+/// [`crate::provider::install_async`]. The runtime must be multi-threaded: the batch
+/// processor blocks its own thread while exporting, and flushing from the only thread of
+/// a current-thread runtime stalls until the export timeout.
 ///
 /// # Returns
 ///
 /// * `Result<SdkLoggerProvider, LoggingError>` - On success, returns the configured
-///   OpenTelemetry logger provider. On failure, returns a `LoggingError`.
+///   OpenTelemetry logger provider, or a clone of the one installed by a previous call.
+///   On failure, returns a `LoggingError`.
 ///
 /// # Errors
 ///
-/// Returns `LoggingError::InternalError` if there's a problem setting up the
-/// log exporter or the tracing subscriber.
+/// Returns `LoggingError::RuntimeUnavailableError` if called outside of a Tokio
+/// runtime, `LoggingError::InvalidBatchConfigError` if the batch processor settings
+/// are incoherent, or `LoggingError::SubscriberInstallError` if the tracing subscriber
+/// can't be set.
+///
+/// Returns `LoggingError::AlreadyInitializedError` if logging is already installed, by
+/// [`crate::provider::install`] or the exporter of another module. Calling this
+/// function again hands back the provider it installed.
 ///
 /// # Examples
 ///
 /// ```no_run
 /// use logging::exporters::otlp_grpc;
 ///
-/// fn main() {
+/// #[tokio::main]
+/// async fn main() {
 ///     let provider = otlp_grpc::install().expect("Failed to set up OTLP logging");
 ///     // Now logs will be written both to the console and sent to the OpenTelemetry collector
 ///     tracing::info!("Application started");
 /// }
 /// ```
 pub fn install() -> Result<SdkLoggerProvider, LoggingError> {
-    let app_cfgs = AppConfigs::new();
-    let otlp_cfgs = OTLPConfigs::new();
-
-    // Create the OTLP log exporter with gRPC configuration
-    let exporter = match LogExporter::builder()
-        .with_tonic()
-        .with_protocol(Protocol::Grpc)
-        .with_timeout(otlp_cfgs.exporter_timeout)
-        .with_endpoint(otlp_cfgs.endpoint.clone())
-        .with_compression(Compression::Gzip)
-        .build()
-    {
-        Ok(exporter) => Ok(exporter),
-        Err(err) => {
-            error!(error = ?err, "failure to create log exporter");
-            Err(LoggingError::InternalError {})
-        }
-    }?;
+    crate::provider::install_exclusive_provider("OTLP", || {
+        install_with(
+            &AppConfigs::new(),
+            &OTLPConfigs::new(),
+            &LoggingConfigs::new(),
+            ConsoleOptions::default(),
+        )
+    })
+}
+
+/// Installs the OTLP gRPC log exporter with the given settings.
+pub(crate) fn install_with(
+    app_cfgs: &AppConfigs,
+    otlp_cfgs: &OTLPConfigs,
+    log_cfgs: &LoggingConfigs,
+    console: ConsoleOptions,
+) -> Result<SdkLoggerProvider, LoggingError> {
+    let (provider, channel) = logger_provider(app_cfgs, otlp_cfgs, log_cfgs, None)?;
+    install_provider(provider, channel, app_cfgs, log_cfgs, console)
+}
+
+/// Builds the logger provider exporting records to the OTLP collector, with the `custom`
+/// resource if any, and the handle on the channel of its primary endpoint.
+///
+/// Nothing is installed yet, so a failure leaves the caller free to install logging
+/// differently.
+///
+/// # Errors
+///
+/// Returns the errors of [`processor`], and `LoggingError::InvalidServiceNameError` if
+/// the service name is empty.
+pub(crate) fn logger_provider(
+    app_cfgs: &AppConfigs,
+    otlp_cfgs: &OTLPConfigs,
+    log_cfgs: &LoggingConfigs,
+    custom: Option<Resource>,
+) -> Result<(SdkLoggerProvider, ChannelHandle), LoggingError> {
+    let resource = resource::build(app_cfgs, log_cfgs, custom)?;
+    let (processor, channel) = processor_with_channel(otlp_cfgs, log_cfgs)?;
 
     // Configure the logger provider with service information
-    let provider: SdkLoggerProvider = SdkLoggerProvider::builder()
-        .with_resource(
-            Resource::builder()
-                .with_service_name(app_cfgs.name.clone())
-                .with_attribute(KeyValue::new(
-                    "service.namespace",
-                    format!("{}", app_cfgs.namespace),
-                ))
-                .with_attribute(KeyValue::new("environment", format!("{}", app_cfgs.env)))
-                .with_attribute(KeyValue::new("library.language", "rust"))
+    let provider = SdkLoggerProvider::builder()
+        .with_resource(resource)
+        .with_log_processor(processor)
+        .build();
+    Ok((provider, channel))
+}
+
+/// Installs the subscriber bridging events to `provider`, next to the console output.
+///
+/// Once installed, the primary endpoint can be changed through `channel` by
+/// [`reconnect`].
+pub(crate) fn install_provider(
+    provider: SdkLoggerProvider,
+    channel: ChannelHandle,
+    app_cfgs: &AppConfigs,
+    log_cfgs: &LoggingConfigs,
+    console: ConsoleOptions,
+) -> Result<SdkLoggerProvider, LoggingError> {
+    // Bridge events to OpenTelemetry next to the environment-aware console output
+    let mut layers = vec![export_layer(
+        layer::OpenTelemetryTracingBridge::new(&provider),
+        log_cfgs,
+    )];
+    layers.extend(console_layers(app_cfgs, log_cfgs, console)?);
+
+    // Set up the global subscriber with all configured layers
+    install_subscriber(app_cfgs, log_cfgs, layers)?;
+    register(Reconnectable::Primary(channel));
+
+    Ok(provider)
+}
+
+/// Builds the processor chain exporting records to the OTLP collector.
+///
+/// Records get their event timestamp, are counted, decorated with baggage and the
+/// correlation id, and remapped to the configured severities, then buffered under the
+/// backpressure policy and exported in batches, failing over to the secondary
+/// endpoints when configured.
+///
+/// # Errors
+///
+/// Returns `LoggingError::RuntimeUnavailableError` outside of a Tokio runtime,
+/// `LoggingError::InvalidBatchConfigError` or `LoggingError::InvalidConfigError` if the
+/// batch, severity or endpoint settings are invalid.
+pub(crate) fn processor(
+    otlp_cfgs: &OTLPConfigs,
+    log_cfgs: &LoggingConfigs,
+) -> Result<impl LogProcessor + use<>, LoggingError> {
+    processor_with_channel(otlp_cfgs, log_cfgs).map(|(processor, _)| processor)
+}
+
+/// Builds the processor chain of [`processor`], and the handle on the channel of its
+/// primary endpoint.
+fn processor_with_channel(
+    otlp_cfgs: &OTLPConfigs,
+    log_cfgs: &LoggingConfigs,
+) -> Result<(impl LogProcessor + use<>, ChannelHandle), LoggingError> {
+    // The transport spawns its connection tasks on the current runtime, and panics
+    // without one
+    let runtime = Handle::try_current().map_err(|_| {
+        LoggingError::RuntimeUnavailableError(
+            "the OTLP exporter must be installed from within a Tokio runtime".to_string(),
+        )
+    })?;
+    if runtime.runtime_flavor() == RuntimeFlavor::CurrentThread {
+        println!(
+            "The OTLP exporter is installed on a current-thread Tokio runtime; flushing \
+             from the runtime thread stalls until the export timeout"
+        );
+    }
+
+    let batch_cfgs = &log_cfgs.otlp_batch;
+    batch_cfgs.validate()?;
+    let severity_mapping = SeverityMapping::from_entries(&log_cfgs.otlp_severity_map)?;
+    let name_rules = NameRules::from_entries(&log_cfgs.event_name_rules)?;
+
+    // Create one OTLP log exporter per endpoint, the primary first
+    let timeout = batch_cfgs
+        .max_export_timeout
+        .unwrap_or(otlp_cfgs.exporter_timeout);
+    let exporters = std::iter::once(&otlp_cfgs.endpoint)
+        .chain(&log_cfgs.otlp_failover.endpoints)
+        .map(|endpoint| exporter(endpoint, timeout, log_cfgs))
+        .collect::<Result<Vec<_>, _>>()?;
+    let channel = exporters[0].channel_handle();
+    let exporter = FailoverExporter::new(exporters, &log_cfgs.otlp_failover);
+
+    // Buffer records and export them in batches, applying the backpressure policy once
+    // the queue is full
+    let queue = BoundedQueue::new(batch_cfgs.max_queue_size, batch_cfgs.backpressure)
+        .with_block_timeout(timeout);
+    stats::register(&queue);
+    let exporter = TimedExporter::new(exporter, EXPORT_TIMING_INTERVAL);
+    let processor = BatchLogProcessor::builder(queue.exporter(CountingExporter::new(exporter)))
+        .with_batch_config(
+            BatchConfigBuilder::default()
+                .with_scheduled_delay(batch_cfgs.scheduled_delay)
+                .with_max_queue_size(queue.batch_queue_size())
+                .with_max_export_batch_size(batch_cfgs.max_export_batch_size)
                 .build(),
         )
-        .with_simple_exporter(exporter)
         .build();
 
-    // Configure the base formatting layer with detailed metadata
-    let base_fmt_layer = tracing_subscriber::fmt::layer()
-        .with_writer(std::io::stderr)
-        .event_format(
-            tracing_subscriber::fmt::format()
-                .with_thread_ids(true)
-                .with_thread_names(true)
-                .with_ansi(app_cfgs.env.is_local())
-                .with_level(true)
-                .with_target(true)
-                .with_file(true)
-                .with_line_number(true)
-                .with_source_location(true)
-                .compact(),
-        );
+    let processor = EventNameProcessor::new(TimestampProcessor::new(
+        SeverityProcessor::new(
+            BaggageProcessor::new(
+                CorrelationProcessor::new(queue.processor(processor)),
+                log_cfgs.baggage_keys.clone(),
+            ),
+            severity_mapping,
+        ),
+        log_cfgs.timestamp_field.clone(),
+    ))
+    .with_rules(name_rules);
+    let processor = StructuredBodyProcessor::new(processor, log_cfgs.otlp_structured_body);
+    Ok((
+        ErrorFlushProcessor::new(processor, log_cfgs.flush_on_error),
+        channel,
+    ))
+}
+
+/// How the endpoint of the installed OTLP exporter can be changed.
+pub(crate) enum Reconnectable {
+    /// Through the channel handle of the primary endpoint of the single exporter.
+    Primary(ChannelHandle),
+    /// Not at all: each output has its own endpoint, set at install time.
+    Outputs,
+}
+
+/// The installed OTLP exporter, registered once its subscriber is set.
+static INSTALLED: Mutex<Option<Reconnectable>> = Mutex::new(None);
+
+/// Registers the installed OTLP exporter, replacing any previous one.
+pub(crate) fn register(installed: Reconnectable) {
+    *INSTALLED.lock().unwrap_or_else(PoisonError::into_inner) = Some(installed);
+}
+
+/// Points the installed OTLP exporter to another primary endpoint, for the next exports.
+///
+/// Returns `false` when no OTLP exporter is installed. The failover endpoints are not
+/// affected.
+///
+/// # Errors
+///
+/// Returns `LoggingError::ReconfigureError` when the OTLP exporter is installed as
+/// outputs, `LoggingError::RuntimeUnavailableError` outside of a Tokio runtime, and
+/// `LoggingError::InvalidConfigError` if the endpoint is invalid.
+pub(crate) fn reconnect(
+    endpoint: &str,
+    timeout: Duration,
+    log_cfgs: &LoggingConfigs,
+) -> Result<bool, LoggingError> {
+    let installed = INSTALLED.lock().unwrap_or_else(PoisonError::into_inner);
+    let handle = match &*installed {
+        Some(Reconnectable::Primary(handle)) => handle,
+        Some(Reconnectable::Outputs) => {
+            return Err(LoggingError::ReconfigureError(
+                "the OTLP endpoint of the outputs can't be changed without a restart".to_string(),
+            ));
+        }
+        None => return Ok(false),
+    };
+    Handle::try_current().map_err(|_| {
+        LoggingError::RuntimeUnavailableError(
+            "the OTLP endpoint must be changed from within a Tokio runtime".to_string(),
+        )
+    })?;
 
-    // Select the appropriate formatter based on environment
-    let mut fmt_pretty: Option<Layer<_, Pretty, Format<Pretty>>> = None;
-    let mut fmt_json = None;
-    if app_cfgs.env.is_local() {
-        fmt_pretty = Some(Layer::new().pretty());
+    handle.replace(channel(endpoint, timeout, log_cfgs)?);
+    Ok(true)
+}
+
+/// Checks whether the collector at `endpoint` accepts TCP connections within `timeout`.
+///
+/// Only the connection is checked, not the gRPC service behind it. Returns `false` for an
+/// invalid endpoint or one whose host doesn't resolve.
+pub(crate) fn preflight(endpoint: &str, timeout: Duration) -> bool {
+    let Ok(uri) = endpoint.parse::<Uri>() else {
+        return false;
+    };
+    let Some(host) = uri.host() else {
+        return false;
+    };
+    let port = uri
+        .port_u16()
+        .unwrap_or(if uri.scheme_str() == Some("https") {
+            443
+        } else {
+            80
+        });
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+
+    (host, port)
+        .to_socket_addrs()
+        .is_ok_and(|mut addrs| addrs.any(|addr| TcpStream::connect_timeout(&addr, timeout).is_ok()))
+}
+
+/// Minimum interval between two reports of the export timings.
+const EXPORT_TIMING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Creates an OTLP log exporter with gRPC configuration for the given endpoint.
+fn exporter(
+    endpoint: &str,
+    timeout: Duration,
+    log_cfgs: &LoggingConfigs,
+) -> Result<OtlpClient, LoggingError> {
+    Ok(OtlpClient::new(
+        channel(endpoint, timeout, log_cfgs)?,
+        log_cfgs.otlp_compression,
+        log_cfgs.otlp_max_encoding_message_size,
+        log_cfgs.otlp_max_decoding_message_size,
+    ))
+}
+
+/// Creates the lazily connected gRPC channel to the given endpoint.
+///
+/// `timeout` bounds each export, while connection attempts are bounded by the shorter
+/// `LoggingConfigs::otlp_connect_timeout`. Connections to an `https` endpoint use TLS,
+/// with the settings of `LoggingConfigs::otlp_tls`.
+fn channel(
+    endpoint: &str,
+    timeout: Duration,
+    log_cfgs: &LoggingConfigs,
+) -> Result<Channel, LoggingError> {
+    let endpoint = Channel::from_shared(endpoint.to_string())
+        .map_err(|err| {
+            LoggingError::InvalidConfigError(format!(
+                "invalid collector endpoint `{endpoint}`: {err}"
+            ))
+        })?
+        .connect_timeout(log_cfgs.otlp_connect_timeout)
+        .timeout(timeout);
+
+    let endpoint = if endpoint.uri().scheme_str() == Some("https") {
+        let uri = endpoint.uri().clone();
+        endpoint
+            .tls_config(tls_config(&log_cfgs.otlp_tls)?)
+            .map_err(|err| {
+                LoggingError::InvalidConfigError(format!("invalid TLS settings for `{uri}`: {err}"))
+            })?
     } else {
-        fmt_json = Some(BunyanFormattingLayer::new(
-            app_cfgs.name.clone(),
-            std::io::stdout,
+        endpoint
+    };
+
+    let endpoint = match Keepalive::from_configs(&log_cfgs.otlp_keepalive) {
+        Some(keepalive) => keepalive.apply(endpoint),
+        None => endpoint,
+    };
+    Ok(endpoint.connect_lazy())
+}
+
+/// Maps the TLS settings, reading the certificate files they point to.
+fn tls_config(tls: &TlsConfigs) -> Result<ClientTlsConfig, LoggingError> {
+    let read = |path: &Path| {
+        std::fs::read(path).map_err(|err| {
+            LoggingError::InvalidConfigError(format!(
+                "cannot read the TLS file `{}`: {err}",
+                path.display()
+            ))
+        })
+    };
+
+    let mut config = ClientTlsConfig::new().with_enabled_roots();
+    if let Some(path) = &tls.ca_certificate {
+        config = config.ca_certificate(Certificate::from_pem(read(path)?));
+    }
+    if let Some(domain_name) = &tls.domain_name {
+        config = config.domain_name(domain_name);
+    }
+    match (&tls.client_certificate, &tls.client_key) {
+        (Some(certificate), Some(key)) => {
+            config = config.identity(Identity::from_pem(read(certificate)?, read(key)?));
+        }
+        (None, None) => {}
+        _ => {
+            return Err(LoggingError::InvalidConfigError(
+                "a TLS client certificate and its key must be set together".to_string(),
+            ));
+        }
+    }
+    Ok(config)
+}
+
+/// HTTP/2 keepalive settings of the gRPC channels.
+#[derive(Debug, PartialEq, Eq)]
+struct Keepalive {
+    interval: Duration,
+    timeout: Duration,
+    while_idle: bool,
+}
+
+impl Keepalive {
+    /// Maps the configured settings, `None` when keepalive is disabled.
+    fn from_configs(cfg: &KeepaliveConfigs) -> Option<Self> {
+        cfg.is_enabled().then_some(Keepalive {
+            interval: cfg.interval,
+            timeout: cfg.timeout,
+            while_idle: cfg.while_idle,
+        })
+    }
+
+    /// Applies the settings to the channel builder.
+    fn apply(&self, endpoint: Endpoint) -> Endpoint {
+        endpoint
+            .http2_keep_alive_interval(self.interval)
+            .keep_alive_timeout(self.timeout)
+            .keep_alive_while_idle(self.while_idle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::collector::StubCollector;
+    use opentelemetry::logs::{AnyValue, LogRecord, Logger, LoggerProvider};
+    use opentelemetry_proto::tonic::collector::logs::v1::{
+        ExportLogsServiceRequest, logs_service_client::LogsServiceClient,
+    };
+
+    fn otlp_configs(endpoint: &str) -> OTLPConfigs {
+        let mut otlp_cfgs = OTLPConfigs::new();
+        otlp_cfgs.endpoint = endpoint.to_string();
+        otlp_cfgs.exporter_timeout = Duration::from_secs(5);
+        otlp_cfgs
+    }
+
+    #[test]
+    fn processor_requires_a_tokio_runtime() {
+        let result = processor(
+            &otlp_configs("http://127.0.0.1:4317"),
+            &LoggingConfigs::default(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(LoggingError::RuntimeUnavailableError(_))
         ));
     }
 
-    // Configure filters and OpenTelemetry bridge
-    let filters = target_filters(&app_cfgs.log_level);
-    let otel_layer = layer::OpenTelemetryTracingBridge::new(&provider).with_filter(filters.clone());
+    #[tokio::test(flavor = "multi_thread")]
+    async fn processor_exports_from_within_a_tokio_runtime() {
+        let _lock = stats::TEST_LOCK.lock().await;
+        let collector = StubCollector::start();
+        let processor = processor(
+            &otlp_configs(collector.endpoint()),
+            &LoggingConfigs::default(),
+        )
+        .unwrap();
+        let provider = SdkLoggerProvider::builder()
+            .with_log_processor(processor)
+            .build();
 
-    // Set up the global subscriber with all configured layers
-    match tracing::subscriber::set_global_default(
-        tracing_subscriber::registry()
-            .with(otel_layer)
-            .with(base_fmt_layer)
-            .with(fmt_json)
-            .with(fmt_pretty)
-            .with(filters),
-    ) {
-        Err(err) => {
-            error!(error = ?err, "failure to set tracing subscribe");
-            return Err(LoggingError::InternalError {});
+        let logger = provider.logger("orders");
+        let mut record = logger.create_log_record();
+        record.set_body(AnyValue::String("order charged".into()));
+        logger.emit(record);
+        // Flushing blocks until the export is done
+        tokio::task::spawn_blocking(move || provider.force_flush())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(collector.bodies(), ["order charged"]);
+    }
+
+    #[tokio::test]
+    async fn building_a_processor_leaves_the_endpoint_unchanged() {
+        let _lock = stats::TEST_LOCK.lock().await;
+        let _processor = processor(
+            &otlp_configs("http://localhost:4317"),
+            &LoggingConfigs::default(),
+        )
+        .unwrap();
+
+        // Only an installed exporter can be pointed to another endpoint
+        let reconnected = reconnect(
+            "http://localhost:4318",
+            Duration::from_secs(5),
+            &LoggingConfigs::default(),
+        );
+        assert!(matches!(reconnected, Ok(false)));
+    }
+
+    #[test]
+    fn keepalive_is_enabled_while_idle_by_default() {
+        assert_eq!(
+            Keepalive::from_configs(&KeepaliveConfigs::default()),
+            Some(Keepalive {
+                interval: Duration::from_secs(30),
+                timeout: Duration::from_secs(10),
+                while_idle: true,
+            })
+        );
+    }
+
+    #[test]
+    fn keepalive_maps_the_configured_settings() {
+        let cfg: KeepaliveConfigs =
+            serde_json::from_str(r#"{"interval": 5000, "timeout": 2000, "while_idle": false}"#)
+                .unwrap();
+
+        assert_eq!(
+            Keepalive::from_configs(&cfg),
+            Some(Keepalive {
+                interval: Duration::from_secs(5),
+                timeout: Duration::from_secs(2),
+                while_idle: false,
+            })
+        );
+    }
+
+    #[test]
+    fn keepalive_is_disabled_by_a_zero_interval() {
+        let cfg = KeepaliveConfigs {
+            interval: Duration::ZERO,
+            ..KeepaliveConfigs::default()
+        };
+
+        assert_eq!(Keepalive::from_configs(&cfg), None);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn keepalive_channel_exports() {
+        let _lock = stats::TEST_LOCK.lock().await;
+        let collector = StubCollector::start();
+        let log_cfgs = LoggingConfigs {
+            otlp_keepalive: KeepaliveConfigs {
+                interval: Duration::from_millis(100),
+                timeout: Duration::from_millis(100),
+                while_idle: true,
+            },
+            ..LoggingConfigs::default()
+        };
+        let processor = processor(&otlp_configs(collector.endpoint()), &log_cfgs).unwrap();
+        let provider = SdkLoggerProvider::builder()
+            .with_log_processor(processor)
+            .build();
+
+        let logger = provider.logger("orders");
+        let mut record = logger.create_log_record();
+        record.set_body(AnyValue::String("order charged".into()));
+        logger.emit(record);
+        // The connection stays usable across pings
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        tokio::task::spawn_blocking(move || provider.force_flush())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(collector.bodies(), ["order charged"]);
+    }
+
+    #[tokio::test]
+    async fn https_endpoints_use_tls() {
+        // The collector speaks plaintext, so only the TLS handshake can fail
+        let collector = StubCollector::start();
+        let endpoint = collector.endpoint().replace("http://", "https://");
+        let channel = channel(
+            &endpoint,
+            Duration::from_secs(5),
+            &LoggingConfigs::default(),
+        )
+        .unwrap();
+
+        let status = LogsServiceClient::new(channel)
+            .export(ExportLogsServiceRequest::default())
+            .await
+            .unwrap_err();
+
+        assert!(!format!("{status:?}").contains("HttpsUriWithoutTlsSupport"));
+        assert!(collector.bodies().is_empty());
+    }
+
+    /// Writes `contents` to a file of the temporary directory unique to this test.
+    fn tls_file(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "logging-tls-{}-{:?}-{name}",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn tls_settings_are_mapped() {
+        let tls = TlsConfigs {
+            ca_certificate: Some(tls_file("ca.pem", "ca")),
+            domain_name: Some("collector.internal".to_string()),
+            client_certificate: Some(tls_file("client.pem", "certificate")),
+            client_key: Some(tls_file("client.key", "key")),
+        };
+
+        let expected = ClientTlsConfig::new()
+            .with_enabled_roots()
+            .ca_certificate(Certificate::from_pem("ca"))
+            .domain_name("collector.internal")
+            .identity(Identity::from_pem("certificate", "key"));
+        assert_eq!(
+            format!("{:?}", tls_config(&tls).unwrap()),
+            format!("{expected:?}")
+        );
+        for path in [tls.ca_certificate, tls.client_certificate, tls.client_key] {
+            std::fs::remove_file(path.unwrap()).unwrap();
         }
-        _ => {}
     }
 
-    Ok(provider)
+    #[test]
+    fn tls_defaults_to_the_native_roots() {
+        assert_eq!(
+            format!("{:?}", tls_config(&TlsConfigs::default()).unwrap()),
+            format!("{:?}", ClientTlsConfig::new().with_enabled_roots())
+        );
+    }
+
+    #[test]
+    fn client_certificate_requires_its_key() {
+        let tls = TlsConfigs {
+            client_certificate: Some("client.pem".into()),
+            ..TlsConfigs::default()
+        };
+
+        assert!(matches!(
+            tls_config(&tls),
+            Err(LoggingError::InvalidConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn unreadable_certificates_are_reported() {
+        let tls = TlsConfigs {
+            ca_certificate: Some("/nonexistent/ca.pem".into()),
+            ..TlsConfigs::default()
+        };
+
+        let Err(LoggingError::InvalidConfigError(message)) = tls_config(&tls) else {
+            panic!("expected an invalid config error");
+        };
+        assert!(message.contains("/nonexistent/ca.pem"));
+    }
+
+    #[test]
+    fn tls_settings_apply_to_https_endpoints_only() {
+        let tls = TlsConfigs {
+            ca_certificate: Some("/nonexistent/ca.pem".into()),
+            ..TlsConfigs::default()
+        };
+        let log_cfgs = LoggingConfigs {
+            otlp_tls: tls,
+            ..LoggingConfigs::default()
+        };
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _runtime = runtime.enter();
+
+        assert!(channel("http://127.0.0.1:4317", Duration::from_secs(1), &log_cfgs).is_ok());
+        assert!(matches!(
+            channel("https://127.0.0.1:4317", Duration::from_secs(1), &log_cfgs),
+            Err(LoggingError::InvalidConfigError(_))
+        ));
+    }
 }
@@ -0,0 +1,476 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Process Fields
+//!
+//! This module provides a layer wrapper adding process and runtime metadata to every
+//! event, as regular fields that reach the console and the exporters alike:
+//! - `pid`: the id of the process
+//! - `thread.id`: the id of the thread within the process
+//! - `thread.name`: the name of the thread, when it has one
+//! - `process.runtime`: `tokio` on a thread of a Tokio runtime, worker or blocking, and
+//!   `none` elsewhere, with the `otlp` feature only
+//! - `process.uptime_ms`: the milliseconds elapsed since logging was installed
+//! - `traceparent`: the current trace context in the W3C Trace Context format, with the
+//!   `sdk` feature only
+//! - `crate` and `module`: the first segment of the event target and the rest of it,
+//!   e.g. `my_app` and `db::pool` for `my_app::db::pool`
+//!
+//! Tokio doesn't tell its workers and blocking threads apart, but names them, so
+//! `thread.name` distinguishes them when the runtime is built with `thread_name_fn`.
+//!
+//! The fields are chosen with `LOG_PROCESS_FIELDS`, `traceparent` with `LOG_TRACEPARENT`
+//! and the target fields with `LOG_TARGET_FIELDS`, as they aren't process metadata. A
+//! target without `::` is a crate on its own, and its events get no `module`. Events already carrying a field of
+//! the same name keep their own value. Each callsite gets a second set of metadata with
+//! the extra fields on first use, kept for the lifetime of the process.
+
+use crate::{
+    errors::LoggingError,
+    exporters::truncate::{OwnedValue, Values},
+};
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    str::FromStr,
+    sync::{OnceLock, PoisonError, RwLock},
+    time::Instant,
+};
+use tracing::{
+    Event, Metadata, Subscriber,
+    callsite::{self, Callsite},
+    field::{FieldSet, Value},
+    metadata::Kind,
+    span::{Attributes, Id, Record},
+    subscriber::Interest,
+};
+use tracing_subscriber::{Layer, layer::Context, registry::LookupSpan};
+
+/// A process or runtime field added to the events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProcessField {
+    Pid,
+    ThreadId,
+    ThreadName,
+    Runtime,
+    Uptime,
+    /// Added outside `LOG_PROCESS_FIELDS`, with `LoggingConfigs::traceparent`.
+    #[cfg(feature = "sdk")]
+    TraceParent,
+    /// Added outside `LOG_PROCESS_FIELDS`, with `LoggingConfigs::target_fields`.
+    Crate,
+    /// Added outside `LOG_PROCESS_FIELDS`, with `LoggingConfigs::target_fields`.
+    Module,
+}
+
+impl ProcessField {
+    /// Returns the name of the field on the events.
+    fn name(self) -> &'static str {
+        match self {
+            ProcessField::Pid => "pid",
+            ProcessField::ThreadId => "thread.id",
+            ProcessField::ThreadName => "thread.name",
+            ProcessField::Runtime => "process.runtime",
+            ProcessField::Uptime => "process.uptime_ms",
+            #[cfg(feature = "sdk")]
+            ProcessField::TraceParent => "traceparent",
+            ProcessField::Crate => "crate",
+            ProcessField::Module => "module",
+        }
+    }
+
+    /// Returns the current value of the field for an event of `target`, if any.
+    fn value(self, target: &str, started: Instant) -> Option<OwnedValue> {
+        Some(match self {
+            ProcessField::Pid => OwnedValue::U64(std::process::id().into()),
+            ProcessField::ThreadId => OwnedValue::U64(thread_id()),
+            ProcessField::ThreadName => OwnedValue::Str(std::thread::current().name()?.to_string()),
+            ProcessField::Runtime => OwnedValue::Str(runtime().to_string()),
+            ProcessField::Uptime => OwnedValue::F64(started.elapsed().as_secs_f64() * 1000.0),
+            #[cfg(feature = "sdk")]
+            ProcessField::TraceParent => OwnedValue::Str(traceparent()?),
+            ProcessField::Crate => OwnedValue::Str(split_target(target).0.to_string()),
+            ProcessField::Module => OwnedValue::Str(split_target(target).1?.to_string()),
+        })
+    }
+}
+
+impl FromStr for ProcessField {
+    type Err = LoggingError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        [
+            ProcessField::Pid,
+            ProcessField::ThreadId,
+            ProcessField::ThreadName,
+            ProcessField::Runtime,
+            ProcessField::Uptime,
+        ]
+        .into_iter()
+        .find(|field| field.name() == name.trim())
+        .ok_or_else(|| {
+            LoggingError::InvalidConfigError(format!(
+                "unknown process field `{name}`, expected `pid`, `thread.id`, `thread.name`, \
+                 `process.runtime` or `process.uptime_ms`"
+            ))
+        })
+    }
+}
+
+/// Parses the configured process fields.
+///
+/// # Errors
+///
+/// Returns `LoggingError::InvalidConfigError` if a field name is unknown.
+pub(crate) fn fields(names: &[String]) -> Result<Vec<ProcessField>, LoggingError> {
+    names.iter().map(|name| name.parse()).collect()
+}
+
+/// Returns the `traceparent` of the current trace, formatted as
+/// `{version}-{trace-id}-{parent-id}-{trace-flags}`, if any.
+#[cfg(feature = "sdk")]
+fn traceparent() -> Option<String> {
+    let span_context = crate::exporters::sampling::current_span_context();
+    span_context.is_valid().then(|| {
+        format!(
+            "00-{}-{}-{:02x}",
+            span_context.trace_id(),
+            span_context.span_id(),
+            span_context.trace_flags().to_u8()
+        )
+    })
+}
+
+/// Splits a target into its crate and its module path within the crate, if any.
+fn split_target(target: &str) -> (&str, Option<&str>) {
+    match target.split_once("::") {
+        Some((krate, module)) => (krate, Some(module).filter(|module| !module.is_empty())),
+        None => (target, None),
+    }
+}
+
+/// Returns the number of the current thread, as shown by its debug representation.
+fn thread_id() -> u64 {
+    let id = format!("{:?}", std::thread::current().id());
+    id.trim_start_matches("ThreadId(")
+        .trim_end_matches(')')
+        .parse()
+        .unwrap_or_default()
+}
+
+/// Returns the runtime the current thread belongs to.
+fn runtime() -> &'static str {
+    #[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+    if tokio::runtime::Handle::try_current().is_ok() {
+        return "tokio";
+    }
+    "none"
+}
+
+/// A layer delivering events to another layer with process fields added.
+#[derive(Debug)]
+pub(crate) struct ProcessFieldsLayer<L> {
+    inner: L,
+    fields: Vec<ProcessField>,
+    started: Instant,
+    /// The metadata with the extra fields, by callsite of the original events.
+    metadata: RwLock<HashMap<callsite::Identifier, &'static Metadata<'static>>>,
+}
+
+impl<L> ProcessFieldsLayer<L> {
+    /// Wraps `inner`, adding the given fields to the events, with the uptime counted
+    /// from now.
+    pub(crate) fn new(inner: L, fields: Vec<ProcessField>) -> Self {
+        ProcessFieldsLayer {
+            inner,
+            fields,
+            started: Instant::now(),
+            metadata: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the metadata of the events of a callsite with the extra fields.
+    fn metadata(&self, original: &'static Metadata<'static>) -> &'static Metadata<'static> {
+        let key = original.callsite();
+        if let Some(metadata) = self
+            .metadata
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+        {
+            return metadata;
+        }
+
+        let mut cache = self
+            .metadata
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        cache.entry(key).or_insert_with(|| {
+            let names: Vec<&'static str> = original
+                .fields()
+                .iter()
+                .map(|field| field.name())
+                .chain(self.fields.iter().map(|field| field.name()))
+                .collect();
+            extended(original, Box::leak(names.into_boxed_slice()))
+        })
+    }
+}
+
+/// Leaks the metadata of an event callsite extended with more field names.
+fn extended(
+    original: &'static Metadata<'static>,
+    names: &'static [&'static str],
+) -> &'static Metadata<'static> {
+    // Metadata must live as long as the events and spans referring to them
+    let callsite: &'static ProcessCallsite = Box::leak(Box::default());
+    callsite.metadata.get_or_init(|| {
+        Metadata::new(
+            original.name(),
+            original.target(),
+            *original.level(),
+            original.file(),
+            original.line(),
+            original.module_path(),
+            FieldSet::new(names, callsite::Identifier(callsite)),
+            Kind::EVENT,
+        )
+    })
+}
+
+/// The dynamically created callsite of the extended events.
+#[derive(Default)]
+struct ProcessCallsite {
+    metadata: OnceLock<Metadata<'static>>,
+}
+
+impl Callsite for ProcessCallsite {
+    fn set_interest(&self, _interest: Interest) {
+        // Never registered, the events are handed to the inner layer directly
+    }
+
+    fn metadata(&self) -> &Metadata<'_> {
+        self.metadata.get().expect("metadata set on creation")
+    }
+}
+
+impl<S, L> Layer<S> for ProcessFieldsLayer<L>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    L: Layer<S>,
+{
+    fn on_register_dispatch(&self, subscriber: &tracing::Dispatch) {
+        self.inner.on_register_dispatch(subscriber);
+    }
+
+    fn on_layer(&mut self, subscriber: &mut S) {
+        self.inner.on_layer(subscriber);
+    }
+
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        self.inner.register_callsite(metadata)
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, ctx: Context<'_, S>) -> bool {
+        self.inner.enabled(metadata, ctx)
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_new_span(attrs, id, ctx);
+    }
+
+    fn max_level_hint(&self) -> Option<tracing::level_filters::LevelFilter> {
+        self.inner.max_level_hint()
+    }
+
+    fn on_record(&self, span: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        self.inner.on_record(span, values, ctx);
+    }
+
+    fn on_follows_from(&self, span: &Id, follows: &Id, ctx: Context<'_, S>) {
+        self.inner.on_follows_from(span, follows, ctx);
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, ctx: Context<'_, S>) -> bool {
+        self.inner.event_enabled(event, ctx)
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let original = event.metadata();
+        let metadata = self.metadata(original);
+
+        let mut values = Values::new(original.fields().len());
+        event.record(&mut values);
+        let mut values = values.into_inner();
+        values.extend(self.fields.iter().map(|field| {
+            // An event field of the same name wins
+            let taken = original.fields().field(field.name()).is_some();
+            if taken {
+                None
+            } else {
+                field.value(original.target(), self.started)
+            }
+        }));
+
+        let values: Vec<Option<&dyn Value>> = values
+            .iter()
+            .map(|value| value.as_ref().map(OwnedValue::as_value))
+            .collect();
+        let value_set = metadata.fields().value_set_all(&values);
+
+        let extended = if event.is_root() {
+            Event::new_child_of(None, metadata, &value_set)
+        } else if let Some(parent) = event.parent() {
+            Event::new_child_of(parent.clone(), metadata, &value_set)
+        } else {
+            Event::new(metadata, &value_set)
+        };
+        self.inner.on_event(&extended, ctx);
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_enter(id, ctx);
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_exit(id, ctx);
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        self.inner.on_close(id, ctx);
+    }
+
+    fn on_id_change(&self, old: &Id, new: &Id, ctx: Context<'_, S>) {
+        self.inner.on_id_change(old, new, ctx);
+    }
+
+    unsafe fn downcast_raw(&self, id: TypeId) -> Option<*const ()> {
+        if id == TypeId::of::<Self>() {
+            return Some(self as *const Self as *const ());
+        }
+        // SAFETY: forwarded as is to the inner layer, which upholds the same contract
+        unsafe { self.inner.downcast_raw(id) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        config::{LoggingConfig, LoggingConfigs, StructuredFormat},
+        test::init_with,
+    };
+
+    #[test]
+    fn every_event_carries_the_process_fields() {
+        let logs = init_with(LoggingConfig {
+            level: Some("info".to_string()),
+            logging: LoggingConfigs {
+                process_fields: vec!["pid".to_string(), "process.uptime_ms".to_string()],
+                ..LoggingConfigs::default()
+            },
+            ..LoggingConfig::default()
+        });
+
+        tracing::info!("first");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        tracing::info!("second");
+
+        let output = logs.output();
+        let uptimes: Vec<f64> = output
+            .lines()
+            .filter(|line| line.contains(&format!("pid={}", std::process::id())))
+            .map(|line| {
+                let uptime = line.split("process.uptime_ms=").nth(1).unwrap();
+                uptime.split_whitespace().next().unwrap().parse().unwrap()
+            })
+            .collect();
+        assert_eq!(uptimes.len(), 2);
+        assert!(uptimes[0] >= 0.0);
+        assert!(uptimes[1] > uptimes[0]);
+    }
+
+    #[cfg(feature = "sdk")]
+    #[test]
+    fn events_in_a_trace_carry_its_traceparent() {
+        use opentelemetry::{
+            Context,
+            trace::{Span, TraceContextExt, Tracer, TracerProvider},
+        };
+        use opentelemetry_sdk::trace::SdkTracerProvider;
+
+        let logs = init_with(LoggingConfig {
+            level: Some("info".to_string()),
+            logging: LoggingConfigs {
+                traceparent: true,
+                structured_format: Some(StructuredFormat::Ndjson),
+                ..LoggingConfigs::default()
+            },
+            ..LoggingConfig::default()
+        });
+
+        // Sampled by the default sampler
+        let tracer = SdkTracerProvider::builder().build().tracer("checkout");
+        let span = tracer.start("charge");
+        let span_context = span.span_context().clone();
+        {
+            let _trace = Context::current_with_span(span).attach();
+            tracing::info!("charging");
+        }
+        tracing::info!("idle");
+
+        let records: Vec<serde_json::Value> = logs
+            .output()
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        let charging = records.iter().find(|r| r["message"] == "charging").unwrap();
+        assert!(span_context.is_sampled());
+        assert_eq!(
+            charging["traceparent"],
+            format!(
+                "00-{}-{}-01",
+                span_context.trace_id(),
+                span_context.span_id()
+            )
+        );
+        let idle = records.iter().find(|r| r["message"] == "idle").unwrap();
+        assert!(idle.get("traceparent").is_none());
+    }
+
+    #[test]
+    fn events_carry_the_crate_and_module_of_their_target() {
+        let logs = init_with(LoggingConfig {
+            level: Some("info".to_string()),
+            logging: LoggingConfigs {
+                target_fields: true,
+                structured_format: Some(StructuredFormat::Ndjson),
+                ..LoggingConfigs::default()
+            },
+            ..LoggingConfig::default()
+        });
+
+        tracing::info!(target: "my_app::db::pool", "pool started");
+        tracing::info!(target: "billing", "invoice sent");
+
+        let records: Vec<serde_json::Value> = logs
+            .output()
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        let pool = records
+            .iter()
+            .find(|r| r["message"] == "pool started")
+            .unwrap();
+        assert_eq!(pool["crate"], "my_app");
+        assert_eq!(pool["module"], "db::pool");
+
+        // A single segment target is only a crate
+        let invoice = records
+            .iter()
+            .find(|r| r["message"] == "invoice sent")
+            .unwrap();
+        assert_eq!(invoice["crate"], "billing");
+        assert!(invoice.get("module").is_none());
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Recent Logs
+//!
+//! This module keeps the most recent events in memory, formatted as single plain text
+//! lines, so a service can serve them from a debug endpoint without scraping files. It's
+//! enabled by setting `LOG_RECENT_LOGS` to the number of lines to retain, and the lines
+//! are read with [`crate::provider::recent_logs`].
+//!
+//! The buffer is bounded: once full, every new line evicts the oldest one. It's fed by a
+//! layer behind the configured filters, so it holds the same events as the console.
+//! When disabled, no layer is installed and nothing is formatted.
+
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    sync::{Arc, Mutex, OnceLock, PoisonError},
+};
+use tracing::Subscriber;
+use tracing_subscriber::{Layer, fmt::MakeWriter, registry::LookupSpan};
+
+/// The buffer of the installed logging system, if enabled.
+static INSTALLED: OnceLock<RecentLogs> = OnceLock::new();
+
+/// A bounded buffer of formatted log lines, shared by its clones.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::recent::RecentLogs;
+///
+/// let recent = RecentLogs::new(2);
+/// recent.push("first".to_string());
+/// recent.push("second".to_string());
+/// recent.push("third".to_string());
+///
+/// // The oldest line was evicted, the others are returned from the oldest
+/// assert_eq!(recent.lines(), vec!["second", "third"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RecentLogs {
+    lines: Arc<Mutex<VecDeque<String>>>,
+    capacity: usize,
+}
+
+impl RecentLogs {
+    /// Creates an empty buffer retaining at most `capacity` lines.
+    pub fn new(capacity: usize) -> Self {
+        RecentLogs {
+            lines: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Appends a line, evicting the oldest one when the buffer is full.
+    pub fn push(&self, line: String) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut lines = self.lines.lock().unwrap_or_else(PoisonError::into_inner);
+        if lines.len() == self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    /// Returns the retained lines, from the oldest to the most recent.
+    pub fn lines(&self) -> Vec<String> {
+        self.lines
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Creates a layer formatting every event it receives into this buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logging::exporters::recent::RecentLogs;
+    /// use tracing_subscriber::layer::SubscriberExt;
+    ///
+    /// let recent = RecentLogs::new(100);
+    /// let subscriber = tracing_subscriber::registry().with(recent.layer());
+    /// tracing::subscriber::with_default(subscriber, || tracing::info!("cache warmed"));
+    ///
+    /// assert!(recent.lines()[0].contains("cache warmed"));
+    /// ```
+    pub fn layer<S>(&self) -> impl Layer<S> + use<S>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        tracing_subscriber::fmt::layer()
+            .with_ansi(false)
+            .with_writer(self.clone())
+    }
+}
+
+impl<'a> MakeWriter<'a> for RecentLogs {
+    type Writer = LineWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        LineWriter {
+            recent: self.clone(),
+            line: Vec::new(),
+        }
+    }
+}
+
+/// The writer of one event, pushing its line to the buffer when dropped.
+#[derive(Debug)]
+pub struct LineWriter {
+    recent: RecentLogs,
+    line: Vec<u8>,
+}
+
+impl Write for LineWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.line.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for LineWriter {
+    fn drop(&mut self) {
+        let line = String::from_utf8_lossy(&self.line);
+        let line = line.trim_end_matches('\n');
+        if !line.is_empty() {
+            self.recent.push(line.to_string());
+        }
+    }
+}
+
+/// Creates the buffer of the logging system being installed, with its layer.
+pub(crate) fn install<S>(capacity: usize) -> Option<impl Layer<S> + use<S>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let recent = RecentLogs::new(capacity);
+    INSTALLED.set(recent.clone()).ok()?;
+    Some(recent.layer())
+}
+
+/// Returns the lines retained by the installed logging system, from the oldest.
+pub(crate) fn lines() -> Vec<String> {
+    INSTALLED.get().map(RecentLogs::lines).unwrap_or_default()
+}
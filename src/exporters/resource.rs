@@ -0,0 +1,190 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Exported Resource
+//!
+//! This module builds the OpenTelemetry resource describing the service, shared by
+//! the exporters that send records to an external system.
+
+use crate::{config::LoggingConfigs, errors::LoggingError, exporters::service};
+use configs::app::AppConfigs;
+use opentelemetry::{Key, KeyValue};
+use opentelemetry_sdk::Resource;
+use std::sync::{PoisonError, RwLock};
+
+/// The `service.instance.id` of the last resource built.
+static INSTANCE_ID: RwLock<Option<String>> = RwLock::new(None);
+
+/// Builds the resource attached to exported records, unless the caller supplied one.
+///
+/// A `custom` resource is used verbatim, e.g. one built by the shared telemetry setup of
+/// the application, and the rest of this description doesn't apply.
+///
+/// It carries the service name, namespace and environment from the `AppConfigs`, the
+/// identifier of the instance as `service.instance.id`, the hostname as `host.name`, the
+/// cloud attributes when built with the `cloud-detect` feature and the
+/// `telemetry.sdk.*` attributes identifying this crate, followed by the configured
+/// additional resource attributes, which override any of them. The environment is set
+/// under the configured `environment_attribute_key`. The service name and namespace are
+/// normalized first, and a blank namespace is left out.
+///
+/// # Errors
+///
+/// Returns `LoggingError::InvalidServiceNameError` if the service name is empty.
+pub(crate) fn build(
+    app_cfgs: &AppConfigs,
+    log_cfgs: &LoggingConfigs,
+    custom: Option<Resource>,
+) -> Result<Resource, LoggingError> {
+    if let Some(resource) = custom {
+        *INSTANCE_ID.write().unwrap_or_else(PoisonError::into_inner) = resource
+            .get(&Key::from_static_str("service.instance.id"))
+            .map(|id| id.to_string());
+        return Ok(resource);
+    }
+
+    let builder = Resource::builder().with_service_name(service::name(app_cfgs, log_cfgs)?);
+
+    let builder = match service::namespace(app_cfgs, log_cfgs) {
+        Some(namespace) => builder.with_attribute(KeyValue::new("service.namespace", namespace)),
+        None => builder,
+    };
+
+    let instance_id = service::instance_id(log_cfgs);
+    *INSTANCE_ID.write().unwrap_or_else(PoisonError::into_inner) = Some(instance_id.clone());
+    let builder = builder.with_attribute(KeyValue::new("service.instance.id", instance_id));
+
+    let builder = match service::hostname(log_cfgs) {
+        Some(hostname) => builder.with_attribute(KeyValue::new("host.name", hostname)),
+        None => builder,
+    };
+
+    #[cfg(all(feature = "cloud-detect", not(target_arch = "wasm32")))]
+    let builder = builder.with_attributes(super::cloud::detect(log_cfgs.cloud_detect_timeout));
+
+    Ok(builder
+        .with_attribute(KeyValue::new(
+            log_cfgs.environment_attribute_key.clone(),
+            app_cfgs.env.to_string(),
+        ))
+        .with_attribute(KeyValue::new("library.language", "rust"))
+        .with_attributes([
+            KeyValue::new("telemetry.sdk.name", env!("CARGO_PKG_NAME")),
+            KeyValue::new("telemetry.sdk.language", "rust"),
+            KeyValue::new("telemetry.sdk.version", env!("CARGO_PKG_VERSION")),
+        ])
+        .with_attributes(
+            log_cfgs
+                .resource_attributes
+                .iter()
+                .map(|(key, value)| KeyValue::new(key.clone(), value.clone())),
+        )
+        .build())
+}
+
+/// Returns the `service.instance.id` of the exported resource, `None` until a resource
+/// has been built.
+pub(crate) fn instance_id() -> Option<String> {
+    INSTANCE_ID
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribute(resource: &Resource, key: &'static str) -> Option<String> {
+        resource
+            .get(&Key::from_static_str(key))
+            .map(|value| value.to_string())
+    }
+
+    fn app_configs() -> AppConfigs {
+        let mut app_cfgs = AppConfigs::new();
+        app_cfgs.name = "orders-api".to_string();
+        app_cfgs
+    }
+
+    #[test]
+    fn environment_is_set_under_the_semantic_convention_key() {
+        let app_cfgs = app_configs();
+        let resource = build(&app_cfgs, &LoggingConfigs::default(), None).unwrap();
+
+        // The environment, never the name of the application
+        assert_eq!(
+            attribute(&resource, "deployment.environment"),
+            Some(app_cfgs.env.to_string())
+        );
+        assert_ne!(
+            attribute(&resource, "deployment.environment").as_deref(),
+            Some("orders-api")
+        );
+        assert_eq!(attribute(&resource, "environment"), None);
+    }
+
+    #[test]
+    fn environment_key_can_be_configured() {
+        let app_cfgs = app_configs();
+        let log_cfgs = LoggingConfigs {
+            environment_attribute_key: "environment".to_string(),
+            ..LoggingConfigs::default()
+        };
+        let resource = build(&app_cfgs, &log_cfgs, None).unwrap();
+
+        assert_eq!(
+            attribute(&resource, "environment"),
+            Some(app_cfgs.env.to_string())
+        );
+        assert_eq!(attribute(&resource, "deployment.environment"), None);
+    }
+
+    #[test]
+    fn telemetry_sdk_identifies_this_crate() {
+        let resource = build(&app_configs(), &LoggingConfigs::default(), None).unwrap();
+
+        assert_eq!(
+            attribute(&resource, "telemetry.sdk.name").as_deref(),
+            Some(env!("CARGO_PKG_NAME"))
+        );
+        assert_eq!(
+            attribute(&resource, "telemetry.sdk.language").as_deref(),
+            Some("rust")
+        );
+        assert_eq!(
+            attribute(&resource, "telemetry.sdk.version").as_deref(),
+            Some(env!("CARGO_PKG_VERSION"))
+        );
+    }
+
+    #[test]
+    fn telemetry_sdk_can_be_overridden() {
+        let log_cfgs = LoggingConfigs {
+            resource_attributes: [
+                (
+                    "telemetry.sdk.name".to_string(),
+                    "ruskit-logging".to_string(),
+                ),
+                ("telemetry.sdk.version".to_string(), "1.2.3".to_string()),
+            ]
+            .into(),
+            ..LoggingConfigs::default()
+        };
+        let resource = build(&app_configs(), &log_cfgs, None).unwrap();
+
+        assert_eq!(
+            attribute(&resource, "telemetry.sdk.name").as_deref(),
+            Some("ruskit-logging")
+        );
+        assert_eq!(
+            attribute(&resource, "telemetry.sdk.version").as_deref(),
+            Some("1.2.3")
+        );
+        assert_eq!(
+            attribute(&resource, "telemetry.sdk.language").as_deref(),
+            Some("rust")
+        );
+    }
+}
@@ -0,0 +1,259 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Sampling
+//!
+//! This module thins out the events reaching the outputs, in two ways.
+//!
+//! ## Trace Sampling
+//!
+//! Exported logs can be kept consistent with the exported traces. When traces are
+//! sampled, the logs of an unsampled trace point to a trace that doesn't exist in the
+//! backend, and make up most of the log volume. With `LOG_FOLLOW_TRACE_SAMPLING=true`,
+//! the export bridge drops the events of unsampled traces and always exports those of
+//! sampled ones.
+//!
+//! The trace is the one of the current OpenTelemetry context, or of the current span
+//! when spans are bridged to OpenTelemetry. Events outside any trace only go through the
+//! usual filters. The console and the other outputs are not affected.
+//!
+//! ## Target Sampling
+//!
+//! A chatty module, e.g. a polling loop, can be sampled on its own with
+//! `LOG_TARGET_SAMPLE_RATES`, a comma-separated list of `target=rate` entries such as
+//! `my_app::poller=0.01`. The events of a target, or of its submodules, are kept at the
+//! given rate, between `0` and `1`, and the other targets are unaffected. The sampling
+//! is deterministic: a rate of `0.01` keeps exactly one event out of every hundred that
+//! pass the other filters. Every output sees the same events.
+
+use crate::errors::LoggingError;
+#[cfg(feature = "sdk")]
+use opentelemetry::{
+    Context,
+    trace::{SpanContext, TraceContextExt},
+};
+use std::{
+    collections::BTreeMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use tracing::{Event, Metadata};
+#[cfg(feature = "sdk")]
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::{self, Filter};
+
+/// A per-layer [`Filter`] rejecting the events of unsampled traces.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::sampling::TraceSamplingFilter;
+/// use opentelemetry::{
+///     Context,
+///     trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState},
+/// };
+/// use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
+/// use opentelemetry_sdk::logs::{InMemoryLogExporter, SdkLoggerProvider};
+/// use tracing_subscriber::{Layer, layer::SubscriberExt};
+///
+/// let exporter = InMemoryLogExporter::default();
+/// let provider = SdkLoggerProvider::builder()
+///     .with_simple_exporter(exporter.clone())
+///     .build();
+/// let subscriber = tracing_subscriber::registry()
+///     .with(OpenTelemetryTracingBridge::new(&provider).with_filter(TraceSamplingFilter));
+///
+/// let trace = |flags| {
+///     Context::new().with_remote_span_context(SpanContext::new(
+///         TraceId::from_u128(1),
+///         SpanId::from_u64(1),
+///         flags,
+///         true,
+///         TraceState::default(),
+///     ))
+/// };
+///
+/// tracing::subscriber::with_default(subscriber, || {
+///     tracing::info!("outside any trace");
+///     {
+///         let _sampled = trace(TraceFlags::SAMPLED).attach();
+///         tracing::info!("in a sampled trace");
+///     }
+///     {
+///         let _unsampled = trace(TraceFlags::default()).attach();
+///         tracing::info!("in an unsampled trace");
+///     }
+/// });
+///
+/// let logs = exporter.get_emitted_logs().unwrap();
+/// let bodies: Vec<String> = logs
+///     .iter()
+///     .map(|log| format!("{:?}", log.record.body().unwrap()))
+///     .collect();
+/// assert_eq!(bodies.len(), 2);
+/// assert!(bodies[0].contains("outside any trace"));
+/// assert!(bodies[1].contains("in a sampled trace"));
+/// ```
+#[cfg(feature = "sdk")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TraceSamplingFilter;
+
+#[cfg(feature = "sdk")]
+impl<S> Filter<S> for TraceSamplingFilter {
+    fn enabled(&self, meta: &Metadata<'_>, _cx: &layer::Context<'_, S>) -> bool {
+        // Spans are never exported by the bridge, only events follow the decision
+        !meta.is_event() || in_sampled_trace()
+    }
+}
+
+/// Returns `false` when the current trace is known and unsampled.
+#[cfg(feature = "sdk")]
+fn in_sampled_trace() -> bool {
+    let span_context = current_span_context();
+    !span_context.is_valid() || span_context.is_sampled()
+}
+
+/// Returns the span context of the current span when spans are bridged to
+/// OpenTelemetry, or of the current OpenTelemetry context otherwise.
+///
+/// The span context is invalid outside any trace.
+#[cfg(feature = "sdk")]
+pub(crate) fn current_span_context() -> SpanContext {
+    let span_context = tracing::Span::current().context();
+    let span_context = span_context.span().span_context().clone();
+    if span_context.is_valid() {
+        span_context
+    } else {
+        Context::map_current(|cx| cx.span().span_context().clone())
+    }
+}
+
+/// A per-layer [`Filter`] keeping a fraction of the events of some targets.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::sampling::TargetSamplingFilter;
+/// use std::collections::BTreeMap;
+/// use tracing_subscriber::{Layer, layer::SubscriberExt};
+///
+/// // Keeps one event out of a hundred from the poller
+/// let rates = BTreeMap::from([("my_app::poller".to_string(), "0.01".to_string())]);
+/// let layer = tracing_subscriber::fmt::layer()
+///     .with_filter(TargetSamplingFilter::new(&rates).unwrap());
+/// let subscriber = tracing_subscriber::registry().with(layer);
+/// ```
+#[derive(Debug, Default)]
+pub struct TargetSamplingFilter {
+    /// The sampled targets, the most specific first.
+    targets: Vec<SampledTarget>,
+}
+
+/// A sampled target, with the number of its events seen so far.
+#[derive(Debug)]
+struct SampledTarget {
+    target: String,
+    rate: f64,
+    seen: AtomicU64,
+}
+
+impl TargetSamplingFilter {
+    /// Creates a filter sampling the events of each target at its rate.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LoggingError::InvalidConfigError` if a rate isn't a number between `0`
+    /// and `1`.
+    pub fn new(rates: &BTreeMap<String, String>) -> Result<Self, LoggingError> {
+        let mut targets = rates
+            .iter()
+            .map(|(target, rate)| {
+                let Some(rate) = rate
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|rate| (0.0..=1.0).contains(rate))
+                else {
+                    return Err(LoggingError::InvalidConfigError(format!(
+                        "sample rate `{rate}` of `{target}` is not between 0 and 1"
+                    )));
+                };
+                Ok(SampledTarget {
+                    target: target.clone(),
+                    rate,
+                    seen: AtomicU64::new(0),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        targets.sort_by_key(|sampled| std::cmp::Reverse(sampled.target.len()));
+        Ok(TargetSamplingFilter { targets })
+    }
+
+    /// Returns `true` when no target is sampled, meaning every event is kept.
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Returns the most specific sampled target covering the given one.
+    fn sampled(&self, target: &str) -> Option<&SampledTarget> {
+        self.targets.iter().find(|sampled| {
+            target
+                .strip_prefix(sampled.target.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        })
+    }
+}
+
+impl SampledTarget {
+    /// Counts an event, returning whether it's kept.
+    fn keep(&self) -> bool {
+        // Kept whenever the running total of the rate crosses an integer
+        let seen = self.seen.fetch_add(1, Ordering::Relaxed) as f64;
+        (seen * self.rate).floor() != ((seen + 1.0) * self.rate).floor()
+    }
+}
+
+impl<S> Filter<S> for TargetSamplingFilter {
+    fn enabled(&self, _meta: &Metadata<'_>, _cx: &layer::Context<'_, S>) -> bool {
+        true
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, _cx: &layer::Context<'_, S>) -> bool {
+        self.sampled(event.metadata().target())
+            .is_none_or(SampledTarget::keep)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::Capture;
+    use tracing_subscriber::{Layer, layer::SubscriberExt};
+
+    #[test]
+    fn keeps_the_rate_of_the_sampled_targets_only() {
+        let rates = BTreeMap::from([("my_app::poller".to_string(), "0.01".to_string())]);
+        let capture = Capture::default();
+        let layer = tracing_subscriber::fmt::layer()
+            .with_ansi(false)
+            .with_writer(capture.clone())
+            .with_filter(TargetSamplingFilter::new(&rates).unwrap());
+
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), || {
+            for _ in 0..1000 {
+                tracing::info!(target: "my_app::poller::http", "polled");
+                tracing::info!(target: "my_app::api", "served");
+            }
+        });
+
+        let output = capture.output();
+        assert_eq!(output.matches("polled").count(), 10);
+        assert_eq!(output.matches("served").count(), 1000);
+    }
+
+    #[test]
+    fn rejects_rates_outside_of_zero_to_one() {
+        let rates = BTreeMap::from([("my_app::poller".to_string(), "5".to_string())]);
+        assert!(TargetSamplingFilter::new(&rates).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Service Identity
+//!
+//! This module validates the service name and namespace taken from the `AppConfigs`
+//! before they are used as resource attributes and as the name of JSON records. A blank
+//! `service.name` breaks the service discovery of many backends, so it's rejected
+//! instead of being exported. It also resolves the hostname the records are attributed
+//! to, and the identifier of the instance of the service.
+
+use crate::{config::LoggingConfigs, errors::LoggingError};
+use configs::app::AppConfigs;
+
+/// Returns the normalized service name of the application.
+///
+/// The name is trimmed and, when `LOG_SERVICE_NAME_KEBAB_CASE` is enabled, converted
+/// to lower kebab case (`My Service_v2` becomes `my-service-v2`).
+///
+/// # Errors
+///
+/// Returns `LoggingError::InvalidServiceNameError` if the name is empty or only made of
+/// whitespace, or of separators once converted to kebab case.
+pub(crate) fn name(
+    app_cfgs: &AppConfigs,
+    log_cfgs: &LoggingConfigs,
+) -> Result<String, LoggingError> {
+    let name = normalize(&app_cfgs.name, log_cfgs.service_name_kebab_case);
+    if name.is_empty() {
+        return Err(LoggingError::InvalidServiceNameError(format!(
+            "`{}` is empty once normalized",
+            app_cfgs.name
+        )));
+    }
+    Ok(name)
+}
+
+/// Returns the normalized service namespace, or `None` when it's blank.
+#[cfg(any(feature = "stdout", feature = "otlp"))]
+pub(crate) fn namespace(app_cfgs: &AppConfigs, log_cfgs: &LoggingConfigs) -> Option<String> {
+    let namespace = normalize(
+        &app_cfgs.namespace.to_string(),
+        log_cfgs.service_name_kebab_case,
+    );
+    (!namespace.is_empty()).then_some(namespace)
+}
+
+/// Returns the identifier of this instance of the service.
+///
+/// The configured identifier wins, then the value of the variable named by
+/// `LOG_SERVICE_INSTANCE_ID_ENV`, and finally a random UUID generated once per process,
+/// so the identifier stays the same for every record.
+#[cfg(any(feature = "stdout", feature = "otlp"))]
+pub(crate) fn instance_id(log_cfgs: &LoggingConfigs) -> String {
+    static GENERATED: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+    let non_blank = |id: String| {
+        let id = id.trim();
+        (!id.is_empty()).then(|| id.to_string())
+    };
+    log_cfgs
+        .service_instance_id
+        .clone()
+        .and_then(non_blank)
+        .or_else(|| {
+            log_cfgs
+                .service_instance_id_env
+                .as_deref()
+                .and_then(|key| std::env::var(key).ok())
+                .and_then(non_blank)
+        })
+        .unwrap_or_else(|| GENERATED.get_or_init(random_uuid).clone())
+}
+
+/// Generates a random version 4 UUID, without a dependency on a random number generator.
+#[cfg(any(feature = "stdout", feature = "otlp"))]
+fn random_uuid() -> String {
+    use std::{
+        hash::{BuildHasher, Hasher},
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    // Every `RandomState` is seeded with its own random keys
+    let random = |seed: u64| {
+        let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+        hasher.write_u64(seed);
+        hasher.finish()
+    };
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+    let high = (random(nanos) & !0xf000) | 0x4000;
+    let low = (random(u64::from(std::process::id())) & !(0xc << 60)) | (0x8 << 60);
+
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        high >> 32,
+        (high >> 16) & 0xffff,
+        high & 0xffff,
+        low >> 48,
+        low & 0xffff_ffff_ffff,
+    )
+}
+
+/// Returns the configured hostname, or the one of the machine.
+///
+/// There's no hostname to detect on `wasm32`.
+#[cfg(any(feature = "stdout", feature = "otlp", feature = "gelf"))]
+pub(crate) fn hostname(log_cfgs: &LoggingConfigs) -> Option<String> {
+    let hostname = configured_hostname(log_cfgs);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let hostname =
+        hostname.or_else(|| Some(gethostname::gethostname().to_string_lossy().into_owned()));
+
+    hostname
+}
+
+/// Returns the hostname set with `LOG_HOSTNAME`, ignoring a blank one.
+pub(crate) fn configured_hostname(log_cfgs: &LoggingConfigs) -> Option<String> {
+    log_cfgs
+        .hostname
+        .as_deref()
+        .map(str::trim)
+        .filter(|hostname| !hostname.is_empty())
+        .map(String::from)
+}
+
+fn normalize(value: &str, kebab_case: bool) -> String {
+    let value = value.trim();
+    if !kebab_case {
+        return value.to_string();
+    }
+
+    value
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service_name(name: &str, kebab_case: bool) -> Result<String, LoggingError> {
+        let mut app_cfgs = AppConfigs::new();
+        app_cfgs.name = name.to_string();
+        let log_cfgs = LoggingConfigs {
+            service_name_kebab_case: kebab_case,
+            ..LoggingConfigs::default()
+        };
+        super::name(&app_cfgs, &log_cfgs)
+    }
+
+    #[test]
+    fn empty_names_are_rejected() {
+        for name in ["", "   ", "\t\n"] {
+            assert!(matches!(
+                service_name(name, false),
+                Err(LoggingError::InvalidServiceNameError(_))
+            ));
+        }
+        // Nothing is left of separators in kebab case
+        assert!(service_name("_ -", true).is_err());
+    }
+
+    #[test]
+    fn valid_names_are_trimmed() {
+        assert_eq!(service_name(" orders-api ", false).unwrap(), "orders-api");
+        assert_eq!(
+            service_name("My Service_v2", false).unwrap(),
+            "My Service_v2"
+        );
+    }
+
+    #[test]
+    fn names_can_be_converted_to_kebab_case() {
+        assert_eq!(
+            service_name(" My Service_v2 ", true).unwrap(),
+            "my-service-v2"
+        );
+        assert_eq!(service_name("orders-api", true).unwrap(), "orders-api");
+    }
+}
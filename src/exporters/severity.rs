@@ -0,0 +1,195 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # OTLP Severity Mapping
+//!
+//! This module allows overriding the severity numbers assigned to exported log records.
+//! The OpenTelemetry tracing bridge maps each tracing level to a fixed OTLP severity
+//! (`TRACE` to `Trace`, `DEBUG` to `Debug`, and so on). Teams whose backend uses a
+//! different severity taxonomy can remap them, for example collapsing `TRACE` into
+//! `DEBUG` or bumping `WARN` to `Warn3`.
+
+use crate::errors::LoggingError;
+use opentelemetry::{
+    InstrumentationScope,
+    logs::{LogRecord, Severity},
+};
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    logs::{LogProcessor, SdkLogRecord},
+};
+use std::time::Duration;
+use tracing::Level;
+
+/// Every OTLP severity, ordered by severity number (1 to 24).
+const SEVERITIES: [Severity; 24] = [
+    Severity::Trace,
+    Severity::Trace2,
+    Severity::Trace3,
+    Severity::Trace4,
+    Severity::Debug,
+    Severity::Debug2,
+    Severity::Debug3,
+    Severity::Debug4,
+    Severity::Info,
+    Severity::Info2,
+    Severity::Info3,
+    Severity::Info4,
+    Severity::Warn,
+    Severity::Warn2,
+    Severity::Warn3,
+    Severity::Warn4,
+    Severity::Error,
+    Severity::Error2,
+    Severity::Error3,
+    Severity::Error4,
+    Severity::Fatal,
+    Severity::Fatal2,
+    Severity::Fatal3,
+    Severity::Fatal4,
+];
+
+/// The OTLP severity assigned to each tracing level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeverityMapping {
+    trace: Severity,
+    debug: Severity,
+    info: Severity,
+    warn: Severity,
+    error: Severity,
+}
+
+impl SeverityMapping {
+    /// Builds a mapping from `level=severity` entries, starting from the standard mapping.
+    ///
+    /// The severity is either an OTLP severity name (`debug`, `warn3`, `fatal`, ...) or
+    /// a severity number between 1 and 24.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LoggingError::InvalidConfigError` if an entry is malformed or names an
+    /// unknown level or severity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logging::exporters::severity::SeverityMapping;
+    /// use opentelemetry::logs::Severity;
+    ///
+    /// let mapping =
+    ///     SeverityMapping::from_entries(&["trace=debug".to_string(), "warn=15".to_string()])
+    ///         .unwrap();
+    /// assert_eq!(mapping.severity(&tracing::Level::TRACE), Severity::Debug);
+    /// assert_eq!(mapping.severity(&tracing::Level::WARN), Severity::Warn3);
+    /// assert_eq!(mapping.severity(&tracing::Level::INFO), Severity::Info);
+    /// ```
+    pub fn from_entries(entries: &[String]) -> Result<Self, LoggingError> {
+        entries
+            .iter()
+            .try_fold(Self::default(), |mut mapping, entry| {
+                let invalid = || {
+                    LoggingError::InvalidConfigError(format!(
+                        "invalid severity mapping `{entry}`, expected level=severity"
+                    ))
+                };
+
+                let (level, severity) = entry.split_once('=').ok_or_else(invalid)?;
+                let level: Level = level.trim().parse().map_err(|_| invalid())?;
+                let severity = parse_severity(severity.trim()).ok_or_else(invalid)?;
+
+                *mapping.slot(&level) = severity;
+                Ok(mapping)
+            })
+    }
+
+    /// Returns the OTLP severity assigned to the given tracing level.
+    pub fn severity(&self, level: &Level) -> Severity {
+        match *level {
+            Level::TRACE => self.trace,
+            Level::DEBUG => self.debug,
+            Level::INFO => self.info,
+            Level::WARN => self.warn,
+            Level::ERROR => self.error,
+        }
+    }
+
+    /// Returns `true` if this is the standard mapping applied by the tracing bridge.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn slot(&mut self, level: &Level) -> &mut Severity {
+        match *level {
+            Level::TRACE => &mut self.trace,
+            Level::DEBUG => &mut self.debug,
+            Level::INFO => &mut self.info,
+            Level::WARN => &mut self.warn,
+            Level::ERROR => &mut self.error,
+        }
+    }
+}
+
+impl Default for SeverityMapping {
+    fn default() -> Self {
+        Self {
+            trace: Severity::Trace,
+            debug: Severity::Debug,
+            info: Severity::Info,
+            warn: Severity::Warn,
+            error: Severity::Error,
+        }
+    }
+}
+
+fn parse_severity(severity: &str) -> Option<Severity> {
+    match severity.parse::<usize>() {
+        Ok(number) => SEVERITIES.get(number.checked_sub(1)?).copied(),
+        Err(_) => SEVERITIES
+            .iter()
+            .find(|candidate| candidate.name().eq_ignore_ascii_case(severity))
+            .copied(),
+    }
+}
+
+/// A [`LogProcessor`] that rewrites severity numbers before delegating to another processor.
+///
+/// The original tracing level is recovered from the severity text set by the bridge,
+/// which is kept unchanged.
+#[derive(Debug)]
+pub struct SeverityProcessor<P> {
+    inner: P,
+    mapping: SeverityMapping,
+}
+
+impl<P> SeverityProcessor<P> {
+    /// Wraps `inner`, applying `mapping` to every record it receives.
+    pub fn new(inner: P, mapping: SeverityMapping) -> Self {
+        SeverityProcessor { inner, mapping }
+    }
+}
+
+impl<P: LogProcessor> LogProcessor for SeverityProcessor<P> {
+    fn emit(&self, data: &mut SdkLogRecord, instrumentation: &InstrumentationScope) {
+        if let Some(level) = data
+            .severity_text()
+            .and_then(|text| text.parse::<Level>().ok())
+        {
+            data.set_severity_number(self.mapping.severity(&level));
+        }
+        self.inner.emit(data, instrumentation);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Event Sizes
+//!
+//! This module measures the size of the logged events and keeps their distribution in a
+//! histogram, giving visibility into oversized logs before they break a collector, e.g.
+//! to alert on them or to choose the truncation limits. It's enabled with
+//! `LOG_EVENT_SIZE_HISTOGRAM=true`, and the buckets are read with
+//! [`crate::provider::size_histogram`].
+//!
+//! The size of an event is the number of bytes of its message and of the `name=value`
+//! pairs of its other fields, separated by a space, like a compact console line without
+//! its timestamp, level and target. Values are measured while they are formatted,
+//! without being buffered.
+//!
+//! The histogram is fed by a layer behind the configured filters, but in front of the
+//! value truncation, so events are measured as they were logged.
+
+use std::{
+    fmt::{self, Write},
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tracing::{
+    Event, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::{Layer, layer::Context};
+
+/// The histogram of the installed logging system, if enabled.
+static INSTALLED: OnceLock<SizeHistogram> = OnceLock::new();
+
+/// The number of events whose size falls in a bucket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeBucket {
+    /// Largest size of the bucket, in bytes, or `None` for the events larger than every
+    /// configured bound.
+    pub upper_bound: Option<usize>,
+    /// Number of events of the bucket measured so far.
+    pub count: u64,
+}
+
+/// A histogram of event sizes, shared by its clones.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::size::{SizeBucket, SizeHistogram};
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let histogram = SizeHistogram::new(&[16, 64]);
+/// let subscriber = tracing_subscriber::registry().with(histogram.layer());
+///
+/// tracing::subscriber::with_default(subscriber, || {
+///     // 10 bytes
+///     tracing::info!("0123456789");
+///     // 10 bytes for the message, a space and 10 bytes for `user=alice`
+///     tracing::info!(user = "alice", "0123456789");
+///     // 100 bytes
+///     tracing::warn!("{}", "x".repeat(100));
+/// });
+///
+/// assert_eq!(
+///     histogram.snapshot(),
+///     vec![
+///         SizeBucket { upper_bound: Some(16), count: 1 },
+///         SizeBucket { upper_bound: Some(64), count: 1 },
+///         SizeBucket { upper_bound: None, count: 1 },
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct SizeHistogram {
+    bounds: Arc<[usize]>,
+    counts: Arc<[AtomicU64]>,
+}
+
+impl SizeHistogram {
+    /// Creates an empty histogram with the given bucket bounds, in bytes.
+    ///
+    /// The bounds are sorted and deduplicated, and a last bucket counts the events
+    /// larger than every bound.
+    pub fn new(bounds: &[usize]) -> Self {
+        let mut bounds = bounds.to_vec();
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        Self {
+            counts: (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect(),
+            bounds: bounds.into(),
+        }
+    }
+
+    /// Counts an event of the given size.
+    fn record(&self, size: usize) {
+        let bucket = self.bounds.partition_point(|bound| *bound < size);
+        self.counts[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the buckets, from the smallest bound to the unbounded one.
+    pub fn snapshot(&self) -> Vec<SizeBucket> {
+        self.bounds
+            .iter()
+            .map(|bound| Some(*bound))
+            .chain([None])
+            .zip(self.counts.iter())
+            .map(|(upper_bound, count)| SizeBucket {
+                upper_bound,
+                count: count.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    /// Creates a layer measuring every event it receives.
+    pub fn layer(&self) -> SizeLayer {
+        SizeLayer(self.clone())
+    }
+}
+
+/// The layer feeding [`SizeHistogram`].
+#[derive(Debug, Clone)]
+pub struct SizeLayer(SizeHistogram);
+
+impl<S: Subscriber> Layer<S> for SizeLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut size = EventSize::default();
+        event.record(&mut size);
+        self.0.record(size.bytes);
+    }
+}
+
+/// Measures the fields of an event.
+#[derive(Default)]
+struct EventSize {
+    bytes: usize,
+    fields: usize,
+}
+
+impl EventSize {
+    /// Adds a field whose value has already been measured.
+    fn add_field(&mut self, field: &Field) {
+        if self.fields > 0 {
+            self.bytes += 1;
+        }
+        if field.name() != "message" {
+            self.bytes += field.name().len() + 1;
+        }
+        self.fields += 1;
+    }
+}
+
+impl Write for EventSize {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.bytes += s.len();
+        Ok(())
+    }
+}
+
+impl Visit for EventSize {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.add_field(field);
+        self.bytes += value.len();
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.add_field(field);
+        let _ = write!(self, "{value:?}");
+    }
+}
+
+/// Creates the histogram of the logging system being installed, with its layer.
+pub(crate) fn install(bounds: &[usize]) -> Option<SizeLayer> {
+    let histogram = SizeHistogram::new(bounds);
+    INSTALLED.set(histogram.clone()).ok()?;
+    Some(histogram.layer())
+}
+
+/// Returns the buckets of the installed logging system, empty when disabled.
+pub(crate) fn snapshot() -> Vec<SizeBucket> {
+    INSTALLED
+        .get()
+        .map(SizeHistogram::snapshot)
+        .unwrap_or_default()
+}
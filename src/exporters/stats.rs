@@ -0,0 +1,270 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Export Statistics
+//!
+//! This module keeps process-wide counters describing the health of the OTLP export
+//! pipeline: records exported, records dropped because the batch queue was full, failed
+//! exports, and records rejected by the collector. They are read with
+//! [`crate::provider::export_stats`], e.g. to report logging health from a metrics or
+//! health endpoint.
+//!
+//! The counters are maintained by an exporter wrapping the OTLP exporter. The records
+//! queued or being exported, and those dropped because the queue was full, are tracked
+//! by the [`super::backpressure::BoundedQueue`] of each OTLP exporter, so that every drop
+//! is accounted for.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+static COUNTERS: Counters = Counters {
+    exported: AtomicU64::new(0),
+    export_errors: AtomicU64::new(0),
+    rejected: AtomicU64::new(0),
+    failed: AtomicU64::new(0),
+};
+
+struct Counters {
+    exported: AtomicU64,
+    export_errors: AtomicU64,
+    rejected: AtomicU64,
+    /// Records lost in failed exports.
+    failed: AtomicU64,
+}
+
+/// Serializes the tests asserting the counters, which every OTLP export updates.
+#[cfg(all(test, feature = "otlp", not(target_arch = "wasm32")))]
+pub(crate) static TEST_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// The batch queues of the installed OTLP exporters.
+#[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+static QUEUES: std::sync::Mutex<Vec<super::backpressure::BoundedQueue>> =
+    std::sync::Mutex::new(Vec::new());
+
+/// Adds the queue of an OTLP exporter to the counters.
+#[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+pub(crate) fn register(queue: &super::backpressure::BoundedQueue) {
+    QUEUES
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .push(queue.clone());
+}
+
+/// Returns the sum of `count` over the queues of the installed OTLP exporters.
+#[cfg_attr(
+    not(all(feature = "otlp", not(target_arch = "wasm32"))),
+    allow(unused_variables)
+)]
+fn queues<T: std::iter::Sum<T>>(count: impl Fn(&super::backpressure::BoundedQueue) -> T) -> T {
+    #[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+    {
+        QUEUES
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .map(count)
+            .sum()
+    }
+    #[cfg(not(all(feature = "otlp", not(target_arch = "wasm32"))))]
+    {
+        std::iter::empty().sum()
+    }
+}
+
+/// A snapshot of the export counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LogExportStats {
+    /// Records successfully exported.
+    pub exported: u64,
+
+    /// Records dropped because the batch queue was full.
+    pub dropped: u64,
+
+    /// Exports that failed, each losing a whole batch of records.
+    pub export_errors: u64,
+
+    /// Records the collector rejected while accepting the rest of their batch.
+    ///
+    /// They're also counted in `exported`, since their export succeeded.
+    pub rejected: u64,
+}
+
+/// Returns the current value of the export counters.
+pub(crate) fn snapshot() -> LogExportStats {
+    LogExportStats {
+        exported: COUNTERS.exported.load(Ordering::Relaxed),
+        dropped: queues(|queue| queue.dropped()),
+        export_errors: COUNTERS.export_errors.load(Ordering::Relaxed),
+        rejected: COUNTERS.rejected.load(Ordering::Relaxed),
+    }
+}
+
+/// Returns the number of records lost so far, dropped or part of a failed export.
+pub(crate) fn lost() -> u64 {
+    queues(|queue| queue.dropped()) + COUNTERS.failed.load(Ordering::Relaxed)
+}
+
+/// Records that the collector rejected `records` records in a partial success.
+#[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+pub(crate) fn record_rejected(records: u64) {
+    COUNTERS.rejected.fetch_add(records, Ordering::Relaxed);
+}
+
+/// Returns the number of records queued or being exported.
+pub(crate) fn pending() -> usize {
+    queues(|queue| queue.len())
+}
+
+/// The outcome of shutting the logging system down.
+///
+/// The record counts come from the export counters, so like them they only move with
+/// the OTLP exporter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Records exported while shutting down.
+    pub flushed: usize,
+
+    /// Records lost while shutting down, including those still queued when it ended.
+    pub dropped: usize,
+
+    /// Time spent shutting down.
+    pub duration: Duration,
+
+    /// Whether the shutdown gave up before the processors were done.
+    pub timed_out: bool,
+}
+
+#[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+pub(crate) use counting::CountingExporter;
+
+#[cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+mod counting {
+    use super::COUNTERS;
+    use opentelemetry_sdk::{
+        Resource,
+        error::OTelSdkResult,
+        logs::{LogBatch, LogExporter},
+    };
+    use std::{sync::atomic::Ordering, time::Duration};
+
+    /// A [`LogExporter`] counting exported records and failed exports.
+    #[derive(Debug)]
+    pub(crate) struct CountingExporter<E> {
+        inner: E,
+    }
+
+    impl<E> CountingExporter<E> {
+        pub(crate) fn new(inner: E) -> Self {
+            CountingExporter { inner }
+        }
+    }
+
+    impl<E: LogExporter> LogExporter for CountingExporter<E> {
+        async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
+            let records = batch.iter().count();
+            let result = self.inner.export(batch).await;
+
+            match result {
+                Ok(()) => COUNTERS
+                    .exported
+                    .fetch_add(records as u64, Ordering::Relaxed),
+                Err(_) => {
+                    COUNTERS.failed.fetch_add(records as u64, Ordering::Relaxed);
+                    COUNTERS.export_errors.fetch_add(1, Ordering::Relaxed)
+                }
+            };
+            result
+        }
+
+        fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+            self.inner.shutdown_with_timeout(timeout)
+        }
+
+        fn set_resource(&mut self, resource: &Resource) {
+            self.inner.set_resource(resource);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "otlp", not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::{config::Backpressure, exporters::backpressure::BoundedQueue};
+    use opentelemetry::logs::{AnyValue, LogRecord, Logger, LoggerProvider};
+    use opentelemetry_sdk::{
+        error::{OTelSdkError, OTelSdkResult},
+        logs::{BatchConfigBuilder, BatchLogProcessor, LogBatch, LogExporter, SdkLoggerProvider},
+    };
+    use std::sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    };
+
+    /// An exporter failing the batches of its choice.
+    #[derive(Debug, Clone, Default)]
+    struct Stub {
+        failing: Arc<AtomicBool>,
+    }
+
+    impl LogExporter for Stub {
+        async fn export(&self, _batch: LogBatch<'_>) -> OTelSdkResult {
+            if self.failing.load(Ordering::SeqCst) {
+                Err(OTelSdkError::InternalFailure("collector down".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn emit(provider: &SdkLoggerProvider, body: &str) {
+        let logger = provider.logger("orders");
+        let mut record = logger.create_log_record();
+        record.set_body(AnyValue::String(body.to_string().into()));
+        logger.emit(record);
+    }
+
+    #[test]
+    fn counts_exported_dropped_and_failed_records() {
+        let _lock = TEST_LOCK.blocking_lock();
+        let stub = Stub::default();
+        // Built as by the OTLP exporter, without the periodic export
+        let queue = BoundedQueue::new(2, Backpressure::DropNew);
+        register(&queue);
+        let batch = BatchLogProcessor::builder(queue.exporter(CountingExporter::new(stub.clone())))
+            .with_batch_config(
+                BatchConfigBuilder::default()
+                    .with_scheduled_delay(Duration::from_secs(3600))
+                    .with_max_queue_size(queue.batch_queue_size())
+                    .build(),
+            )
+            .build();
+        let provider = SdkLoggerProvider::builder()
+            .with_log_processor(queue.processor(batch))
+            .build();
+        let (before, lost_before) = (snapshot(), lost());
+
+        // The third record finds the queue full
+        for body in ["1", "2", "3"] {
+            emit(&provider, body);
+        }
+        provider.force_flush().unwrap();
+        stub.failing.store(true, Ordering::SeqCst);
+        for body in ["4", "5"] {
+            emit(&provider, body);
+        }
+        let _ = provider.force_flush();
+
+        let after = snapshot();
+        assert_eq!(after.exported - before.exported, 2);
+        assert_eq!(after.dropped - before.dropped, 1);
+        assert_eq!(after.export_errors - before.export_errors, 1);
+        assert_eq!(after.rejected - before.rejected, 0);
+        // The dropped record and the failed batch
+        assert_eq!(lost() - lost_before, 3);
+        assert!(queue.is_empty());
+        provider.shutdown().unwrap();
+    }
+}
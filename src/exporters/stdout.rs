@@ -8,23 +8,25 @@
 //! It configures a logging system that writes logs either in a pretty format
 //! (for local development) or JSON/Bunyan format (for production environments).
 
-use crate::{errors::LoggingError, exporters::filters::target_filters};
+use crate::{
+    config::LoggingConfigs,
+    correlation::CorrelationProcessor,
+    errors::LoggingError,
+    exporters::{
+        baggage::BaggageProcessor,
+        error_flush::ErrorFlushProcessor,
+        flush::IntervalFlushProcessor,
+        layers::{ConsoleOptions, console_layers, export_layer, install_subscriber},
+        resource,
+    },
+};
 use configs::app::AppConfigs;
-use opentelemetry::KeyValue;
 use opentelemetry_appender_tracing::layer;
-use opentelemetry_sdk::{Resource, logs::SdkLoggerProvider};
-use opentelemetry_stdout::LogExporter;
-use tracing::error;
-use tracing_bunyan_formatter::BunyanFormattingLayer;
-use tracing_log::LogTracer;
-use tracing_subscriber::{
-    fmt::{
-        Layer,
-        format::{Format, Pretty},
-    },
-    layer::SubscriberExt,
-    prelude::*,
+use opentelemetry_sdk::{
+    Resource,
+    logs::{LogProcessor, SdkLoggerProvider, SimpleLogProcessor},
 };
+use opentelemetry_stdout::LogExporter;
 
 /// Installs and configures the stdout log exporter.
 ///
@@ -34,21 +36,28 @@ use tracing_subscriber::{
 /// - In non-local environments, logs are formatted as JSON in Bunyan format
 ///
 /// It also configures OpenTelemetry integration and appropriate filtering
-/// based on the application configuration.
+/// based on the application configuration. Records are exported as they're emitted;
+/// with `LOG_STDOUT_FLUSH_INTERVAL` set, the exporter is also flushed periodically from
+/// a background thread, which stops when the provider is shut down.
 ///
 /// # Returns
 ///
 /// * `Result<SdkLoggerProvider, LoggingError>` - On success, returns the configured
-///   OpenTelemetry logger provider. On failure, returns a `LoggingError`.
+///   OpenTelemetry logger provider, or a clone of the one installed by a previous call.
+///   On failure, returns a `LoggingError`.
 ///
 /// # Errors
 ///
-/// Returns `LoggingError::InternalError` if there's a problem setting up the
-/// tracing subscriber.
+/// Returns `LoggingError::SubscriberInstallError` if the tracing subscriber can't be
+/// set, e.g. because another global subscriber is already set.
+///
+/// Returns `LoggingError::AlreadyInitializedError` if logging is already installed, by
+/// [`crate::provider::install`] or the exporter of another module. Calling this
+/// function again hands back the provider it installed.
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use logging::exporters::stdout;
 ///
 /// fn main() {
@@ -58,76 +67,53 @@ use tracing_subscriber::{
 /// }
 /// ```
 pub fn install() -> Result<SdkLoggerProvider, LoggingError> {
-    let app_cfgs = AppConfigs::new();
-
-    match LogTracer::init() {
-        Err(err) => {
-            error!(
-                error = ?err,
-                "failure to initialize logger, probably the log was already initialized"
-            );
-            Ok(())
-        }
-        _ => Ok(()),
-    }?;
+    crate::provider::install_exclusive_provider("stdout", || {
+        install_with(
+            &AppConfigs::new(),
+            &LoggingConfigs::new(),
+            ConsoleOptions::default(),
+            None,
+        )
+    })
+}
 
-    let exporter = LogExporter::default();
+/// Installs the stdout log exporter with the given settings, and the `custom` resource
+/// if any.
+pub(crate) fn install_with(
+    app_cfgs: &AppConfigs,
+    log_cfgs: &LoggingConfigs,
+    console: ConsoleOptions,
+    custom: Option<Resource>,
+) -> Result<SdkLoggerProvider, LoggingError> {
     let provider: SdkLoggerProvider = SdkLoggerProvider::builder()
-        .with_resource(
-            Resource::builder()
-                .with_service_name(app_cfgs.name.clone())
-                .with_attribute(KeyValue::new(
-                    "service.namespace",
-                    format!("{}", app_cfgs.namespace),
-                ))
-                .with_attribute(KeyValue::new("environment", format!("{}", app_cfgs.env)))
-                .with_attribute(KeyValue::new("library.language", "rust"))
-                .build(),
-        )
-        .with_simple_exporter(exporter)
+        .with_resource(resource::build(app_cfgs, log_cfgs, custom)?)
+        .with_log_processor(processor(log_cfgs))
         .build();
 
-    let base_fmt_layer = tracing_subscriber::fmt::layer()
-        .with_writer(std::io::stderr)
-        .event_format(
-            tracing_subscriber::fmt::format()
-                .with_thread_ids(true)
-                .with_thread_names(true)
-                .with_ansi(app_cfgs.env.is_local())
-                .with_level(true)
-                .with_target(true)
-                .with_file(true)
-                .with_line_number(true)
-                .with_source_location(true)
-                .compact(),
-        );
+    let mut layers = vec![export_layer(
+        layer::OpenTelemetryTracingBridge::new(&provider),
+        log_cfgs,
+    )];
+    layers.extend(console_layers(app_cfgs, log_cfgs, console)?);
 
-    let mut fmt_pretty: Option<Layer<_, Pretty, Format<Pretty>>> = None;
-    let mut fmt_json = None;
-    if app_cfgs.env.is_local() {
-        fmt_pretty = Some(Layer::new().pretty());
-    } else {
-        fmt_json = Some(BunyanFormattingLayer::new(
-            app_cfgs.name.clone(),
-            std::io::stdout,
-        ));
-    }
+    install_subscriber(app_cfgs, log_cfgs, layers)?;
 
-    let filters = target_filters(&app_cfgs.log_level);
-    let otel_layer = layer::OpenTelemetryTracingBridge::new(&provider).with_filter(filters.clone());
+    Ok(provider)
+}
 
-    match tracing::subscriber::set_global_default(
-        tracing_subscriber::registry()
-            .with(otel_layer)
-            .with(base_fmt_layer)
-            .with(fmt_json)
-            .with(fmt_pretty)
-            .with(filters),
-    ) {
-        Err(err) => {
-            error!(error = ?err, "failure to set tracing subscribe");
-            return Err(LoggingError::InternalError {});
-        }
-        _ => Ok(provider),
-    }
+/// Builds the processor chain writing records to stdout.
+///
+/// Records are decorated with baggage and the correlation id, and exported as they're
+/// emitted, with the optional periodic flush and flush after errors.
+pub(crate) fn processor(log_cfgs: &LoggingConfigs) -> impl LogProcessor + use<> {
+    ErrorFlushProcessor::new(
+        BaggageProcessor::new(
+            CorrelationProcessor::new(IntervalFlushProcessor::new(
+                SimpleLogProcessor::new(LogExporter::default()),
+                log_cfgs.stdout_flush_interval,
+            )),
+            log_cfgs.baggage_keys.clone(),
+        ),
+        log_cfgs.flush_on_error,
+    )
 }
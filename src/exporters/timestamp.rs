@@ -0,0 +1,121 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Event Timestamps
+//!
+//! This module sets the timestamp of exported log records, distinct from their observed
+//! timestamp. The tracing bridge only gets the observed timestamp, the time the SDK
+//! received the record, which is the wrong one for replayed or batched events that
+//! happened earlier: backends order records by their timestamp.
+//!
+//! An event can carry its own time in a dedicated field, `ts` by default, either as an
+//! RFC 3339 string or as an integer number of milliseconds since the Unix epoch. Records
+//! without it, or with a value that can't be read, get their observed timestamp.
+
+use opentelemetry::{
+    InstrumentationScope, Key,
+    logs::{AnyValue, LogRecord},
+};
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    logs::{LogProcessor, SdkLogRecord},
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+
+/// A [`LogProcessor`] that sets the timestamp of records before delegating to another
+/// processor.
+///
+/// The timestamp is read from the attribute named by `field`, and defaults to the
+/// observed timestamp. The attribute itself is left on the record.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::timestamp::TimestampProcessor;
+/// use opentelemetry::logs::{LogRecord, Logger, LoggerProvider};
+/// use opentelemetry_sdk::logs::{InMemoryLogExporter, SdkLoggerProvider, SimpleLogProcessor};
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let exporter = InMemoryLogExporter::default();
+/// let provider = SdkLoggerProvider::builder()
+///     .with_log_processor(TimestampProcessor::new(
+///         SimpleLogProcessor::new(exporter.clone()),
+///         "ts",
+///     ))
+///     .build();
+///
+/// // A replayed event that happened before it's recorded
+/// let logger = provider.logger("replay");
+/// let mut record = logger.create_log_record();
+/// record.add_attribute("ts", "2024-05-01T12:00:00Z");
+/// logger.emit(record);
+///
+/// let logs = exporter.get_emitted_logs().unwrap();
+/// let record = &logs[0].record;
+/// assert_eq!(
+///     record.timestamp(),
+///     Some(UNIX_EPOCH + Duration::from_secs(1_714_564_800))
+/// );
+/// assert!(record.observed_timestamp() > record.timestamp());
+/// ```
+#[derive(Debug)]
+pub struct TimestampProcessor<P> {
+    inner: P,
+    field: Key,
+}
+
+impl<P> TimestampProcessor<P> {
+    /// Wraps `inner`, reading the event timestamps from the attribute named `field`.
+    pub fn new(inner: P, field: impl Into<String>) -> Self {
+        TimestampProcessor {
+            inner,
+            field: Key::from(field.into()),
+        }
+    }
+}
+
+impl<P: LogProcessor> LogProcessor for TimestampProcessor<P> {
+    fn emit(&self, data: &mut SdkLogRecord, instrumentation: &InstrumentationScope) {
+        if data.timestamp().is_none() {
+            let timestamp = data
+                .attributes_iter()
+                .find(|(key, _)| *key == self.field)
+                .and_then(|(_, value)| parse(value))
+                .or_else(|| data.observed_timestamp());
+            if let Some(timestamp) = timestamp {
+                data.set_timestamp(timestamp);
+            }
+        }
+        self.inner.emit(data, instrumentation);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+/// Reads a timestamp from an RFC 3339 string or a number of milliseconds since the
+/// Unix epoch.
+fn parse(value: &AnyValue) -> Option<SystemTime> {
+    match value {
+        AnyValue::String(value) => OffsetDateTime::parse(value.as_str(), &Rfc3339)
+            .ok()
+            .map(SystemTime::from),
+        AnyValue::Int(millis) => {
+            let millis = u64::try_from(*millis).ok()?;
+            UNIX_EPOCH.checked_add(Duration::from_millis(millis))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Span Timing Summaries
+//!
+//! This module provides a layer that logs how long each span lived when it closes.
+//! It's a lightweight alternative to exporting traces for ad-hoc performance analysis:
+//! instrumenting a function with `#[tracing::instrument]` is enough to get a log line
+//! with its duration.
+//!
+//! Each summary reports the total duration of the span, the time spent inside it
+//! (`busy_ms`) and the time it existed without being entered (`idle_ms`), which is
+//! mostly time spent awaiting for instrumented async functions.
+
+use std::time::{Duration, Instant};
+use tracing::{
+    Level, Subscriber,
+    span::{Attributes, Id},
+};
+use tracing_subscriber::{Layer, layer::Context, registry::LookupSpan};
+
+/// A layer emitting a summary event when a span closes.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::timing::SpanTimingLayer;
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let subscriber = tracing_subscriber::registry().with(SpanTimingLayer::new(tracing::Level::INFO));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SpanTimingLayer {
+    level: Level,
+}
+
+impl SpanTimingLayer {
+    /// Creates a layer emitting its summaries at the given level.
+    pub fn new(level: Level) -> Self {
+        SpanTimingLayer { level }
+    }
+}
+
+/// Timings accumulated in the extensions of each span.
+struct Timings {
+    created: Instant,
+    last: Instant,
+    busy: Duration,
+    idle: Duration,
+}
+
+impl<S> Layer<S> for SpanTimingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let now = Instant::now();
+            span.extensions_mut().insert(Timings {
+                created: now,
+                last: now,
+                busy: Duration::ZERO,
+                idle: Duration::ZERO,
+            });
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id)
+            && let Some(timings) = span.extensions_mut().get_mut::<Timings>()
+        {
+            let now = Instant::now();
+            timings.idle += now - timings.last;
+            timings.last = now;
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id)
+            && let Some(timings) = span.extensions_mut().get_mut::<Timings>()
+        {
+            let now = Instant::now();
+            timings.busy += now - timings.last;
+            timings.last = now;
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(timings) = span.extensions_mut().remove::<Timings>() else {
+            return;
+        };
+
+        let now = Instant::now();
+        let name = span.name();
+        let duration_ms = millis(now - timings.created);
+        let busy_ms = millis(timings.busy);
+        let idle_ms = millis(timings.idle + (now - timings.last));
+
+        macro_rules! summary {
+            ($level:expr) => {
+                tracing::event!(
+                    $level,
+                    span.name = name,
+                    duration_ms,
+                    busy_ms,
+                    idle_ms,
+                    "span closed"
+                )
+            };
+        }
+
+        match self.level {
+            Level::TRACE => summary!(Level::TRACE),
+            Level::DEBUG => summary!(Level::DEBUG),
+            Level::INFO => summary!(Level::INFO),
+            Level::WARN => summary!(Level::WARN),
+            Level::ERROR => summary!(Level::ERROR),
+        }
+    }
+}
+
+/// Converts a duration to fractional milliseconds.
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::Capture;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[tracing::instrument]
+    async fn handle() {
+        std::thread::sleep(Duration::from_millis(10));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    /// Returns the value of the `name` field of a formatted line.
+    fn field(line: &str, name: &str) -> f64 {
+        line.split_whitespace()
+            .find_map(|field| field.strip_prefix(&format!("{name}=")))
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_else(|| panic!("no {name} in {line}"))
+    }
+
+    #[tokio::test]
+    async fn summarizes_an_instrumented_async_fn() {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(SpanTimingLayer::new(Level::INFO))
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(capture.clone()),
+            );
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        handle().await;
+
+        let output = capture.output();
+        let line = output
+            .lines()
+            .find(|line| line.contains("span closed"))
+            .unwrap();
+        assert!(line.contains("INFO"));
+        assert!(line.contains("span.name=\"handle\""));
+        let (duration, busy, idle) = (
+            field(line, "duration_ms"),
+            field(line, "busy_ms"),
+            field(line, "idle_ms"),
+        );
+        assert!(busy >= 10.0, "{line}");
+        assert!(idle >= 20.0, "{line}");
+        assert!((duration - busy - idle).abs() < 1.0, "{line}");
+    }
+}
@@ -0,0 +1,328 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Value Truncation
+//!
+//! This module provides a layer wrapper that clips over-length event values before they
+//! reach the formatting and export layers. A single oversized value, such as a
+//! serialized payload logged by mistake, can otherwise exceed the per-record size limit
+//! of a collector and get the whole batch rejected.
+//!
+//! The message and the other fields have separate limits, expressed in bytes. Clipped
+//! values end with [`TRUNCATED_MARKER`] and are always cut on a character boundary.
+//! Span fields are left untouched.
+
+use std::{any::TypeId, borrow::Cow, fmt};
+use tracing::{
+    Event, Metadata, Subscriber,
+    field::{self, DisplayValue, Field, Value, Visit},
+    span::{Attributes, Id, Record},
+    subscriber::Interest,
+};
+use tracing_subscriber::{Layer, layer::Context, registry::LookupSpan};
+
+/// Marker appended to clipped values.
+pub const TRUNCATED_MARKER: &str = "…(truncated)";
+
+/// Clips `value` to at most `max` bytes and appends [`TRUNCATED_MARKER`].
+///
+/// The value is cut on the last character boundary within the limit, so multi-byte
+/// characters are never split. Values within the limit are returned as is.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::truncate::truncate;
+///
+/// assert_eq!(truncate("short", 10), "short");
+/// assert_eq!(truncate("abcdef", 3), "abc…(truncated)");
+///
+/// // "é" takes two bytes, it's dropped rather than split
+/// assert_eq!(truncate("café", 4), "caf…(truncated)");
+/// assert_eq!(truncate("日本語", 5), "日…(truncated)");
+/// ```
+pub fn truncate(value: &str, max: usize) -> Cow<'_, str> {
+    if value.len() <= max {
+        return Cow::Borrowed(value);
+    }
+
+    let mut end = max;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    Cow::Owned(format!("{}{TRUNCATED_MARKER}", &value[..end]))
+}
+
+/// A layer delivering events to another layer with their over-length values clipped.
+///
+/// Events without an over-length value are delivered unchanged. Other events are
+/// rebuilt with the same metadata and parent, their string and debug-formatted values
+/// clipped with [`truncate`].
+#[derive(Debug)]
+pub(crate) struct TruncateLayer<L> {
+    inner: L,
+    max_message: Option<usize>,
+    max_field: Option<usize>,
+}
+
+impl<L> TruncateLayer<L> {
+    /// Wraps `inner`, clipping the message and the other field values to the given
+    /// number of bytes.
+    pub(crate) fn new(inner: L, max_message: Option<usize>, max_field: Option<usize>) -> Self {
+        TruncateLayer {
+            inner,
+            max_message,
+            max_field,
+        }
+    }
+
+    fn limit(&self, field: &Field) -> Option<usize> {
+        if field.name() == "message" {
+            self.max_message
+        } else {
+            self.max_field
+        }
+    }
+}
+
+/// An owned copy of an event value.
+pub(crate) enum OwnedValue {
+    Str(String),
+    Debug(DisplayValue<String>),
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    F64(f64),
+    Bool(bool),
+}
+
+impl OwnedValue {
+    pub(crate) fn as_value(&self) -> &dyn Value {
+        match self {
+            OwnedValue::Str(value) => value,
+            OwnedValue::Debug(value) => value,
+            OwnedValue::I64(value) => value,
+            OwnedValue::U64(value) => value,
+            OwnedValue::I128(value) => value,
+            OwnedValue::U128(value) => value,
+            OwnedValue::F64(value) => value,
+            OwnedValue::Bool(value) => value,
+        }
+    }
+}
+
+/// Collects owned copies of the values of an event, by field index.
+pub(crate) struct Values(Vec<Option<OwnedValue>>);
+
+impl Values {
+    /// Creates an empty collection for an event with `len` fields.
+    pub(crate) fn new(len: usize) -> Self {
+        Values((0..len).map(|_| None).collect())
+    }
+
+    /// Returns the collected values, `None` for the fields without one.
+    pub(crate) fn into_inner(self) -> Vec<Option<OwnedValue>> {
+        self.0
+    }
+
+    fn set(&mut self, field: &Field, value: OwnedValue) {
+        self.0[field.index()] = Some(value);
+    }
+}
+
+impl Visit for Values {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.set(field, OwnedValue::Str(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.set(
+            field,
+            OwnedValue::Debug(field::display(format!("{value:?}"))),
+        );
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.set(field, OwnedValue::I64(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.set(field, OwnedValue::U64(value));
+    }
+
+    fn record_i128(&mut self, field: &Field, value: i128) {
+        self.set(field, OwnedValue::I128(value));
+    }
+
+    fn record_u128(&mut self, field: &Field, value: u128) {
+        self.set(field, OwnedValue::U128(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.set(field, OwnedValue::F64(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.set(field, OwnedValue::Bool(value));
+    }
+}
+
+/// Collects the values of an event, clipping the over-length ones.
+struct Collector<'a, L> {
+    layer: &'a TruncateLayer<L>,
+    values: Values,
+    truncated: bool,
+}
+
+impl<L> Collector<'_, L> {
+    fn text(&mut self, field: &Field, value: &str, debug: bool) {
+        let value = match self.layer.limit(field) {
+            Some(max) => truncate(value, max),
+            None => Cow::Borrowed(value),
+        };
+        self.truncated |= matches!(value, Cow::Owned(_));
+
+        let value = value.into_owned();
+        self.values.set(
+            field,
+            if debug {
+                OwnedValue::Debug(field::display(value))
+            } else {
+                OwnedValue::Str(value)
+            },
+        );
+    }
+}
+
+impl<L> Visit for Collector<'_, L> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.text(field, value, false);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.text(field, &format!("{value:?}"), true);
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.values.record_i64(field, value);
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.values.record_u64(field, value);
+    }
+
+    fn record_i128(&mut self, field: &Field, value: i128) {
+        self.values.record_i128(field, value);
+    }
+
+    fn record_u128(&mut self, field: &Field, value: u128) {
+        self.values.record_u128(field, value);
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.values.record_f64(field, value);
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.values.record_bool(field, value);
+    }
+}
+
+impl<S, L> Layer<S> for TruncateLayer<L>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    L: Layer<S>,
+{
+    fn on_register_dispatch(&self, subscriber: &tracing::Dispatch) {
+        self.inner.on_register_dispatch(subscriber);
+    }
+
+    fn on_layer(&mut self, subscriber: &mut S) {
+        self.inner.on_layer(subscriber);
+    }
+
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        self.inner.register_callsite(metadata)
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, ctx: Context<'_, S>) -> bool {
+        self.inner.enabled(metadata, ctx)
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_new_span(attrs, id, ctx);
+    }
+
+    fn max_level_hint(&self) -> Option<tracing::level_filters::LevelFilter> {
+        self.inner.max_level_hint()
+    }
+
+    fn on_record(&self, span: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        self.inner.on_record(span, values, ctx);
+    }
+
+    fn on_follows_from(&self, span: &Id, follows: &Id, ctx: Context<'_, S>) {
+        self.inner.on_follows_from(span, follows, ctx);
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, ctx: Context<'_, S>) -> bool {
+        self.inner.event_enabled(event, ctx)
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut collector = Collector {
+            layer: self,
+            values: Values::new(metadata.fields().len()),
+            truncated: false,
+        };
+        event.record(&mut collector);
+
+        if !collector.truncated {
+            self.inner.on_event(event, ctx);
+            return;
+        }
+
+        let values = collector.values.into_inner();
+        let values: Vec<Option<&dyn Value>> = values
+            .iter()
+            .map(|value| value.as_ref().map(OwnedValue::as_value))
+            .collect();
+        let value_set = metadata.fields().value_set_all(&values);
+
+        let truncated = if event.is_root() {
+            Event::new_child_of(None, metadata, &value_set)
+        } else if let Some(parent) = event.parent() {
+            Event::new_child_of(parent.clone(), metadata, &value_set)
+        } else {
+            Event::new(metadata, &value_set)
+        };
+        self.inner.on_event(&truncated, ctx);
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_enter(id, ctx);
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_exit(id, ctx);
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        self.inner.on_close(id, ctx);
+    }
+
+    fn on_id_change(&self, old: &Id, new: &Id, ctx: Context<'_, S>) {
+        self.inner.on_id_change(old, new, ctx);
+    }
+
+    unsafe fn downcast_raw(&self, id: TypeId) -> Option<*const ()> {
+        if id == TypeId::of::<Self>() {
+            return Some(self as *const Self as *const ());
+        }
+        // SAFETY: forwarded as is to the inner layer, which upholds the same contract
+        unsafe { self.inner.downcast_raw(id) }
+    }
+}
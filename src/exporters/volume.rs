@@ -0,0 +1,146 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Log Volume
+//!
+//! This module counts the logged events by level and target, a cheap signal for teams
+//! that alert on log rates, e.g. a spike of errors, without shipping the logs
+//! themselves. It's enabled with `LOG_VOLUME_COUNTERS=true`, and the counts are read
+//! with [`crate::provider::log_volume`].
+//!
+//! The counters are fed by a layer behind the configured filters, so they count the
+//! events the outputs receive. They only grow: rates are derived by the caller from two
+//! snapshots, e.g. when exposing them as metrics.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, OnceLock, PoisonError, RwLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::{Layer, layer::Context};
+
+/// The counters of the installed logging system, if enabled.
+static INSTALLED: OnceLock<VolumeCounters> = OnceLock::new();
+
+/// The number of events logged at a level by a target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VolumeCount {
+    /// Level of the events.
+    pub level: Level,
+    /// Target of the events.
+    pub target: String,
+    /// Number of events logged so far.
+    pub count: u64,
+}
+
+/// Counters of events by level and target, shared by their clones.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::volume::VolumeCounters;
+/// use tracing::Level;
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let counters = VolumeCounters::new();
+/// let subscriber = tracing_subscriber::registry().with(counters.layer());
+///
+/// tracing::subscriber::with_default(subscriber, || {
+///     tracing::info!(target: "my_app::api", "served");
+///     tracing::info!(target: "my_app::api", "served");
+///     tracing::error!(target: "my_app::api", "failed");
+///     tracing::info!(target: "my_app::db", "connected");
+/// });
+///
+/// let count = |level, target| {
+///     counters
+///         .snapshot()
+///         .into_iter()
+///         .find(|count| count.level == level && count.target == target)
+///         .map_or(0, |count| count.count)
+/// };
+/// assert_eq!(count(Level::INFO, "my_app::api"), 2);
+/// assert_eq!(count(Level::ERROR, "my_app::api"), 1);
+/// assert_eq!(count(Level::INFO, "my_app::db"), 1);
+/// assert_eq!(count(Level::WARN, "my_app::db"), 0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct VolumeCounters {
+    counts: Arc<RwLock<HashMap<(Level, &'static str), AtomicU64>>>,
+}
+
+impl VolumeCounters {
+    /// Creates counters without any event counted.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts an event of the given level and target.
+    fn increment(&self, level: Level, target: &'static str) {
+        let counts = self.counts.read().unwrap_or_else(PoisonError::into_inner);
+        if let Some(count) = counts.get(&(level, target)) {
+            count.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        drop(counts);
+
+        self.counts
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry((level, target))
+            .or_default()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the counts, by target and from the most to the least severe level.
+    pub fn snapshot(&self) -> Vec<VolumeCount> {
+        let mut counts: Vec<VolumeCount> = self
+            .counts
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|((level, target), count)| VolumeCount {
+                level: *level,
+                target: target.to_string(),
+                count: count.load(Ordering::Relaxed),
+            })
+            .collect();
+        counts.sort_by(|a, b| a.target.cmp(&b.target).then(a.level.cmp(&b.level)));
+        counts
+    }
+
+    /// Creates a layer counting every event it receives.
+    pub fn layer(&self) -> VolumeLayer {
+        VolumeLayer(self.clone())
+    }
+}
+
+/// The layer feeding [`VolumeCounters`].
+#[derive(Debug, Clone)]
+pub struct VolumeLayer(VolumeCounters);
+
+impl<S: Subscriber> Layer<S> for VolumeLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        self.0.increment(*metadata.level(), metadata.target());
+    }
+}
+
+/// Creates the counters of the logging system being installed, with their layer.
+pub(crate) fn install() -> Option<VolumeLayer> {
+    let counters = VolumeCounters::new();
+    INSTALLED.set(counters.clone()).ok()?;
+    Some(counters.layer())
+}
+
+/// Returns the counts of the installed logging system, empty when disabled.
+pub(crate) fn snapshot() -> Vec<VolumeCount> {
+    INSTALLED
+        .get()
+        .map(VolumeCounters::snapshot)
+        .unwrap_or_default()
+}
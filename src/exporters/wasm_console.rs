@@ -0,0 +1,103 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # WASM Browser Console Log Exporter
+//!
+//! This module provides a logging exporter for `wasm32-unknown-unknown` targets, such as
+//! browser frontends or edge workers. Events are routed to the browser console through
+//! `console.log`/`console.warn`/`console.error` and spans are recorded as browser
+//! performance marks, so they show up in the developer tools timeline.
+//!
+//! Neither `std::io::stdout` nor the OTLP/tonic stack are usable in the browser, so this
+//! exporter does not set up any OpenTelemetry export bridge.
+
+use crate::{
+    config::{ExporterKind, LoggingConfigs},
+    errors::LoggingError,
+    exporters::layers::install_subscriber,
+};
+use configs::app::AppConfigs;
+use opentelemetry_sdk::logs::{LoggerProviderBuilder, SdkLoggerProvider};
+use tracing_subscriber::{Layer, fmt::format::Pretty};
+use tracing_web::{MakeWebConsoleWriter, performance_layer};
+
+/// Installs and configures the browser console log exporter.
+///
+/// This function sets up a logging system that writes formatted events to the browser
+/// console and records spans using the browser performance API. ANSI colors and
+/// timestamps are disabled since they are not reliably supported by browser consoles
+/// and `std::time` is not available on `wasm32-unknown-unknown`.
+///
+/// # Returns
+///
+/// * `Result<SdkLoggerProvider, LoggingError>` - On success, returns a default
+///   OpenTelemetry logger provider with no exporters configured. On failure,
+///   returns a `LoggingError`.
+///
+/// # Errors
+///
+/// Returns `LoggingError::InvalidFeaturesError` when not targeting `wasm32`, and
+/// `LoggingError::SubscriberInstallError` if the tracing subscriber can't be set.
+///
+/// Returns `LoggingError::AlreadyInitializedError` if logging is already installed, by
+/// [`crate::provider::install`] or the exporter of another module. Calling this
+/// function again hands back the provider it installed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use logging::exporters::wasm_console;
+///
+/// fn main() {
+///     let provider = wasm_console::install().expect("Failed to set up logging");
+///     // Now logs will be written to the browser console
+///     tracing::info!("Application started");
+/// }
+/// ```
+pub fn install() -> Result<SdkLoggerProvider, LoggingError> {
+    crate::provider::install_exclusive_provider("browser console", || {
+        install_with(&AppConfigs::new(), &LoggingConfigs::new())
+    })
+}
+
+/// Installs the browser console log exporter with the given settings.
+pub(crate) fn install_with(
+    app_cfgs: &AppConfigs,
+    log_cfgs: &LoggingConfigs,
+) -> Result<SdkLoggerProvider, LoggingError> {
+    // The browser APIs only exist on wasm32, where they would panic on first use elsewhere
+    if !cfg!(target_arch = "wasm32") {
+        return Err(ExporterKind::Wasm.unavailable());
+    }
+
+    let console_layer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .without_time()
+        .with_level(true)
+        .with_target(true)
+        .with_writer(MakeWebConsoleWriter::new());
+
+    let perf_layer = performance_layer().with_details_from_fields(Pretty::default());
+
+    install_subscriber(
+        app_cfgs,
+        log_cfgs,
+        vec![console_layer.boxed(), perf_layer.boxed()],
+    )?;
+
+    Ok(LoggerProviderBuilder::default().build())
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_unavailable_outside_of_wasm32() {
+        let err = install_with(&AppConfigs::new(), &LoggingConfigs::default()).unwrap_err();
+
+        assert!(matches!(err, LoggingError::InvalidFeaturesError(_)));
+        assert!(!crate::provider::is_installed());
+    }
+}
@@ -0,0 +1,581 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Console Writers
+//!
+//! This module provides writer wrappers used by the console layers of the exporters.
+
+use std::{
+    io::{self, ErrorKind, Write},
+    sync::{
+        Arc, Mutex, PoisonError, Weak,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// A [`MakeWriter`] that swallows broken pipe errors.
+///
+/// When the consumer of the process output (for example `head` or a log shipper)
+/// closes the pipe, writes fail with `EPIPE`. Without this guard, the error would be
+/// reported by the formatting layers on every subsequent event. Once a broken pipe is
+/// observed, the guard marks the destination as closed and silently discards all
+/// further output, while other errors are still propagated.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::writer::BrokenPipeGuard;
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let layer = tracing_subscriber::fmt::layer().with_writer(BrokenPipeGuard::new(std::io::stdout));
+/// let subscriber = tracing_subscriber::registry().with(layer);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BrokenPipeGuard<M> {
+    make_writer: M,
+    closed: Arc<AtomicBool>,
+}
+
+impl<M> BrokenPipeGuard<M> {
+    /// Wraps the given writer factory.
+    pub fn new(make_writer: M) -> Self {
+        BrokenPipeGuard {
+            make_writer,
+            closed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns `true` once a broken pipe was observed on the destination.
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Relaxed)
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for BrokenPipeGuard<M> {
+    type Writer = BrokenPipeWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        BrokenPipeWriter {
+            inner: self.make_writer.make_writer(),
+            closed: self.closed.clone(),
+        }
+    }
+}
+
+/// The writer produced by [`BrokenPipeGuard`].
+pub struct BrokenPipeWriter<W> {
+    inner: W,
+    closed: Arc<AtomicBool>,
+}
+
+impl<W: Write> BrokenPipeWriter<W> {
+    fn guard<T>(&self, result: io::Result<T>, fallback: T) -> io::Result<T> {
+        match result {
+            Err(err) if err.kind() == ErrorKind::BrokenPipe => {
+                self.closed.store(true, Ordering::Relaxed);
+                Ok(fallback)
+            }
+            result => result,
+        }
+    }
+}
+
+impl<W: Write> Write for BrokenPipeWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.closed.load(Ordering::Relaxed) {
+            return Ok(buf.len());
+        }
+
+        let result = self.inner.write(buf);
+        self.guard(result, buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.closed.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let result = self.inner.flush();
+        self.guard(result, ())
+    }
+}
+
+/// A lock serializing the writes of several [`MakeWriter`]s.
+///
+/// Each event is buffered by its writer and written at once, under the lock, when the
+/// writer is dropped. The lines of the writers sharing a lock never interleave, even
+/// when they go to different destinations that end up in the same terminal, at the cost
+/// of writing one event at a time.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::writer::WriteLock;
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let lock = WriteLock::new();
+/// let subscriber = tracing_subscriber::registry()
+///     .with(tracing_subscriber::fmt::layer().with_writer(lock.writer(std::io::stderr)))
+///     .with(tracing_subscriber::fmt::layer().json().with_writer(lock.writer(std::io::stdout)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WriteLock(Arc<Mutex<()>>);
+
+impl WriteLock {
+    /// Creates a lock not shared by any writer yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps a writer factory, so its writes are serialized by this lock.
+    pub fn writer<M>(&self, make_writer: M) -> SerializedWriter<M> {
+        SerializedWriter {
+            make_writer,
+            lock: self.clone(),
+        }
+    }
+}
+
+/// A [`MakeWriter`] whose writes are serialized by a [`WriteLock`].
+#[derive(Debug, Clone)]
+pub struct SerializedWriter<M> {
+    make_writer: M,
+    lock: WriteLock,
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for SerializedWriter<M> {
+    type Writer = SerializedEventWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        SerializedEventWriter {
+            inner: self.make_writer.make_writer(),
+            lock: self.lock.clone(),
+            buffer: Vec::new(),
+        }
+    }
+
+    fn make_writer_for(&'a self, meta: &tracing::Metadata<'_>) -> Self::Writer {
+        SerializedEventWriter {
+            inner: self.make_writer.make_writer_for(meta),
+            lock: self.lock.clone(),
+            buffer: Vec::new(),
+        }
+    }
+}
+
+/// The writer produced by [`SerializedWriter`], writing its event when dropped.
+pub struct SerializedEventWriter<W: Write> {
+    inner: W,
+    lock: WriteLock,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> Write for SerializedEventWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // Written on drop, in one go
+        Ok(())
+    }
+}
+
+impl<W: Write> Drop for SerializedEventWriter<W> {
+    fn drop(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+
+        let _guard = self.lock.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let _ = self.inner.write_all(&self.buffer);
+        let _ = self.inner.flush();
+    }
+}
+
+/// A [`MakeWriter`] gathering the writes of many events into few larger ones.
+///
+/// Events are appended to an in-memory buffer, written to the destination when it's
+/// full, every flush interval if one is set, and on [`WriteBuffer::flush`]. Busy
+/// services trade a small delay of their output for far fewer system calls. Lines that
+/// are still buffered when the process exits are lost, so the buffer must be flushed on
+/// shutdown.
+///
+/// # Examples
+///
+/// ```
+/// use logging::exporters::writer::WriteBuffer;
+/// use std::{
+///     io::{self, Write},
+///     sync::{
+///         Arc,
+///         atomic::{AtomicUsize, Ordering},
+///     },
+/// };
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// /// Counts its write calls, one system call each for a real destination.
+/// #[derive(Clone, Default)]
+/// struct Counting(Arc<AtomicUsize>);
+///
+/// impl Write for Counting {
+///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///         Ok(buf.len())
+///     }
+///
+///     fn flush(&mut self) -> io::Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// let log_events = |make_writer| {
+///     let subscriber = tracing_subscriber::registry()
+///         .with(tracing_subscriber::fmt::layer().json().with_writer(make_writer));
+///     tracing::subscriber::with_default(subscriber, || {
+///         for seq in 0..100 {
+///             tracing::info!(seq, "request served");
+///         }
+///     });
+/// };
+///
+/// let unbuffered = Counting::default();
+/// let writer = unbuffered.clone();
+/// log_events(tracing_subscriber::fmt::writer::BoxMakeWriter::new(move || writer.clone()));
+/// assert!(unbuffered.0.load(Ordering::Relaxed) >= 100);
+///
+/// let buffered = Counting::default();
+/// let writer = buffered.clone();
+/// let buffer = WriteBuffer::new(move || writer.clone(), 64 * 1024);
+/// log_events(tracing_subscriber::fmt::writer::BoxMakeWriter::new(buffer.clone()));
+/// assert_eq!(buffered.0.load(Ordering::Relaxed), 0);
+/// buffer.flush().unwrap();
+/// assert_eq!(buffered.0.load(Ordering::Relaxed), 1);
+/// ```
+#[derive(Debug)]
+pub struct WriteBuffer<M> {
+    shared: Arc<BufferShared<M>>,
+}
+
+impl<M> Clone for WriteBuffer<M> {
+    fn clone(&self) -> Self {
+        WriteBuffer {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+/// The state shared by the clones of a [`WriteBuffer`] and its flush thread.
+#[derive(Debug)]
+struct BufferShared<M> {
+    make_writer: M,
+    capacity: usize,
+    buffer: Mutex<Vec<u8>>,
+}
+
+impl<M> WriteBuffer<M>
+where
+    M: for<'a> MakeWriter<'a>,
+{
+    /// Wraps a writer factory, holding up to `capacity` bytes before writing them.
+    pub fn new(make_writer: M, capacity: usize) -> Self {
+        WriteBuffer {
+            shared: Arc::new(BufferShared {
+                make_writer,
+                capacity,
+                buffer: Mutex::new(Vec::with_capacity(capacity)),
+            }),
+        }
+    }
+
+    /// Flushes the buffer every `interval` from a background thread, which exits once
+    /// every clone of the buffer is dropped.
+    ///
+    /// The buffer is only flushed when full or on demand if the thread can't be
+    /// spawned, e.g. on WebAssembly.
+    pub fn with_flush_interval(self, interval: Duration) -> Self
+    where
+        M: Send + Sync + 'static,
+    {
+        let shared = Arc::downgrade(&self.shared);
+        let _ = thread::Builder::new()
+            .name("logging-console-flush".to_string())
+            .spawn(move || flush_every(&shared, interval));
+        self
+    }
+
+    /// Writes the buffered bytes to the destination.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the destination; the buffered bytes are dropped anyway.
+    pub fn flush(&self) -> io::Result<()> {
+        let mut buffer = self.shared.lock();
+        self.shared.drain(&mut buffer)
+    }
+
+    /// Returns a handle on the buffer that doesn't keep it alive.
+    #[cfg(any(feature = "sdk", feature = "bare"))]
+    pub(crate) fn downgrade(&self) -> WeakWriteBuffer<M> {
+        WeakWriteBuffer(Arc::downgrade(&self.shared))
+    }
+}
+
+/// A handle on a [`WriteBuffer`] that doesn't keep it alive.
+#[cfg(any(feature = "sdk", feature = "bare"))]
+#[derive(Debug)]
+pub(crate) struct WeakWriteBuffer<M>(Weak<BufferShared<M>>);
+
+#[cfg(any(feature = "sdk", feature = "bare"))]
+impl<M> WeakWriteBuffer<M> {
+    /// Returns the buffer, unless every clone of it was dropped.
+    pub(crate) fn upgrade(&self) -> Option<WriteBuffer<M>> {
+        self.0.upgrade().map(|shared| WriteBuffer { shared })
+    }
+}
+
+/// Flushes the buffer every `interval`, until it's dropped.
+fn flush_every<M>(shared: &Weak<BufferShared<M>>, interval: Duration)
+where
+    M: for<'a> MakeWriter<'a>,
+{
+    loop {
+        thread::sleep(interval);
+        let Some(shared) = shared.upgrade() else {
+            return;
+        };
+        let mut buffer = shared.lock();
+        let _ = shared.drain(&mut buffer);
+    }
+}
+
+impl<M> BufferShared<M>
+where
+    M: for<'a> MakeWriter<'a>,
+{
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<u8>> {
+        self.buffer.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Appends `buf`, writing the buffer first if it would overflow.
+    fn write(&self, buf: &[u8]) -> io::Result<()> {
+        let mut buffer = self.lock();
+        if buffer.len() + buf.len() > self.capacity {
+            self.drain(&mut buffer)?;
+        }
+        if buf.len() >= self.capacity {
+            return self.make_writer.make_writer().write_all(buf);
+        }
+        buffer.extend_from_slice(buf);
+        Ok(())
+    }
+
+    /// Writes the buffer to the destination in one go and empties it.
+    fn drain(&self, buffer: &mut Vec<u8>) -> io::Result<()> {
+        if buffer.is_empty() {
+            return Ok(());
+        }
+
+        let mut writer = self.make_writer.make_writer();
+        let result = writer.write_all(buffer).and_then(|()| writer.flush());
+        buffer.clear();
+        result
+    }
+}
+
+impl<'a, M> MakeWriter<'a> for WriteBuffer<M>
+where
+    M: for<'b> MakeWriter<'b> + 'a,
+{
+    type Writer = BufferedEventWriter<'a, M>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        BufferedEventWriter {
+            shared: &self.shared,
+        }
+    }
+}
+
+/// The writer produced by [`WriteBuffer`], appending to its buffer.
+pub struct BufferedEventWriter<'a, M> {
+    shared: &'a BufferShared<M>,
+}
+
+impl<M> Write for BufferedEventWriter<'_, M>
+where
+    M: for<'a> MakeWriter<'a>,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.shared.write(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // Flushed when full, periodically or on demand
+        Ok(())
+    }
+}
+
+/// A type-erased [`MakeWriter`] that can be shared by several layers.
+#[cfg(any(feature = "sdk", feature = "bare"))]
+#[derive(Debug, Clone)]
+pub(crate) struct SharedWriter(Arc<tracing_subscriber::fmt::writer::BoxMakeWriter>);
+
+#[cfg(any(feature = "sdk", feature = "bare"))]
+impl SharedWriter {
+    /// Wraps the given writer factory.
+    pub(crate) fn new<M>(make_writer: M) -> Self
+    where
+        M: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+    {
+        SharedWriter(Arc::new(
+            tracing_subscriber::fmt::writer::BoxMakeWriter::new(make_writer),
+        ))
+    }
+}
+
+#[cfg(any(feature = "sdk", feature = "bare"))]
+impl<'a> MakeWriter<'a> for SharedWriter {
+    type Writer = Box<dyn Write + 'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.0.make_writer()
+    }
+
+    fn make_writer_for(&'a self, meta: &tracing::Metadata<'_>) -> Self::Writer {
+        self.0.make_writer_for(meta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// A destination whose reader went away, counting the writes it was asked for.
+    #[derive(Clone, Default)]
+    struct ClosedPipe(Arc<AtomicUsize>);
+
+    impl Write for ClosedPipe {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            Err(ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Err(ErrorKind::BrokenPipe.into())
+        }
+    }
+
+    #[test]
+    fn broken_pipe_swallows_the_later_writes() {
+        let pipe = ClosedPipe::default();
+        let destination = pipe.clone();
+        let guard = BrokenPipeGuard::new(move || destination.clone());
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_subscriber::fmt::layer().with_writer(guard.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            for seq in 0..10 {
+                tracing::info!(seq, "request served");
+            }
+        });
+
+        assert!(guard.is_closed());
+        // Only the first event reached the closed destination
+        assert_eq!(pipe.0.load(Ordering::Relaxed), 1);
+    }
+
+    /// A destination failing with another error than a broken pipe.
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(ErrorKind::PermissionDenied.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn other_errors_are_still_reported() {
+        let mut writer = BrokenPipeGuard::new(|| FailingWriter).make_writer();
+
+        assert_eq!(
+            writer.write(b"line").unwrap_err().kind(),
+            ErrorKind::PermissionDenied
+        );
+    }
+
+    /// Waits up to a second for `condition` to hold.
+    fn eventually(condition: impl Fn() -> bool) -> bool {
+        let deadline = std::time::Instant::now() + Duration::from_secs(1);
+        while !condition() {
+            if std::time::Instant::now() > deadline {
+                return false;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        true
+    }
+
+    #[test]
+    fn full_buffer_is_written_before_it_overflows() {
+        let capture = crate::test::Capture::default();
+        let buffer = WriteBuffer::new(capture.clone(), 16);
+
+        buffer.make_writer().write_all(b"first line\n").unwrap();
+        assert_eq!(capture.output(), "");
+        buffer.make_writer().write_all(b"second line\n").unwrap();
+        assert_eq!(capture.output(), "first line\n");
+        // Too large to be buffered, written right after the buffered bytes
+        buffer
+            .make_writer()
+            .write_all(b"a line longer than the buffer\n")
+            .unwrap();
+        assert_eq!(
+            capture.output(),
+            "first line\nsecond line\na line longer than the buffer\n"
+        );
+    }
+
+    #[test]
+    fn buffer_is_flushed_every_interval() {
+        let capture = crate::test::Capture::default();
+        let buffer =
+            WriteBuffer::new(capture.clone(), 1024).with_flush_interval(Duration::from_millis(20));
+
+        buffer.make_writer().write_all(b"first line\n").unwrap();
+        assert_eq!(capture.output(), "");
+        assert!(eventually(|| capture.output() == "first line\n"));
+
+        buffer.make_writer().write_all(b"second line\n").unwrap();
+        assert!(eventually(
+            || capture.output() == "first line\nsecond line\n"
+        ));
+    }
+
+    #[test]
+    fn flush_thread_lets_the_buffer_go() {
+        let capture = crate::test::Capture::default();
+        let buffer =
+            WriteBuffer::new(capture.clone(), 1024).with_flush_interval(Duration::from_millis(5));
+        let shared = Arc::downgrade(&buffer.shared);
+        let clone = buffer.clone();
+
+        drop(buffer);
+        thread::sleep(Duration::from_millis(20));
+        assert!(shared.upgrade().is_some());
+        drop(clone);
+        // Only held by the flush thread while flushing
+        assert!(eventually(|| shared.strong_count() == 0));
+    }
+}
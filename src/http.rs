@@ -0,0 +1,112 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # HTTP Request Logging
+//!
+//! This module provides a pre-configured `tower_http` [`TraceLayer`] for HTTP services,
+//! such as axum routers. It creates an `http.request` span per request carrying the
+//! method, path, status and latency, and logs the completion of each request.
+//!
+//! The span is parented to the OpenTelemetry context propagated in the request headers
+//! (e.g. W3C `traceparent`), using the globally registered text map propagator, so
+//! request spans join the caller's trace when a tracing layer exports them.
+
+use http::{HeaderMap, Request, Response};
+use opentelemetry::propagation::Extractor;
+use std::time::Duration;
+use tower_http::{
+    classify::{ServerErrorsAsFailures, SharedClassifier},
+    trace::{DefaultOnRequest, MakeSpan, OnResponse, TraceLayer},
+};
+use tracing::{Span, field};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// The request tracing layer returned by [`trace_layer`].
+pub type HttpTraceLayer = TraceLayer<
+    SharedClassifier<ServerErrorsAsFailures>,
+    RequestSpan,
+    DefaultOnRequest,
+    RequestCompletion,
+>;
+
+/// Creates the request tracing layer.
+///
+/// Responses with a 5xx status and errors returned by the inner service are logged at
+/// ERROR level by `tower_http`; every completed request is logged at INFO level.
+///
+/// # Examples
+///
+/// ```ignore
+/// use axum::{Router, routing::get};
+/// use logging::http;
+///
+/// let app: Router = Router::new()
+///     .route("/health", get(|| async { "ok" }))
+///     .layer(http::trace_layer());
+/// ```
+pub fn trace_layer() -> HttpTraceLayer {
+    TraceLayer::new_for_http()
+        .make_span_with(RequestSpan)
+        .on_response(RequestCompletion)
+}
+
+/// Creates the `http.request` span of each request.
+///
+/// The `http.status` and `http.latency_ms` fields are recorded by [`RequestCompletion`]
+/// once the response is produced.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestSpan;
+
+impl<B> MakeSpan<B> for RequestSpan {
+    fn make_span(&mut self, request: &Request<B>) -> Span {
+        let span = tracing::info_span!(
+            "http.request",
+            otel.kind = "server",
+            http.method = %request.method(),
+            http.path = request.uri().path(),
+            http.status = field::Empty,
+            http.latency_ms = field::Empty,
+        );
+
+        let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.extract(&HeaderExtractor(request.headers()))
+        });
+        span.set_parent(parent);
+
+        span
+    }
+}
+
+/// Records the status and latency of each request and logs its completion.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestCompletion;
+
+impl<B> OnResponse<B> for RequestCompletion {
+    fn on_response(self, response: &Response<B>, latency: Duration, span: &Span) {
+        let status = response.status().as_u16();
+        let latency_ms = latency.as_millis() as u64;
+
+        span.record("http.status", status);
+        span.record("http.latency_ms", latency_ms);
+
+        tracing::info!(
+            http.status = status,
+            http.latency_ms = latency_ms,
+            "finished processing request"
+        );
+    }
+}
+
+/// Reads propagated context values from request headers.
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Effective Log Level
+//!
+//! This module keeps track of the default level installed by the crate, so application
+//! code can cheaply skip building expensive diagnostic payloads that would be filtered
+//! out anyway. Scoped levels and per-target overrides are not reflected: the level is
+//! the one applied to the targets without a specific filter.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use tracing::level_filters::LevelFilter;
+
+/// The installed default level, from `OFF` (0) to `TRACE` (5). `OFF` until installation.
+static CURRENT_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// Returns the default level installed by the crate.
+///
+/// Returns `LevelFilter::OFF` until the logging system is installed. The level is read
+/// with a relaxed atomic load, so it can be checked on hot paths.
+///
+/// # Examples
+///
+/// ```
+/// use logging::{
+///     config::{ExporterKind, LoggingConfig},
+///     provider,
+/// };
+/// use tracing::level_filters::LevelFilter;
+///
+/// let cfg = LoggingConfig {
+///     exporter: Some(ExporterKind::Noop),
+///     level: Some("info".to_string()),
+///     ..LoggingConfig::default()
+/// };
+/// provider::install_from_config(cfg).expect("Failed to initialize logging");
+///
+/// assert_eq!(logging::current_level(), LevelFilter::INFO);
+/// if logging::current_level() >= LevelFilter::DEBUG {
+///     // Build the expensive debug payload
+/// }
+/// ```
+pub fn current_level() -> LevelFilter {
+    match CURRENT_LEVEL.load(Ordering::Relaxed) {
+        1 => LevelFilter::ERROR,
+        2 => LevelFilter::WARN,
+        3 => LevelFilter::INFO,
+        4 => LevelFilter::DEBUG,
+        5 => LevelFilter::TRACE,
+        _ => LevelFilter::OFF,
+    }
+}
+
+/// Records the default level of the installed subscriber.
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub(crate) fn set(level: LevelFilter) {
+    CURRENT_LEVEL.store(encode(level), Ordering::Relaxed);
+}
+
+/// Encodes a level from the least (`OFF`) to the most verbose (`TRACE`).
+#[cfg(any(feature = "sdk", feature = "bare"))]
+fn encode(level: LevelFilter) -> u8 {
+    match level.into_level() {
+        None => 0,
+        Some(tracing::Level::ERROR) => 1,
+        Some(tracing::Level::WARN) => 2,
+        Some(tracing::Level::INFO) => 3,
+        Some(tracing::Level::DEBUG) => 4,
+        Some(tracing::Level::TRACE) => 5,
+    }
+}
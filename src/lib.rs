@@ -18,6 +18,24 @@
 //! - **Environment-aware formatting**: Format logs appropriately for different environments
 //! - **Targeted filtering**: Control verbosity of external dependencies
 //! - **OpenTelemetry integration**: Seamless integration with OpenTelemetry tracing
+//! - **Bare mode**: Console-only logging without OpenTelemetry via the `bare` feature
+//! - **Scoped levels**: Temporarily raise the log level of a thread with [`scoped_level`]
+//! - **Level queries**: Check the installed level with [`current_level`]
+//! - **Correlation IDs**: Attach a request correlation id to every event with
+//!   [`correlation::with_correlation_id`]
+//! - **Context propagation**: Spawn tasks that keep the span and correlation id of
+//!   their caller with `task::spawn`, behind the `tokio` feature
+//! - **Contextual loggers**: Pass around a [`Logger`] whose fields every event carries
+//! - **Error chains**: Log an error with its whole source chain as structured fields
+//!   with [`record_error!`]
+//! - **Audit events**: Emit audit events with their required fields checked at compile
+//!   time with [`audit!`]
+//! - **slog compatibility**: Forward `slog` records into the tracing pipeline with the
+//!   `TracingDrain` of the `slog-compat` feature
+//! - **Minimal filtering**: Reuse the level parsing and target filters with your own
+//!   subscriber through [`minimal`], without OpenTelemetry
+//! - **Test utilities**: Capture the events of a single test with `test::init`, behind
+//!   the `test-util` feature
 //!
 //! ## Usage
 //!
@@ -36,6 +54,33 @@
 //! }
 //! ```
 
+pub mod audit;
+#[cfg(feature = "sdk")]
+pub mod builder;
+pub mod chain;
+pub mod config;
+pub mod correlation;
 pub mod errors;
 pub mod exporters;
+#[cfg(feature = "http")]
+pub mod http;
+pub mod level;
+pub mod logger;
+pub mod minimal;
+pub mod panic;
+pub mod profile;
+#[cfg(any(feature = "sdk", feature = "bare"))]
 pub mod provider;
+pub mod scoped;
+#[cfg(feature = "slog-compat")]
+pub mod slog_compat;
+pub mod task;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test;
+
+pub use level::current_level;
+pub use logger::Logger;
+pub use scoped::{ScopedLevelGuard, scoped_level};
+
+#[doc(hidden)]
+pub use tracing as __tracing;
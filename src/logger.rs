@@ -0,0 +1,313 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Contextual Loggers
+//!
+//! This module provides [`Logger`], a handle preloaded with fields that every event
+//! logged through it carries, like the child loggers of `slog`. Tracing expresses
+//! shared context with spans, but libraries and subsystems migrating from `slog` or
+//! `log` often want an explicit object passed around instead, e.g. a logger with
+//! `component="db-pool"` handed to the pool.
+//!
+//! The events are regular tracing events: they go through the installed subscriber, its
+//! filters and every output, with the loggers' fields as event fields. Each distinct
+//! combination of target, level and field names gets its callsite registered once, and
+//! kept for the lifetime of the process.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, OnceLock, PoisonError},
+};
+use tracing::{
+    Event, Level, Metadata,
+    callsite::{self, Callsite},
+    field::{FieldSet, Value},
+    level_filters::LevelFilter,
+    metadata::Kind,
+    subscriber::Interest,
+};
+
+/// The callsites of the events logged through loggers, by target, level and fields.
+static CALLSITES: OnceLock<Mutex<HashMap<CallsiteKey, &'static Metadata<'static>>>> =
+    OnceLock::new();
+
+/// Identifies the callsite of a logger event.
+type CallsiteKey = (&'static str, Level, Vec<&'static str>);
+
+/// A handle logging events that carry a fixed set of fields.
+///
+/// Loggers are cheap to clone, and [`Logger::with`] derives a child logger carrying an
+/// additional field, so a subsystem can hand its logger down to its components.
+///
+/// # Examples
+///
+/// ```
+/// use logging::Logger;
+///
+/// let pool = Logger::new("my_service::db").with("component", "db-pool");
+/// pool.info("pool started");
+///
+/// let connection = pool.with("connection_id", 7);
+/// connection.warn("connection reset");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Logger {
+    target: &'static str,
+    fields: Arc<[(&'static str, FieldValue)]>,
+    /// The callsite of each level, from `TRACE` to `ERROR`, looked up on first use.
+    callsites: Arc<[OnceLock<&'static Metadata<'static>>; 5]>,
+}
+
+impl Logger {
+    /// Creates a logger without fields, logging events with the given target.
+    ///
+    /// The target is usually the module path of the owner, e.g. `module_path!()`, so the
+    /// target filters apply to its events like to the tracing macros of that module.
+    pub fn new(target: &'static str) -> Self {
+        Self::with_fields(target, Arc::new([]))
+    }
+
+    /// Returns a child logger carrying an additional field.
+    ///
+    /// The child keeps the fields of this logger, except one with the same name, whose
+    /// value is replaced.
+    pub fn with(&self, field: &'static str, value: impl Into<FieldValue>) -> Self {
+        let fields = self
+            .fields
+            .iter()
+            .filter(|(name, _)| *name != field)
+            .cloned()
+            .chain(std::iter::once((field, value.into())))
+            .collect();
+        Self::with_fields(self.target, fields)
+    }
+
+    /// Logs a TRACE event with the given message.
+    pub fn trace(&self, message: impl fmt::Display) {
+        self.log(Level::TRACE, message);
+    }
+
+    /// Logs a DEBUG event with the given message.
+    pub fn debug(&self, message: impl fmt::Display) {
+        self.log(Level::DEBUG, message);
+    }
+
+    /// Logs an INFO event with the given message.
+    pub fn info(&self, message: impl fmt::Display) {
+        self.log(Level::INFO, message);
+    }
+
+    /// Logs a WARN event with the given message.
+    pub fn warn(&self, message: impl fmt::Display) {
+        self.log(Level::WARN, message);
+    }
+
+    /// Logs an ERROR event with the given message.
+    pub fn error(&self, message: impl fmt::Display) {
+        self.log(Level::ERROR, message);
+    }
+
+    /// Logs an event at the given level, carrying the message and the logger fields.
+    pub fn log(&self, level: Level, message: impl fmt::Display) {
+        if level > LevelFilter::current() {
+            return;
+        }
+
+        let metadata = self.metadata(level);
+        if !tracing::dispatcher::get_default(|dispatch| dispatch.enabled(metadata)) {
+            return;
+        }
+
+        let message = format_args!("{message}");
+        let values: Vec<Option<&dyn Value>> = std::iter::once(Some(&message as &dyn Value))
+            .chain(self.fields.iter().map(|(_, value)| Some(value.as_value())))
+            .collect();
+        Event::dispatch(metadata, &metadata.fields().value_set_all(&values));
+    }
+
+    fn with_fields(target: &'static str, fields: Arc<[(&'static str, FieldValue)]>) -> Self {
+        Logger {
+            target,
+            fields,
+            callsites: Arc::new(Default::default()),
+        }
+    }
+
+    /// Returns the metadata of the events of this logger at the given level.
+    fn metadata(&self, level: Level) -> &'static Metadata<'static> {
+        let index = match level {
+            Level::TRACE => 0,
+            Level::DEBUG => 1,
+            Level::INFO => 2,
+            Level::WARN => 3,
+            Level::ERROR => 4,
+        };
+        self.callsites[index].get_or_init(|| {
+            let names = std::iter::once("message")
+                .chain(self.fields.iter().map(|(name, _)| *name))
+                .collect();
+            metadata(self.target, level, names)
+        })
+    }
+}
+
+/// Returns the metadata of the callsite identified by the given target, level and
+/// field names, registering it on first use.
+fn metadata(
+    target: &'static str,
+    level: Level,
+    names: Vec<&'static str>,
+) -> &'static Metadata<'static> {
+    let mut callsites = CALLSITES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let key = (target, level, names);
+    if let Some(metadata) = callsites.get(&key) {
+        return metadata;
+    }
+
+    // Callsites must live as long as the subscribers keeping them, so they're leaked
+    let callsite: &'static LoggerCallsite = Box::leak(Box::default());
+    let names: &'static [&'static str] = Box::leak(key.2.clone().into_boxed_slice());
+    let metadata = callsite.metadata.get_or_init(|| {
+        Metadata::new(
+            "logger event",
+            target,
+            level,
+            None,
+            None,
+            None,
+            FieldSet::new(names, callsite::Identifier(callsite)),
+            Kind::EVENT,
+        )
+    });
+    callsite::register(callsite);
+
+    callsites.insert(key, metadata);
+    metadata
+}
+
+/// The dynamically created callsite of logger events.
+#[derive(Default)]
+struct LoggerCallsite {
+    metadata: OnceLock<Metadata<'static>>,
+}
+
+impl Callsite for LoggerCallsite {
+    fn set_interest(&self, _interest: Interest) {
+        // The subscriber is asked whether each event is enabled
+    }
+
+    fn metadata(&self) -> &Metadata<'_> {
+        self.metadata
+            .get()
+            .expect("metadata set before registration")
+    }
+}
+
+/// The value of a logger field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    /// A string.
+    Str(String),
+    /// A signed integer.
+    I64(i64),
+    /// An unsigned integer.
+    U64(u64),
+    /// A floating point number.
+    F64(f64),
+    /// A boolean.
+    Bool(bool),
+}
+
+impl FieldValue {
+    fn as_value(&self) -> &dyn Value {
+        match self {
+            FieldValue::Str(value) => value,
+            FieldValue::I64(value) => value,
+            FieldValue::U64(value) => value,
+            FieldValue::F64(value) => value,
+            FieldValue::Bool(value) => value,
+        }
+    }
+}
+
+impl From<&str> for FieldValue {
+    fn from(value: &str) -> Self {
+        FieldValue::Str(value.to_string())
+    }
+}
+
+impl From<String> for FieldValue {
+    fn from(value: String) -> Self {
+        FieldValue::Str(value)
+    }
+}
+
+impl From<i32> for FieldValue {
+    fn from(value: i32) -> Self {
+        FieldValue::I64(value.into())
+    }
+}
+
+impl From<i64> for FieldValue {
+    fn from(value: i64) -> Self {
+        FieldValue::I64(value)
+    }
+}
+
+impl From<u32> for FieldValue {
+    fn from(value: u32) -> Self {
+        FieldValue::U64(value.into())
+    }
+}
+
+impl From<u64> for FieldValue {
+    fn from(value: u64) -> Self {
+        FieldValue::U64(value)
+    }
+}
+
+impl From<f64> for FieldValue {
+    fn from(value: f64) -> Self {
+        FieldValue::F64(value)
+    }
+}
+
+impl From<bool> for FieldValue {
+    fn from(value: bool) -> Self {
+        FieldValue::Bool(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::Capture;
+
+    #[test]
+    fn events_carry_the_fields_of_the_logger_and_its_parents() {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(capture.clone())
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let pool = Logger::new("my_service::db").with("component", "db-pool");
+            pool.info("pool started");
+
+            let connection = pool.with("connection_id", 7);
+            connection.warn("connection reset");
+        });
+
+        let output = capture.output();
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[0].contains("INFO my_service::db: pool started component=\"db-pool\""));
+        assert!(lines[1].contains("WARN my_service::db: connection reset"));
+        assert!(lines[1].contains("component=\"db-pool\" connection_id=7"));
+    }
+}
@@ -0,0 +1,385 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Minimal Filtering
+//!
+//! This module holds the level parsing and target filtering logic of the crate, for
+//! components that bring their own subscriber and can't afford the observability stack.
+//! It's always compiled and only depends on `tracing` and the filter types of
+//! `tracing-subscriber`: with `--no-default-features`, the crate pulls in neither
+//! OpenTelemetry nor tonic.
+//!
+//! The module isn't `no_std`, as `tracing-subscriber` filters require `std`, but it
+//! avoids everything else: no global state, no environment variables, no I/O.
+//!
+//! ```
+//! use logging::minimal::{FilterBuilder, parse_level};
+//! use tracing::Level;
+//! use tracing_subscriber::{Layer, layer::SubscriberExt};
+//!
+//! let filter = FilterBuilder::new()
+//!     .with_default(parse_level("info").unwrap())
+//!     .with_quiet_dependencies()
+//!     .with_directives("my_app::db=debug")
+//!     .unwrap()
+//!     .build();
+//! assert!(filter.would_enable("my_app::db", &Level::DEBUG));
+//! assert!(!filter.would_enable("hyper", &Level::INFO));
+//!
+//! // The filter plugs into any subscriber
+//! let subscriber =
+//!     tracing_subscriber::registry().with(tracing_subscriber::fmt::layer().with_filter(filter));
+//! tracing::subscriber::with_default(subscriber, || tracing::info!("filtered"));
+//! ```
+
+use crate::errors::LoggingError;
+use serde::Deserialize;
+use std::str::FromStr;
+use tracing_subscriber::filter::{LevelFilter, Targets};
+
+/// Dependencies whose verbosity is capped at WARN by
+/// [`FilterBuilder::with_quiet_dependencies`], the targets of every [`FilterPreset`].
+pub const QUIET_TARGETS: [&str; 12] = [
+    "lapin",
+    "tower",
+    "h2",
+    "hyper",
+    "rustls",
+    "paho_mqtt",
+    "c_trace",
+    "aws_smithy_runtime",
+    "aws_config",
+    "aws_sdk_secretsmanager",
+    "aws_runtime",
+    "opentelemetry_sdk",
+];
+
+/// A named set of dependency targets and the level they're capped at, for the kind of
+/// subsystem a service uses.
+///
+/// A service only quiets the dependencies it pulls in by picking its presets, see
+/// [`FilterBuilder::with_presets`]. Every preset together makes the
+/// [`QUIET_TARGETS`].
+///
+/// # Examples
+///
+/// ```
+/// use logging::minimal::FilterPreset;
+/// use tracing::level_filters::LevelFilter;
+///
+/// let targets = |preset: FilterPreset| -> Vec<&str> {
+///     preset.targets().iter().map(|(target, _)| *target).collect()
+/// };
+/// assert_eq!(
+///     targets(FilterPreset::Aws),
+///     ["aws_smithy_runtime", "aws_config", "aws_sdk_secretsmanager", "aws_runtime"]
+/// );
+/// assert_eq!(targets(FilterPreset::Messaging), ["lapin", "paho_mqtt", "c_trace"]);
+/// assert_eq!(targets(FilterPreset::Web), ["tower", "h2", "hyper", "rustls"]);
+/// assert_eq!(targets(FilterPreset::Telemetry), ["opentelemetry_sdk"]);
+///
+/// for preset in FilterPreset::ALL {
+///     assert!(preset.targets().iter().all(|(_, level)| *level == LevelFilter::WARN));
+/// }
+/// assert_eq!("messaging".parse::<FilterPreset>().unwrap(), FilterPreset::Messaging);
+/// assert!("kafka".parse::<FilterPreset>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterPreset {
+    /// The AWS SDK runtime and clients.
+    Aws,
+    /// The RabbitMQ and MQTT clients, and the tracing of the Paho C library.
+    Messaging,
+    /// The HTTP and gRPC stack: tower, h2, hyper and rustls.
+    Web,
+    /// The OpenTelemetry SDK.
+    Telemetry,
+}
+
+impl FilterPreset {
+    /// Every preset, in the order of [`QUIET_TARGETS`].
+    pub const ALL: [FilterPreset; 4] = [
+        FilterPreset::Messaging,
+        FilterPreset::Web,
+        FilterPreset::Aws,
+        FilterPreset::Telemetry,
+    ];
+
+    /// Returns the targets of the preset with the level they're capped at.
+    pub fn targets(self) -> &'static [(&'static str, LevelFilter)] {
+        match self {
+            FilterPreset::Aws => &[
+                ("aws_smithy_runtime", LevelFilter::WARN),
+                ("aws_config", LevelFilter::WARN),
+                ("aws_sdk_secretsmanager", LevelFilter::WARN),
+                ("aws_runtime", LevelFilter::WARN),
+            ],
+            FilterPreset::Messaging => &[
+                ("lapin", LevelFilter::WARN),
+                ("paho_mqtt", LevelFilter::WARN),
+                ("c_trace", LevelFilter::WARN),
+            ],
+            FilterPreset::Web => &[
+                ("tower", LevelFilter::WARN),
+                ("h2", LevelFilter::WARN),
+                ("hyper", LevelFilter::WARN),
+                ("rustls", LevelFilter::WARN),
+            ],
+            FilterPreset::Telemetry => &[("opentelemetry_sdk", LevelFilter::WARN)],
+        }
+    }
+}
+
+impl FromStr for FilterPreset {
+    type Err = LoggingError;
+
+    fn from_str(preset: &str) -> Result<Self, Self::Err> {
+        match preset.to_lowercase().as_str() {
+            "aws" => Ok(FilterPreset::Aws),
+            "messaging" => Ok(FilterPreset::Messaging),
+            "web" => Ok(FilterPreset::Web),
+            "telemetry" => Ok(FilterPreset::Telemetry),
+            _ => Err(LoggingError::InvalidConfigError(format!(
+                "unknown filter preset `{preset}`, expected aws, messaging, web or telemetry"
+            ))),
+        }
+    }
+}
+
+/// Parses a level name, `None` if it isn't one.
+///
+/// Names are accepted in lowercase, uppercase and title case, and `off` disables
+/// logging.
+///
+/// # Examples
+///
+/// ```
+/// use logging::minimal::parse_level;
+/// use tracing::level_filters::LevelFilter;
+///
+/// assert_eq!(parse_level("warn"), Some(LevelFilter::WARN));
+/// assert_eq!(parse_level("Debug"), Some(LevelFilter::DEBUG));
+/// assert_eq!(parse_level("OFF"), Some(LevelFilter::OFF));
+/// assert_eq!(parse_level("verbose"), None);
+/// ```
+pub fn parse_level(level: &str) -> Option<LevelFilter> {
+    match level {
+        "trace" | "Trace" | "TRACE" => Some(LevelFilter::TRACE),
+        "debug" | "Debug" | "DEBUG" => Some(LevelFilter::DEBUG),
+        "info" | "Info" | "INFO" => Some(LevelFilter::INFO),
+        "warn" | "Warn" | "WARN" => Some(LevelFilter::WARN),
+        "error" | "Error" | "ERROR" => Some(LevelFilter::ERROR),
+        "off" | "Off" | "OFF" => Some(LevelFilter::OFF),
+        _ => None,
+    }
+}
+
+/// Resolves the default level from its sources, the first one holding a level winning.
+///
+/// From the highest precedence to the lowest: the `explicit` level of the
+/// configuration, then the values of `APP_LOG_LEVEL`, `LOG_LEVEL` and `RUST_LOG`, and
+/// finally `default`. Only the default directive of `RUST_LOG` is used, its bare level,
+/// e.g. `info` in `info,hyper=warn`. Unset and empty sources are skipped, and so are
+/// values that aren't a level, so a typo falls back to the next source rather than
+/// turning logging off. The function doesn't read the environment itself.
+///
+/// # Examples
+///
+/// ```
+/// use logging::minimal::resolve_level;
+/// use tracing::level_filters::LevelFilter;
+///
+/// let default = LevelFilter::INFO;
+///
+/// // Nothing set
+/// assert_eq!(resolve_level(None, None, None, None, default), LevelFilter::INFO);
+///
+/// // Each source over the lower ones
+/// let all = |explicit| resolve_level(explicit, Some("warn"), Some("error"), Some("trace"), default);
+/// assert_eq!(all(Some("debug")), LevelFilter::DEBUG);
+/// assert_eq!(all(None), LevelFilter::WARN);
+/// assert_eq!(resolve_level(None, None, Some("error"), Some("trace"), default), LevelFilter::ERROR);
+/// assert_eq!(resolve_level(None, None, None, Some("trace"), default), LevelFilter::TRACE);
+///
+/// // Only the default directive of RUST_LOG counts
+/// assert_eq!(
+///     resolve_level(None, None, None, Some("hyper=warn,debug"), default),
+///     LevelFilter::DEBUG
+/// );
+/// assert_eq!(resolve_level(None, None, None, Some("hyper=warn"), default), LevelFilter::INFO);
+///
+/// // Empty and invalid values fall through
+/// assert_eq!(
+///     resolve_level(Some(""), Some("verbose"), Some("error"), None, default),
+///     LevelFilter::ERROR
+/// );
+/// assert_eq!(resolve_level(None, None, None, Some(""), default), LevelFilter::INFO);
+///
+/// // `off` is a level
+/// assert_eq!(resolve_level(None, Some("off"), Some("error"), None, default), LevelFilter::OFF);
+/// ```
+pub fn resolve_level(
+    explicit: Option<&str>,
+    app_log_level: Option<&str>,
+    log_level: Option<&str>,
+    rust_log: Option<&str>,
+    default: LevelFilter,
+) -> LevelFilter {
+    [explicit, app_log_level, log_level]
+        .into_iter()
+        .flatten()
+        .find_map(|level| parse_level(level.trim()))
+        .or_else(|| rust_log.and_then(default_directive))
+        .unwrap_or(default)
+}
+
+/// Returns the level of the last bare level entry of `RUST_LOG`-style directives.
+fn default_directive(directives: &str) -> Option<LevelFilter> {
+    directives
+        .rsplit(',')
+        .map(str::trim)
+        .filter(|directive| !directive.contains('='))
+        .find_map(parse_level)
+}
+
+/// Builds a [`Targets`] filter from a default level, per-target levels and directives.
+///
+/// Entries apply in order, so a later entry for a target overrides an earlier one: call
+/// [`Self::with_silenced`] last for the silenced targets to win.
+///
+/// # Examples
+///
+/// ```
+/// use logging::minimal::FilterBuilder;
+/// use tracing::{Level, level_filters::LevelFilter};
+///
+/// let filter = FilterBuilder::new()
+///     .with_default(LevelFilter::TRACE)
+///     .with_quiet_dependencies()
+///     .with_target("tower::buffer", LevelFilter::DEBUG)
+///     .with_silenced(&["leaky_dep".to_string()])
+///     .build();
+/// assert!(filter.would_enable("my_app", &Level::TRACE));
+/// assert!(!filter.would_enable("tower", &Level::INFO));
+/// assert!(filter.would_enable("tower::buffer", &Level::DEBUG));
+/// assert!(!filter.would_enable("leaky_dep::auth", &Level::ERROR));
+///
+/// // Without a default level, only the listed targets are enabled
+/// let filter = FilterBuilder::new().with_target("my_app", LevelFilter::INFO).build();
+/// assert!(!filter.would_enable("other", &Level::ERROR));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FilterBuilder {
+    targets: Targets,
+}
+
+impl FilterBuilder {
+    /// Creates a builder enabling nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the level of the targets without a specific level.
+    pub fn with_default(self, level: LevelFilter) -> Self {
+        Self {
+            targets: self.targets.with_default(level),
+        }
+    }
+
+    /// Sets the level of `target` and its submodules.
+    pub fn with_target(self, target: &str, level: LevelFilter) -> Self {
+        Self {
+            targets: self.targets.with_target(target.trim(), level),
+        }
+    }
+
+    /// Caps the [`QUIET_TARGETS`] at WARN, the targets of every [`FilterPreset`].
+    pub fn with_quiet_dependencies(self) -> Self {
+        self.with_presets(&FilterPreset::ALL)
+    }
+
+    /// Caps the targets of the given presets at their level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use logging::minimal::{FilterBuilder, FilterPreset};
+    /// use tracing::{Level, level_filters::LevelFilter};
+    ///
+    /// // A service talking to RabbitMQ over AWS, without an HTTP server
+    /// let filter = FilterBuilder::new()
+    ///     .with_default(LevelFilter::DEBUG)
+    ///     .with_presets(&[FilterPreset::Aws, FilterPreset::Messaging])
+    ///     .build();
+    /// assert!(!filter.would_enable("lapin::channel", &Level::INFO));
+    /// assert!(!filter.would_enable("aws_config", &Level::INFO));
+    /// assert!(filter.would_enable("lapin", &Level::WARN));
+    /// assert!(filter.would_enable("hyper", &Level::DEBUG));
+    /// assert!(filter.would_enable("my_app", &Level::DEBUG));
+    /// ```
+    pub fn with_presets(self, presets: &[FilterPreset]) -> Self {
+        presets
+            .iter()
+            .flat_map(|preset| preset.targets())
+            .fold(self, |builder, (target, level)| {
+                builder.with_target(target, *level)
+            })
+    }
+
+    /// Sets the given targets and their submodules to `LevelFilter::OFF`.
+    pub fn with_silenced(self, targets: &[String]) -> Self {
+        targets.iter().fold(self, |builder, target| {
+            builder.with_target(target, LevelFilter::OFF)
+        })
+    }
+
+    /// Applies comma-separated filter directives such as `info,sqlx=warn,my::mod=trace`.
+    ///
+    /// A bare level sets the default level, and `target=level` entries set the level of
+    /// a target and its submodules. Levels are those accepted by [`parse_level`].
+    /// Whitespace around the tokens and empty entries are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LoggingError::InvalidDirectiveError` naming the offending token for an
+    /// unknown level, a bare target without `=level`, or an empty target.
+    pub fn with_directives(self, directives: &str) -> Result<Self, LoggingError> {
+        directives
+            .split(',')
+            .map(str::trim)
+            .filter(|directive| !directive.is_empty())
+            .try_fold(self, |builder, directive| {
+                match directive.split_once('=') {
+                    Some((target, level)) => {
+                        let target = target.trim();
+                        if target.is_empty() {
+                            return Err(LoggingError::InvalidDirectiveError(format!(
+                                "`{directive}` has no target before `=`"
+                            )));
+                        }
+                        parse_level(level.trim())
+                            .map(|level| builder.with_target(target, level))
+                            .ok_or_else(|| {
+                                LoggingError::InvalidDirectiveError(format!(
+                                    "unknown level in `{directive}`, expected trace, debug, info, warn, error or off"
+                                ))
+                            })
+                    }
+                    None => parse_level(directive)
+                        .map(|level| builder.with_default(level))
+                        .ok_or_else(|| {
+                            LoggingError::InvalidDirectiveError(format!(
+                                "`{directive}` is neither a level nor a `target=level` entry"
+                            ))
+                        }),
+                }
+            })
+    }
+
+    /// Returns the built filter.
+    pub fn build(self) -> Targets {
+        self.targets
+    }
+}
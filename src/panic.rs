@@ -0,0 +1,76 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Panic Logging
+//!
+//! This module translates Rust panics into structured tracing events, so panics reach
+//! the log pipeline (and any configured exporter) instead of only being printed to
+//! stderr by the default panic hook.
+
+use std::{
+    backtrace::{Backtrace, BacktraceStatus},
+    panic::{self, PanicHookInfo},
+};
+use tracing::error;
+
+/// Installs a panic hook that records every panic as a structured ERROR event.
+///
+/// The event carries the following fields:
+///
+/// - `panic.message`: The panic payload, when it is a string
+/// - `panic.location`: The `file:line:column` where the panic happened
+/// - `panic.backtrace`: The captured backtrace, only when backtraces are enabled
+///   through `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE`
+///
+/// The previously installed hook is chained and runs after the event is emitted,
+/// so the default stderr output is preserved. This hook only logs; use
+/// [`crate::provider::install_panic_hook`] to also flush the logger provider.
+///
+/// # Examples
+///
+/// ```no_run
+/// use logging::{panic, provider};
+///
+/// fn main() {
+///     let _provider = provider::install().expect("Failed to initialize logging");
+///     panic::install_hook();
+///
+///     panic!("this panic becomes a structured ERROR event");
+/// }
+/// ```
+pub fn install_hook() {
+    let previous_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        record(info, Backtrace::capture());
+        previous_hook(info);
+    }));
+}
+
+/// Emits the structured ERROR event describing a panic.
+///
+/// The backtrace is only recorded when it was actually captured, so callers decide
+/// whether to honor `RUST_BACKTRACE` (`Backtrace::capture`) or to always capture it
+/// (`Backtrace::force_capture`).
+pub(crate) fn record(info: &PanicHookInfo<'_>, backtrace: Backtrace) {
+    let message = if let Some(message) = info.payload().downcast_ref::<&str>() {
+        Some(message.to_string())
+    } else {
+        info.payload().downcast_ref::<String>().cloned()
+    };
+
+    let location = info.location().map(|location| location.to_string());
+
+    let backtrace = match backtrace.status() {
+        BacktraceStatus::Captured => Some(backtrace.to_string()),
+        _ => None,
+    };
+
+    error!(
+        panic.message = message.as_deref(),
+        panic.location = location.as_deref(),
+        panic.backtrace = backtrace.as_deref(),
+        "application panicked"
+    );
+}
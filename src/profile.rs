@@ -0,0 +1,108 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Environment Profiles
+//!
+//! This module gathers the defaults that depend on the deployment environment into a
+//! single table. Each [`Profile`] supplies the console format, the log level and the
+//! exporter used when the configuration doesn't set them explicitly:
+//!
+//! | Profile       | Format | Level   | Exporter                 |
+//! |---------------|--------|---------|--------------------------|
+//! | `Local`       | pretty | `trace` | noop                     |
+//! | `Development` | JSON   | `debug` | chosen from the features |
+//! | `Staging`     | JSON   | `debug` | chosen from the features |
+//! | `Production`  | JSON   | `info`  | chosen from the features |
+//!
+//! The format and the console colors follow the [`RenderMode`] of the profile:
+//! interactive for the local profile, machine for the others.
+
+use crate::config::{ExporterKind, RenderMode, StructuredFormat};
+use configs::app::Environment;
+use tracing::level_filters::LevelFilter;
+
+/// The built-in defaults of a deployment environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// A developer machine.
+    Local,
+    /// A shared development environment.
+    Development,
+    /// A pre-production environment.
+    Staging,
+    /// The production environment.
+    Production,
+}
+
+impl Profile {
+    /// Returns the profile of the given environment.
+    ///
+    /// Local environments map to [`Profile::Local`]. Other environments are recognized
+    /// by name, and environments that are neither staging nor production use
+    /// [`Profile::Development`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use configs::app::Environment;
+    /// use logging::profile::Profile;
+    ///
+    /// assert_eq!(Profile::for_env(&Environment::Local), Profile::Local);
+    /// ```
+    pub fn for_env(env: &Environment) -> Self {
+        if env.is_local() {
+            return Profile::Local;
+        }
+
+        let name = env.to_string().to_lowercase();
+        if name.starts_with("stag") || name == "stg" {
+            Profile::Staging
+        } else if name.starts_with("prod") || name == "prd" {
+            Profile::Production
+        } else {
+            Profile::Development
+        }
+    }
+
+    /// Render mode of the console output, unless `LOG_RENDER_MODE` is set.
+    pub fn render_mode(&self) -> RenderMode {
+        match self {
+            Profile::Local => RenderMode::Interactive,
+            Profile::Development | Profile::Staging | Profile::Production => RenderMode::Machine,
+        }
+    }
+
+    /// Format of the structured console layer, unless `LOG_STRUCTURED_FORMAT` is set.
+    pub fn structured_format(&self) -> StructuredFormat {
+        self.render_mode().structured_format()
+    }
+
+    /// Whether console output is colored with ANSI escape codes.
+    pub fn ansi(&self) -> bool {
+        self.render_mode().ansi()
+    }
+
+    /// Log level applied when `AppConfigs::log_level` is empty.
+    pub fn level(&self) -> LevelFilter {
+        match self {
+            Profile::Local => LevelFilter::TRACE,
+            Profile::Development | Profile::Staging => LevelFilter::DEBUG,
+            Profile::Production => LevelFilter::INFO,
+        }
+    }
+
+    /// Exporter installed when none is configured, given the one chosen from the features.
+    ///
+    /// The local profile keeps logs on the machine: the OTLP and stdout exporters are
+    /// replaced by the noop exporter, which is announced at installation. Other
+    /// exporters, and other profiles, keep the exporter chosen from the features. An
+    /// exporter set with `LOG_EXPORTER`, or an OTLP endpoint set in the configuration,
+    /// is never replaced.
+    pub fn exporter(&self, feature_default: ExporterKind) -> ExporterKind {
+        match (self, feature_default) {
+            (Profile::Local, ExporterKind::Otlp | ExporterKind::Stdout) => ExporterKind::Noop,
+            (_, exporter) => exporter,
+        }
+    }
+}
@@ -0,0 +1,228 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Scoped Log Levels
+//!
+//! This module allows raising the log level temporarily, for the current thread only.
+//! It's meant to instrument a suspect code path with DEBUG or TRACE events while the
+//! rest of the application keeps logging at the configured level.
+//!
+//! The raised level only relaxes the target and level filtering: field filters still
+//! apply, and a scoped level can never make logging quieter than the configured one.
+//! Since the level is bound to the thread, async tasks that move between threads of a
+//! multi-threaded runtime should hold the guard only around synchronous sections.
+
+use std::{
+    cell::Cell,
+    marker::PhantomData,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use tracing::level_filters::LevelFilter;
+#[cfg(any(feature = "sdk", feature = "bare"))]
+use tracing::{
+    Event, Metadata,
+    span::{Attributes, Id, Record},
+    subscriber::Interest,
+};
+#[cfg(any(feature = "sdk", feature = "bare"))]
+use tracing_subscriber::layer::{Context, Filter};
+
+thread_local! {
+    static SCOPED_LEVEL: Cell<Option<LevelFilter>> = const { Cell::new(None) };
+}
+
+/// Set once the first scoped level is created, after which callsites disabled by the
+/// configured filters are re-evaluated on each event instead of being cached as never
+/// enabled.
+static SCOPES_IN_USE: AtomicBool = AtomicBool::new(false);
+
+/// Raises the log level of the current thread until the returned guard is dropped.
+///
+/// Guards can be nested: the effective level is the most verbose of the active ones,
+/// and dropping a guard restores the level that was in effect when it was created.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tracing::level_filters::LevelFilter;
+///
+/// fn handle_suspect_request() {
+///     let _guard = logging::scoped_level(LevelFilter::DEBUG);
+///     tracing::debug!("emitted even though the configured level is INFO");
+/// }
+/// ```
+pub fn scoped_level(level: LevelFilter) -> ScopedLevelGuard {
+    if !SCOPES_IN_USE.swap(true, Ordering::AcqRel) {
+        // Callsites cached as disabled must be re-evaluated against the scoped level
+        tracing::callsite::rebuild_interest_cache();
+    }
+
+    let previous = SCOPED_LEVEL.with(|scoped| {
+        let previous = scoped.get();
+        scoped.set(Some(previous.map_or(level, |previous| previous.max(level))));
+        previous
+    });
+
+    ScopedLevelGuard {
+        previous,
+        _thread_bound: PhantomData,
+    }
+}
+
+/// Restores the previous log level of the current thread when dropped.
+///
+/// The guard is bound to the thread that created it and cannot be sent to another.
+#[must_use = "the scoped level is restored as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct ScopedLevelGuard {
+    previous: Option<LevelFilter>,
+    _thread_bound: PhantomData<*const ()>,
+}
+
+impl Drop for ScopedLevelGuard {
+    fn drop(&mut self) {
+        SCOPED_LEVEL.with(|scoped| scoped.set(self.previous));
+    }
+}
+
+/// A per-layer filter that also enables what the scoped level of the thread allows.
+///
+/// It wraps the configured target filter. Once scoped levels are in use, callsites the
+/// wrapped filter never enables are reported as sometimes enabled, so that they are
+/// checked against the scoped level of the emitting thread.
+#[cfg(any(feature = "sdk", feature = "bare"))]
+#[derive(Debug)]
+pub(crate) struct ScopedLevelFilter<F> {
+    inner: F,
+}
+
+#[cfg(any(feature = "sdk", feature = "bare"))]
+impl<F> ScopedLevelFilter<F> {
+    pub(crate) fn new(inner: F) -> Self {
+        ScopedLevelFilter { inner }
+    }
+}
+
+#[cfg(any(feature = "sdk", feature = "bare"))]
+impl<S, F: Filter<S>> Filter<S> for ScopedLevelFilter<F> {
+    fn enabled(&self, meta: &Metadata<'_>, cx: &Context<'_, S>) -> bool {
+        self.inner.enabled(meta, cx)
+            || SCOPED_LEVEL
+                .with(Cell::get)
+                .is_some_and(|level| *meta.level() <= level)
+    }
+
+    fn callsite_enabled(&self, meta: &'static Metadata<'static>) -> Interest {
+        let interest = self.inner.callsite_enabled(meta);
+        if interest.is_never() && SCOPES_IN_USE.load(Ordering::Acquire) {
+            return Interest::sometimes();
+        }
+        interest
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        if SCOPES_IN_USE.load(Ordering::Acquire) {
+            return None;
+        }
+        self.inner.max_level_hint()
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, cx: &Context<'_, S>) -> bool {
+        self.inner.event_enabled(event, cx)
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_new_span(attrs, id, ctx)
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        self.inner.on_record(id, values, ctx)
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_enter(id, ctx)
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_exit(id, ctx)
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        self.inner.on_close(id, ctx)
+    }
+}
+
+#[cfg(all(test, any(feature = "sdk", feature = "bare")))]
+mod tests {
+    use super::*;
+    use crate::{config::LoggingConfig, test::init_with};
+
+    #[test]
+    fn raises_the_level_inside_the_guard_only() {
+        let logs = init_with(LoggingConfig {
+            level: Some("info".to_string()),
+            ..LoggingConfig::default()
+        });
+
+        tracing::debug!("before the scope");
+        {
+            let _guard = scoped_level(LevelFilter::DEBUG);
+            tracing::debug!("inside the scope");
+        }
+        tracing::debug!("after the scope");
+
+        assert!(!logs.contains("before the scope"));
+        assert!(logs.contains("inside the scope"));
+        assert!(!logs.contains("after the scope"));
+    }
+
+    #[test]
+    fn raises_the_level_of_the_current_thread_only() {
+        let logs = init_with(LoggingConfig {
+            level: Some("info".to_string()),
+            ..LoggingConfig::default()
+        });
+
+        let _guard = scoped_level(LevelFilter::DEBUG);
+        let dispatch = logs.dispatch().clone();
+        std::thread::spawn(move || {
+            tracing::dispatcher::with_default(&dispatch, || {
+                tracing::debug!("on another thread");
+            })
+        })
+        .join()
+        .unwrap();
+        tracing::debug!("on the scoped thread");
+
+        assert!(!logs.contains("on another thread"));
+        assert!(logs.contains("on the scoped thread"));
+    }
+
+    #[test]
+    fn nested_guards_restore_the_level_they_raised() {
+        let logs = init_with(LoggingConfig {
+            level: Some("info".to_string()),
+            ..LoggingConfig::default()
+        });
+
+        let outer = scoped_level(LevelFilter::DEBUG);
+        let inner = scoped_level(LevelFilter::TRACE);
+        tracing::trace!("trace in both");
+        // A quieter nested guard doesn't lower the level
+        let quieter = scoped_level(LevelFilter::WARN);
+        tracing::trace!("trace under a quieter guard");
+        drop(quieter);
+        drop(inner);
+        tracing::trace!("trace in the outer");
+        tracing::debug!("debug in the outer");
+        drop(outer);
+        tracing::debug!("debug in none");
+
+        assert!(logs.contains("trace in both"));
+        assert!(logs.contains("trace under a quieter guard"));
+        assert!(!logs.contains("trace in the outer"));
+        assert!(logs.contains("debug in the outer"));
+        assert!(!logs.contains("debug in none"));
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # `slog` Compatibility
+//!
+//! This module forwards the records of `slog` loggers into the tracing pipeline, so a
+//! codebase migrating from `slog` keeps its `slog::Logger` call sites while their
+//! records reach the console and the exporters configured by this crate. Call sites can
+//! then move to tracing, or to [`crate::Logger`], one at a time.
+//!
+//! Records become regular tracing events, with the module of the call site as target,
+//! so the target filters apply to them. Levels map onto their tracing counterpart, with
+//! `CRITICAL` logged as ERROR, and the key-value pairs of the record and of its logger
+//! become event fields. Integers, floats, booleans and strings keep their type, and
+//! other values are formatted as strings.
+//!
+//! The module is only compiled with the `slog-compat` feature.
+
+use crate::logger::{FieldValue, Logger};
+use slog::{Drain, KV, Key, Never, OwnedKVList, Record, Serializer};
+use std::fmt;
+use tracing::Level;
+
+/// A [`Drain`] logging `slog` records as tracing events.
+///
+/// # Examples
+///
+/// ```
+/// use logging::slog_compat::TracingDrain;
+///
+/// let log = slog::Logger::root(TracingDrain, slog::o!("component" => "billing"));
+/// slog::info!(log, "order charged"; "order_id" => 42, "paid" => true);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingDrain;
+
+impl Drain for TracingDrain {
+    type Ok = ();
+    type Err = Never;
+
+    fn log(&self, record: &Record<'_>, values: &OwnedKVList) -> Result<(), Never> {
+        let mut fields = Fields(Logger::new(record.module()));
+        // Best effort, the fields serialized so far are kept
+        let _ = record.kv().serialize(record, &mut fields);
+        let _ = values.serialize(record, &mut fields);
+
+        fields.0.log(level(record.level()), record.msg());
+        Ok(())
+    }
+}
+
+/// Maps a `slog` level onto the tracing level of the same severity.
+fn level(level: slog::Level) -> Level {
+    match level {
+        slog::Level::Critical | slog::Level::Error => Level::ERROR,
+        slog::Level::Warning => Level::WARN,
+        slog::Level::Info => Level::INFO,
+        slog::Level::Debug => Level::DEBUG,
+        slog::Level::Trace => Level::TRACE,
+    }
+}
+
+/// Collects the key-value pairs of a record into the fields of a logger.
+struct Fields(Logger);
+
+impl Fields {
+    fn add(&mut self, key: Key, value: impl Into<FieldValue>) -> slog::Result {
+        self.0 = self.0.with(key, value);
+        Ok(())
+    }
+}
+
+impl Serializer for Fields {
+    fn emit_arguments(&mut self, key: Key, value: &fmt::Arguments<'_>) -> slog::Result {
+        self.add(key, value.to_string())
+    }
+
+    fn emit_str(&mut self, key: Key, value: &str) -> slog::Result {
+        self.add(key, value)
+    }
+
+    fn emit_bool(&mut self, key: Key, value: bool) -> slog::Result {
+        self.add(key, value)
+    }
+
+    fn emit_i32(&mut self, key: Key, value: i32) -> slog::Result {
+        self.add(key, value)
+    }
+
+    fn emit_i64(&mut self, key: Key, value: i64) -> slog::Result {
+        self.add(key, value)
+    }
+
+    fn emit_u32(&mut self, key: Key, value: u32) -> slog::Result {
+        self.add(key, value)
+    }
+
+    fn emit_u64(&mut self, key: Key, value: u64) -> slog::Result {
+        self.add(key, value)
+    }
+
+    fn emit_usize(&mut self, key: Key, value: usize) -> slog::Result {
+        self.add(key, value as u64)
+    }
+
+    fn emit_f64(&mut self, key: Key, value: f64) -> slog::Result {
+        self.add(key, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::Capture;
+
+    #[test]
+    fn records_become_events_with_their_fields() {
+        let console = Capture::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(console.clone())
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let log = slog::Logger::root(TracingDrain, slog::o!("component" => "billing"));
+            slog::info!(log, "order charged"; "order_id" => 42, "paid" => true);
+            slog::crit!(log, "ledger unavailable");
+        });
+
+        let output = console.output();
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[0].contains("INFO"));
+        assert!(lines[0].contains("order charged"));
+        assert!(lines[0].contains("order_id=42"));
+        assert!(lines[0].contains("paid=true"));
+        assert!(lines[0].contains("component=\"billing\""));
+        assert!(lines[1].contains("ERROR"));
+        assert!(lines[1].contains("ledger unavailable"));
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Context Propagation
+//!
+//! This module carries the logging context of the caller over to spawned tasks. A task
+//! started with `tokio::spawn` begins without span, so the fields of the request that
+//! spawned it, and its correlation id, are missing from its events unless the future is
+//! instrumented by hand.
+//!
+//! [`in_current_context`] binds a future to the current span and correlation id, for
+//! any executor. With the `tokio` feature, [`spawn`] does it for `tokio::spawn`.
+
+use crate::correlation;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tracing::Span;
+
+/// Binds `future` to the current span and correlation id, which it's polled within.
+///
+/// # Examples
+///
+/// A job queued while handling a request, and run later by a worker:
+///
+/// ```no_run
+/// use logging::task::in_current_context;
+/// use std::{future::Future, pin::Pin};
+///
+/// type Job = Pin<Box<dyn Future<Output = ()> + Send>>;
+///
+/// #[tracing::instrument(skip(jobs))]
+/// fn handle(order_id: u64, jobs: &mut Vec<Job>) {
+///     jobs.push(Box::pin(in_current_context(async {
+///         tracing::info!("carries the order_id of the request span");
+///     })));
+/// }
+/// ```
+pub fn in_current_context<F: Future>(future: F) -> InContext<F> {
+    InContext {
+        span: Span::current(),
+        correlation_id: correlation::current(),
+        future,
+    }
+}
+
+/// Spawns `future` on the current Tokio runtime, in the current span and with the
+/// current correlation id.
+///
+/// Like `tokio::spawn`, it panics when called outside of a Tokio runtime.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn charge() {}
+/// logging::task::spawn(async {
+///     charge().await;
+///     tracing::info!("charged in the background");
+/// });
+/// ```
+#[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+pub fn spawn<F>(future: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(in_current_context(future))
+}
+
+/// A future polled within the span and correlation id of the context that created it,
+/// created by [`in_current_context`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct InContext<F> {
+    span: Span,
+    correlation_id: Option<Arc<str>>,
+    future: F,
+}
+
+impl<F: Future> Future for InContext<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: the inner future is never moved out of the pinned wrapper
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+
+        let _entered = this.span.enter();
+        let _restore = correlation::set_current(this.correlation_id.clone());
+        future.poll(cx)
+    }
+}
+
+#[cfg(all(test, feature = "tokio", not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::test::Capture;
+    use tracing::Instrument;
+
+    #[tokio::test]
+    async fn spawned_tasks_keep_the_span_and_correlation_id() {
+        let console = Capture::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(console.clone())
+            .finish();
+        // The tasks run on the thread of the test, with its default subscriber
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let request = async {
+            spawn(async { tracing::info!("charged in the background") })
+                .await
+                .unwrap();
+            tokio::spawn(async { tracing::info!("spawned without context") })
+                .await
+                .unwrap();
+
+            correlation::with_correlation_id("req-7", async {
+                spawn(async {
+                    assert_eq!(correlation::correlation_id().as_deref(), Some("req-7"));
+                })
+                .await
+                .unwrap();
+            })
+            .await;
+        };
+        request
+            .instrument(tracing::info_span!("request", request_id = 42))
+            .await;
+
+        let output = console.output();
+        let line = |message| output.lines().find(|line| line.contains(message)).unwrap();
+        assert!(line("charged in the background").contains("request{request_id=42}"));
+        assert!(!line("spawned without context").contains("request_id"));
+    }
+}
@@ -0,0 +1,366 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! # Test Utilities
+//!
+//! This module installs logging for the duration of a test, with the `test-util`
+//! feature. The installers of [`crate::provider`] set the global subscriber, which can
+//! only be set once per process and leaks into every other test of the binary.
+//! [`init`] sets a subscriber as the default of the current thread only, and removes it
+//! when the returned guard is dropped, so every test gets its own.
+//!
+//! The subscriber is assembled from the same filters, formats and layers as the
+//! installed one, with two differences: the console output is written, without colors,
+//! to an in-memory [`Capture`] read with [`TestGuard::output`], and the records that
+//! would be exported are kept in memory, read with [`TestGuard::exported`]. Since it's
+//! bound to the thread, async tests must run on a current-thread runtime, the default of
+//! `#[tokio::test]`, or spread it with [`TestGuard::dispatch`].
+//!
+//! ```no_run
+//! #[test]
+//! fn logs_the_login() {
+//!     let logs = logging::test::init();
+//!     tracing::info!(user_id = "123", "User logged in");
+//!     assert!(logs.contains("User logged in"));
+//! }
+//!
+//! #[test]
+//! fn logs_the_logout() {
+//!     let logs = logging::test::init();
+//!     tracing::info!(user_id = "123", "User logged out");
+//!     assert!(logs.contains("User logged out"));
+//!     // Events of the other test never show up here
+//!     assert!(!logs.contains("User logged in"));
+//! }
+//! ```
+
+#[cfg(all(test, feature = "otlp", not(target_arch = "wasm32")))]
+pub(crate) mod collector;
+
+#[cfg(any(feature = "sdk", feature = "bare"))]
+use crate::{
+    config::LoggingConfig,
+    exporters::{
+        layers::{ConsoleOptions, build_subscriber, console_layers},
+        writer::SharedWriter,
+    },
+};
+#[cfg(any(feature = "sdk", feature = "bare"))]
+use configs::app::AppConfigs;
+#[cfg(feature = "sdk")]
+use opentelemetry_sdk::Resource;
+#[cfg(any(feature = "stdout", feature = "otlp"))]
+use opentelemetry_sdk::logs::{SdkLogRecord, SdkLoggerProvider};
+#[cfg(any(feature = "sdk", feature = "bare"))]
+use std::sync::Once;
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex, PoisonError},
+};
+#[cfg(any(feature = "sdk", feature = "bare"))]
+use tracing::{Dispatch, subscriber::DefaultGuard};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Sets a capturing subscriber as the default of the current thread, logging every
+/// level with the default settings.
+///
+/// The subscriber is removed, and the previous default restored, when the returned
+/// guard is dropped.
+///
+/// # Examples
+///
+/// ```
+/// let first = logging::test::init();
+/// tracing::info!("first test");
+///
+/// let second = std::thread::spawn(|| {
+///     let second = logging::test::init();
+///     tracing::debug!("second test");
+///     second.output()
+/// })
+/// .join()
+/// .unwrap();
+///
+/// assert!(first.contains("first test"));
+/// assert!(!first.contains("second test"));
+/// assert!(second.contains("second test"));
+/// assert!(!second.contains("first test"));
+/// ```
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub fn init() -> TestGuard {
+    init_with(LoggingConfig {
+        level: Some("trace".to_string()),
+        ..LoggingConfig::default()
+    })
+}
+
+/// Sets a capturing subscriber built from `cfg` as the default of the current thread.
+///
+/// The level, the filters, the formats and the console settings of `cfg` apply as
+/// they would once installed, but the exporter and the outputs are replaced by the
+/// in-memory capture. Without a level in `cfg`, the level is resolved from the
+/// environment, as by [`crate::provider::install`].
+///
+/// # Panics
+///
+/// Panics if `cfg` is invalid, e.g. with a malformed field filter.
+///
+/// # Examples
+///
+/// ```
+/// use logging::config::{LoggingConfig, LoggingConfigs};
+///
+/// let logs = logging::test::init_with(LoggingConfig {
+///     level: Some("info".to_string()),
+///     logging: LoggingConfigs {
+///         silenced_targets: vec!["leaky_dep".to_string()],
+///         ..LoggingConfigs::default()
+///     },
+///     ..LoggingConfig::default()
+/// });
+///
+/// tracing::error!(target: "leaky_dep::auth", "token=secret");
+/// tracing::debug!("polling");
+/// tracing::info!("request served");
+///
+/// assert!(!logs.contains("token=secret"));
+/// assert!(!logs.contains("polling"));
+/// assert!(logs.contains("request served"));
+/// ```
+#[cfg(any(feature = "sdk", feature = "bare"))]
+pub fn init_with(cfg: LoggingConfig) -> TestGuard {
+    #[cfg(feature = "sdk")]
+    let guard = capture(cfg, ConsoleOptions::default(), None);
+    #[cfg(not(feature = "sdk"))]
+    let guard = capture(cfg, ConsoleOptions::default());
+    guard
+}
+
+/// Sets a capturing subscriber built from the settings of `builder` as the default of
+/// the current thread.
+///
+/// The console settings of the builder apply, so a custom format can be checked. The
+/// console destinations it doesn't replace write to the in-memory capture.
+///
+/// # Panics
+///
+/// Panics if the settings of `builder` are invalid.
+///
+/// # Examples
+///
+/// ```
+/// use logging::{builder::LoggingBuilder, config::StructuredFormat};
+///
+/// let logs = logging::test::init_from(
+///     LoggingBuilder::new().with_structured_format(StructuredFormat::Ndjson),
+/// );
+/// tracing::info!(order_id = 42, "order charged");
+///
+/// assert!(logs.contains(r#""order_id":42"#));
+/// ```
+#[cfg(feature = "sdk")]
+pub fn init_from(builder: crate::builder::LoggingBuilder) -> TestGuard {
+    let (cfg, console, resource) = builder.into_parts();
+    capture(cfg, console, resource)
+}
+
+/// Sets the capturing subscriber built from `cfg` and `console` as the default of the
+/// current thread.
+#[cfg(any(feature = "sdk", feature = "bare"))]
+#[cfg_attr(
+    not(any(feature = "stdout", feature = "otlp")),
+    allow(unused_variables)
+)]
+fn capture(
+    cfg: LoggingConfig,
+    mut console: ConsoleOptions,
+    #[cfg(feature = "sdk")] resource: Option<Resource>,
+) -> TestGuard {
+    let mut app_cfgs = AppConfigs::new();
+    if let Some(level) = &cfg.level {
+        app_cfgs.log_level = level.clone();
+    }
+    let log_cfgs = &cfg.logging;
+
+    // The `log` logger is global, but forwards to the default of the emitting thread
+    if log_cfgs.capture_log_crate {
+        static LOG_BRIDGE: Once = Once::new();
+        LOG_BRIDGE.call_once(|| {
+            let _ = tracing_log::LogTracer::init();
+        });
+    }
+
+    // Custom destinations are never colored, unless forced
+    let capture = Capture::default();
+    if console.writer.is_none() {
+        let writer = SharedWriter::new(capture.clone());
+        console.stdout_writer.get_or_insert_with(|| writer.clone());
+        console.stderr_writer.get_or_insert(writer);
+    }
+    let mut layers = Vec::new();
+
+    #[cfg(any(feature = "stdout", feature = "otlp"))]
+    let (exporter, provider) = {
+        let exporter = CaptureExporter::default();
+        let provider = SdkLoggerProvider::builder()
+            .with_resource(resource.unwrap_or_else(|| Resource::builder_empty().build()))
+            .with_log_processor(crate::exporters::baggage::BaggageProcessor::new(
+                crate::correlation::CorrelationProcessor::new(
+                    opentelemetry_sdk::logs::SimpleLogProcessor::new(exporter.clone()),
+                ),
+                log_cfgs.baggage_keys.clone(),
+            ))
+            .build();
+        layers.push(crate::exporters::layers::export_layer(
+            opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge::new(&provider),
+            log_cfgs,
+        ));
+        (exporter, provider)
+    };
+
+    layers.extend(console_layers(&app_cfgs, log_cfgs, console).expect("invalid test config"));
+    let (subscriber, _, _) =
+        build_subscriber(&app_cfgs, log_cfgs, layers).expect("invalid test config");
+    let dispatch = Dispatch::new(subscriber);
+
+    TestGuard {
+        _default: tracing::dispatcher::set_default(&dispatch),
+        dispatch,
+        capture,
+        #[cfg(any(feature = "stdout", feature = "otlp"))]
+        exporter,
+        #[cfg(any(feature = "stdout", feature = "otlp"))]
+        _provider: provider,
+    }
+}
+
+/// The guard returned by [`init`], giving access to the captured output.
+#[cfg(any(feature = "sdk", feature = "bare"))]
+#[derive(Debug)]
+#[must_use = "the subscriber is removed when the guard is dropped"]
+pub struct TestGuard {
+    _default: DefaultGuard,
+    dispatch: Dispatch,
+    capture: Capture,
+    #[cfg(any(feature = "stdout", feature = "otlp"))]
+    exporter: CaptureExporter,
+    #[cfg(any(feature = "stdout", feature = "otlp"))]
+    _provider: SdkLoggerProvider,
+}
+
+#[cfg(any(feature = "sdk", feature = "bare"))]
+impl TestGuard {
+    /// Returns the console output captured so far.
+    pub fn output(&self) -> String {
+        self.capture.output()
+    }
+
+    /// Returns `true` if the captured console output contains `text`.
+    pub fn contains(&self, text: &str) -> bool {
+        self.capture.contains(text)
+    }
+
+    /// Returns the capturing subscriber, e.g. to set it as the default of another
+    /// thread with [`tracing::dispatcher::with_default`].
+    pub fn dispatch(&self) -> &Dispatch {
+        &self.dispatch
+    }
+
+    /// Returns the records exported so far, in order.
+    #[cfg(any(feature = "stdout", feature = "otlp"))]
+    pub fn exported(&self) -> Vec<SdkLogRecord> {
+        self.exporter
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Returns the bodies of the records exported so far, in order, empty for the
+    /// records without a string body.
+    #[cfg(any(feature = "stdout", feature = "otlp"))]
+    pub fn exported_bodies(&self) -> Vec<String> {
+        self.exported()
+            .iter()
+            .map(|record| match record.body() {
+                Some(opentelemetry::logs::AnyValue::String(body)) => body.to_string(),
+                _ => String::new(),
+            })
+            .collect()
+    }
+}
+
+/// An in-memory destination, shared by its clones.
+///
+/// It's the writer of the console output of [`init`], and can be given to any layer
+/// taking a [`MakeWriter`].
+///
+/// # Examples
+///
+/// ```
+/// use logging::test::Capture;
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let capture = Capture::default();
+/// let subscriber = tracing_subscriber::registry()
+///     .with(tracing_subscriber::fmt::layer().with_writer(capture.clone()));
+///
+/// tracing::subscriber::with_default(subscriber, || tracing::info!("captured"));
+/// assert!(capture.contains("captured"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Capture(Arc<Mutex<Vec<u8>>>);
+
+impl Capture {
+    /// Returns the output written so far.
+    pub fn output(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap_or_else(PoisonError::into_inner)).into_owned()
+    }
+
+    /// Returns `true` if the output written so far contains `text`.
+    pub fn contains(&self, text: &str) -> bool {
+        self.output().contains(text)
+    }
+}
+
+impl Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Capture {
+    type Writer = Capture;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+/// An exporter keeping the records in memory.
+#[cfg(any(feature = "stdout", feature = "otlp"))]
+#[derive(Debug, Clone, Default)]
+struct CaptureExporter(Arc<Mutex<Vec<SdkLogRecord>>>);
+
+#[cfg(any(feature = "stdout", feature = "otlp"))]
+impl opentelemetry_sdk::logs::LogExporter for CaptureExporter {
+    async fn export(
+        &self,
+        batch: opentelemetry_sdk::logs::LogBatch<'_>,
+    ) -> opentelemetry_sdk::error::OTelSdkResult {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .extend(batch.iter().map(|(record, _)| record.clone()));
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! A stub OTLP collector serving the logs gRPC service on a local port, for the tests of
+//! the OTLP exporter.
+
+use opentelemetry_proto::tonic::{
+    collector::logs::v1::{
+        ExportLogsPartialSuccess, ExportLogsServiceRequest, ExportLogsServiceResponse,
+        logs_service_server::{LogsService, LogsServiceServer},
+    },
+    common::v1::any_value::Value,
+};
+use std::sync::{Arc, Mutex, PoisonError};
+use tonic::{
+    Request, Response, Status,
+    codec::CompressionEncoding,
+    metadata::MetadataMap,
+    transport::{Server, server::TcpIncoming},
+};
+
+/// A collector recording the requests it receives, running on the current Tokio
+/// runtime until the runtime is dropped.
+#[derive(Debug, Clone)]
+pub(crate) struct StubCollector {
+    endpoint: String,
+    service: Service,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Service {
+    received: Arc<Mutex<Vec<(MetadataMap, ExportLogsServiceRequest)>>>,
+    rejected: i64,
+}
+
+impl StubCollector {
+    /// Starts a collector accepting every record.
+    ///
+    /// # Panics
+    ///
+    /// Panics outside of a Tokio runtime.
+    pub(crate) fn start() -> Self {
+        Self::rejecting(0)
+    }
+
+    /// Starts a collector rejecting `rejected` records of each request in a partial
+    /// success.
+    pub(crate) fn rejecting(rejected: i64) -> Self {
+        let incoming = TcpIncoming::bind(([127, 0, 0, 1], 0).into()).unwrap();
+        let endpoint = format!("http://{}", incoming.local_addr().unwrap());
+        let service = Service {
+            rejected,
+            ..Service::default()
+        };
+
+        tokio::spawn(
+            Server::builder()
+                .add_service(
+                    LogsServiceServer::new(service.clone())
+                        .accept_compressed(CompressionEncoding::Gzip),
+                )
+                .serve_with_incoming(incoming),
+        );
+        StubCollector { endpoint, service }
+    }
+
+    /// Returns the endpoint of the collector.
+    pub(crate) fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Returns the string bodies of the records received so far, in order.
+    pub(crate) fn bodies(&self) -> Vec<String> {
+        self.received()
+            .iter()
+            .flat_map(|(_, request)| &request.resource_logs)
+            .flat_map(|resource_logs| &resource_logs.scope_logs)
+            .flat_map(|scope_logs| &scope_logs.log_records)
+            .filter_map(|record| match record.body.as_ref()?.value.as_ref()? {
+                Value::StringValue(body) => Some(body.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn received(&self) -> Vec<(MetadataMap, ExportLogsServiceRequest)> {
+        self.service
+            .received
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+#[tonic::async_trait]
+impl LogsService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportLogsServiceRequest>,
+    ) -> Result<Response<ExportLogsServiceResponse>, Status> {
+        let (metadata, _, request) = request.into_parts();
+        self.received
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((metadata, request));
+
+        Ok(Response::new(ExportLogsServiceResponse {
+            partial_success: (self.rejected > 0).then(|| ExportLogsPartialSuccess {
+                rejected_log_records: self.rejected,
+                error_message: "attribute limit exceeded".to_string(),
+            }),
+        }))
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! The failure policy of the OTLP exporter decides the global install, checked in a
+//! process of its own.
+
+#![cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+
+use logging::{
+    builder::LoggingBuilder,
+    config::{ExporterKind, FailPolicy, LoggingConfig, LoggingConfigs},
+    errors::LoggingError,
+    provider,
+    test::Capture,
+};
+
+fn config(on_exporter_failure: FailPolicy) -> LoggingConfig {
+    LoggingConfig {
+        exporter: Some(ExporterKind::Otlp),
+        level: Some("info".to_string()),
+        logging: LoggingConfigs {
+            on_exporter_failure,
+            startup_banner: false,
+            ..LoggingConfigs::default()
+        },
+        ..LoggingConfig::default()
+    }
+}
+
+/// Outside of a Tokio runtime, the OTLP exporter can't be built.
+#[test]
+fn failing_exporter_fails_or_falls_back_to_the_console() {
+    // Refuses to run by default
+    let err = provider::install_from_config(config(FailPolicy::Fail)).unwrap_err();
+    assert!(matches!(err, LoggingError::RuntimeUnavailableError(_)));
+    assert!(!provider::is_installed());
+
+    // Or degrades to the console
+    let console = Capture::default();
+    LoggingBuilder::from_config(config(FailPolicy::FallbackToConsole))
+        .with_console_writer(console.clone())
+        .install()
+        .expect("Failed to initialize logging");
+    tracing::info!("still logged");
+
+    assert!(console.contains("exporter unavailable, logging to the console only"));
+    assert!(console.contains("still logged"));
+}
@@ -0,0 +1,50 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! The heartbeat thread is started by the global install, checked in a process of its own.
+
+#![cfg(feature = "sdk")]
+
+use logging::{
+    builder::LoggingBuilder,
+    config::{ExporterKind, LoggingConfig, LoggingConfigs, RenderMode},
+    provider,
+    test::Capture,
+};
+use std::{thread, time::Duration};
+
+#[test]
+fn heartbeats_follow_the_interval_until_shutdown() {
+    let console = Capture::default();
+    LoggingBuilder::from_config(LoggingConfig {
+        exporter: Some(ExporterKind::Noop),
+        level: Some("info".to_string()),
+        logging: LoggingConfigs {
+            render_mode: Some(RenderMode::Machine),
+            heartbeat_interval: Some(Duration::from_millis(100)),
+            startup_banner: false,
+            ..LoggingConfigs::default()
+        },
+        ..LoggingConfig::default()
+    })
+    .with_console_writer(console.clone())
+    .install()
+    .expect("Failed to initialize logging");
+
+    // One heartbeat per interval, counted on the JSON records
+    let heartbeats = || {
+        console
+            .output()
+            .lines()
+            .filter(|line| line.starts_with('{') && line.contains(r#""msg":"heartbeat""#))
+            .count()
+    };
+    thread::sleep(Duration::from_millis(450));
+    let beats = heartbeats();
+    assert!((3..=5).contains(&beats), "{beats} heartbeats");
+
+    provider::shutdown(Duration::from_secs(1)).expect("Failed to shut down");
+    thread::sleep(Duration::from_millis(250));
+    assert_eq!(heartbeats(), beats);
+}
@@ -0,0 +1,31 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! The journald exporter writes to the journal of systemd hosts, checked in a process of
+//! its own.
+
+#![cfg(all(feature = "journald", target_os = "linux"))]
+
+use logging::{errors::LoggingError, exporters::journald};
+use std::path::Path;
+
+#[test]
+fn installs_only_where_the_journal_runs() {
+    if !Path::new("/run/systemd/journal").exists() {
+        eprintln!("skipped: no systemd journal on this host");
+        // Reported instead of silently logging nowhere
+        assert!(matches!(
+            journald::install(),
+            Err(LoggingError::JournaldUnavailableError(_))
+        ));
+        return;
+    }
+
+    journald::install().expect("Failed to set up logging");
+    tracing::info!(http.status = 200, "request served");
+    assert!(matches!(
+        journald::install(),
+        Err(LoggingError::AlreadyInitializedError(_))
+    ));
+}
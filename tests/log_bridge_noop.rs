@@ -0,0 +1,42 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! The `log` crate records reach the subscriber of the noop exporter, checked in a
+//! process of its own.
+
+#![cfg(feature = "noop")]
+
+use logging::{
+    builder::LoggingBuilder,
+    config::{ExporterKind, LoggingConfig, LoggingConfigs},
+    test::Capture,
+};
+
+#[test]
+fn log_records_reach_the_noop_subscriber() {
+    let console = Capture::default();
+    LoggingBuilder::from_config(LoggingConfig {
+        exporter: Some(ExporterKind::Noop),
+        level: Some("info".to_string()),
+        logging: LoggingConfigs {
+            startup_banner: false,
+            ..LoggingConfigs::default()
+        },
+        ..LoggingConfig::default()
+    })
+    .with_console_writer(console.clone())
+    .install()
+    .expect("Failed to initialize logging");
+
+    tracing_log::log::warn!(target: "legacy_dep::pool", "pool exhausted");
+
+    let line = console
+        .output()
+        .lines()
+        .find(|line| line.contains("pool exhausted"))
+        .map(String::from)
+        .expect("the log record");
+    assert!(line.contains("WARN"));
+    assert!(line.contains("legacy_dep::pool"));
+}
@@ -0,0 +1,48 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! A `log` logger set by the application is kept, and the failure to bridge the `log`
+//! crate reaches the installed subscriber, checked in a process of its own.
+
+#![cfg(feature = "noop")]
+
+use logging::{
+    builder::LoggingBuilder,
+    config::{ExporterKind, LoggingConfig, LoggingConfigs},
+    test::Capture,
+};
+use tracing_log::log::{self, Log, Metadata, Record};
+
+/// The `log` logger of the application.
+struct AppLogger;
+
+impl Log for AppLogger {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn log(&self, _record: &Record<'_>) {}
+
+    fn flush(&self) {}
+}
+
+#[test]
+fn bridge_failure_reaches_the_subscriber() {
+    log::set_logger(&AppLogger).unwrap();
+    let console = Capture::default();
+    LoggingBuilder::from_config(LoggingConfig {
+        exporter: Some(ExporterKind::Noop),
+        level: Some("info".to_string()),
+        logging: LoggingConfigs {
+            startup_banner: false,
+            ..LoggingConfigs::default()
+        },
+        ..LoggingConfig::default()
+    })
+    .with_console_writer(console.clone())
+    .install()
+    .expect("Failed to initialize logging");
+
+    assert!(console.contains("failure to initialize log bridge"));
+}
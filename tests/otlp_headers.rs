@@ -0,0 +1,94 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! The headers of `OTEL_EXPORTER_OTLP_HEADERS` and `OTEL_EXPORTER_OTLP_LOGS_HEADERS` are
+//! sent with every export, checked in a process of its own.
+
+#![cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+
+use logging::{
+    builder::LoggingBuilder,
+    config::{ExporterKind, LoggingConfig, LoggingConfigs},
+    provider,
+    test::Capture,
+};
+use opentelemetry_proto::tonic::collector::logs::v1::{
+    ExportLogsServiceRequest, ExportLogsServiceResponse,
+    logs_service_server::{LogsService, LogsServiceServer},
+};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tonic::{
+    Request, Response, Status,
+    codec::CompressionEncoding,
+    metadata::MetadataMap,
+    transport::{Server, server::TcpIncoming},
+};
+
+/// A collector recording the metadata of the requests it receives.
+#[derive(Debug, Clone, Default)]
+struct Collector(Arc<Mutex<Vec<MetadataMap>>>);
+
+#[tonic::async_trait]
+impl LogsService for Collector {
+    async fn export(
+        &self,
+        request: Request<ExportLogsServiceRequest>,
+    ) -> Result<Response<ExportLogsServiceResponse>, Status> {
+        self.0.lock().unwrap().push(request.metadata().clone());
+        Ok(Response::new(ExportLogsServiceResponse::default()))
+    }
+}
+
+#[test]
+fn headers_are_sent_with_every_export() {
+    // SAFETY: no other thread is running yet
+    unsafe {
+        std::env::set_var(
+            "OTEL_EXPORTER_OTLP_HEADERS",
+            "authorization=Bearer%20abc,x-tenant=acme",
+        );
+        std::env::set_var("OTEL_EXPORTER_OTLP_LOGS_HEADERS", "x-tenant=globex");
+    }
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let _runtime = runtime.enter();
+    let collector = Collector::default();
+    let incoming = TcpIncoming::bind(([127, 0, 0, 1], 0).into()).unwrap();
+    let endpoint = format!("http://{}", incoming.local_addr().unwrap());
+    runtime.spawn(
+        Server::builder()
+            .add_service(
+                LogsServiceServer::new(collector.clone())
+                    .accept_compressed(CompressionEncoding::Gzip),
+            )
+            .serve_with_incoming(incoming),
+    );
+
+    LoggingBuilder::from_config(LoggingConfig {
+        exporter: Some(ExporterKind::Otlp),
+        level: Some("info".to_string()),
+        otlp_endpoint: Some(endpoint),
+        otlp_timeout: Some(Duration::from_secs(5)),
+        logging: LoggingConfigs {
+            startup_banner: false,
+            ..LoggingConfigs::default()
+        },
+        ..LoggingConfig::default()
+    })
+    .with_console_writer(Capture::default())
+    .install()
+    .expect("Failed to initialize logging");
+
+    tracing::info!("order charged");
+    let report = provider::shutdown(Duration::from_secs(5)).unwrap();
+    assert_eq!(report.flushed, 1);
+
+    let received = collector.0.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].get("authorization").unwrap(), "Bearer abc");
+    assert_eq!(received[0].get("x-tenant").unwrap(), "globex");
+}
@@ -0,0 +1,55 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Each OTLP output connects to its `https` endpoint with its own TLS settings, checked
+//! in a process of its own.
+
+#![cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+
+use logging::{
+    config::{LoggingConfig, LoggingConfigs, OutputConfig, TlsConfigs},
+    errors::LoggingError,
+    provider,
+};
+
+fn output(endpoint: &str, tls: Option<TlsConfigs>) -> OutputConfig {
+    OutputConfig::Otlp {
+        level: None,
+        below: None,
+        endpoint: Some(endpoint.to_string()),
+        timeout: None,
+        compression: None,
+        tls,
+    }
+}
+
+#[test]
+fn outputs_use_their_own_tls_settings() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let _runtime = runtime.enter();
+    let missing_ca = TlsConfigs {
+        ca_certificate: Some("/nonexistent/internal-ca.pem".into()),
+        ..TlsConfigs::default()
+    };
+
+    // Only the internal collector trusts a CA of its own
+    let result = provider::install_from_config(LoggingConfig {
+        level: Some("info".to_string()),
+        outputs: vec![
+            output("https://vendor.example.com:4317", None),
+            output("https://collector.internal:4317", Some(missing_ca)),
+        ],
+        logging: LoggingConfigs {
+            startup_banner: false,
+            ..LoggingConfigs::default()
+        },
+        ..LoggingConfig::default()
+    });
+
+    let Err(LoggingError::InvalidConfigError(message)) = result else {
+        panic!("expected an invalid config error, got {result:?}");
+    };
+    assert!(message.contains("/nonexistent/internal-ca.pem"));
+    assert!(!provider::is_installed());
+}
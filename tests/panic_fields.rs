@@ -0,0 +1,39 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! The logging panic hook replaces the hook of the whole process, checked in a process
+//! of its own.
+
+use logging::test::Capture;
+use std::{
+    backtrace::{Backtrace, BacktraceStatus},
+    panic,
+};
+
+#[test]
+fn panics_become_structured_error_events() {
+    let console = Capture::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_writer(console.clone())
+        .finish();
+    let _default = tracing::subscriber::set_default(subscriber);
+
+    logging::panic::install_hook();
+    let line = line!() + 1;
+    let result = panic::catch_unwind(|| panic!("ledger {} corrupted", 7));
+
+    assert!(result.is_err());
+    let output = console.output();
+    let event = output
+        .lines()
+        .find(|line| line.contains("application panicked"))
+        .expect("the panic event");
+    assert!(event.contains("ERROR"));
+    assert!(event.contains(r#"panic.message="ledger 7 corrupted""#));
+    assert!(event.contains(&format!("panic.location=\"{}:{line}:", file!())));
+    // Only captured when RUST_BACKTRACE or RUST_LIB_BACKTRACE enables it
+    let captured = Backtrace::capture().status() == BacktraceStatus::Captured;
+    assert_eq!(output.contains("panic.backtrace="), captured);
+}
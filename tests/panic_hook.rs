@@ -0,0 +1,47 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! The flushing panic hook replaces the hook of the whole process, checked in a process
+//! of its own.
+
+#![cfg(feature = "sdk")]
+
+use logging::{provider, test::Capture};
+use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
+use opentelemetry_sdk::logs::{BatchLogProcessor, InMemoryLogExporter, SdkLoggerProvider};
+use std::panic;
+use tracing_subscriber::layer::SubscriberExt;
+
+#[test]
+fn panics_are_logged_and_flushed() {
+    // Batched, so the record is only exported by a flush
+    let exporter = InMemoryLogExporter::default();
+    let provider = SdkLoggerProvider::builder()
+        .with_log_processor(BatchLogProcessor::builder(exporter.clone()).build())
+        .build();
+    let console = Capture::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(OpenTelemetryTracingBridge::new(&provider))
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(console.clone()),
+        );
+    let _default = tracing::subscriber::set_default(subscriber);
+
+    provider::install_panic_hook(&provider);
+    let result = panic::catch_unwind(|| panic!("ledger corrupted"));
+
+    assert!(result.is_err());
+    assert!(console.contains("application panicked"));
+    assert!(console.contains("panic.message=\"ledger corrupted\""));
+    let logs = exporter.get_emitted_logs().unwrap();
+    assert_eq!(logs.len(), 1);
+    assert!(
+        logs[0]
+            .record
+            .attributes_iter()
+            .any(|(key, _)| key.as_str() == "panic.backtrace")
+    );
+}
@@ -0,0 +1,52 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! A pretty JSON terminal next to an NDJSON file output, checked in a process of its own.
+
+#![cfg(all(feature = "sdk", any(feature = "stdout", feature = "otlp")))]
+
+use logging::{
+    builder::LoggingBuilder,
+    config::{LoggingConfig, LoggingConfigs, OutputConfig, StructuredFormat},
+    test::Capture,
+};
+use std::io;
+
+#[test]
+fn terminal_records_are_indented_and_file_records_compact() {
+    let path = std::env::temp_dir().join(format!("pretty-json-{}.log", std::process::id()));
+    let terminal = Capture::default();
+    LoggingBuilder::from_config(LoggingConfig {
+        level: Some("info".to_string()),
+        outputs: vec![OutputConfig::File {
+            path: path.clone(),
+            level: None,
+            below: None,
+            format: Some(StructuredFormat::Ndjson),
+        }],
+        logging: LoggingConfigs {
+            structured_format: Some(StructuredFormat::PrettyJson),
+            startup_banner: false,
+            ..LoggingConfigs::default()
+        },
+        ..LoggingConfig::default()
+    })
+    .with_stdout_writer(terminal.clone())
+    .with_stderr_writer(io::sink)
+    .install()
+    .expect("Failed to initialize logging");
+
+    tracing::info!(user_id = "123", "User logged in");
+
+    let output = terminal.output();
+    assert!(output.starts_with("{\n  \""));
+    assert!(output.contains("\n  \"user_id\": \"123\""));
+    let record: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(record["message"], "User logged in");
+
+    let file = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(file.lines().count(), 1);
+    assert!(file.contains(r#""user_id":"123""#));
+}
@@ -0,0 +1,45 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Reconfiguring swaps the filters of the global install, checked in a process of its own.
+
+#![cfg(feature = "sdk")]
+
+use logging::{
+    builder::LoggingBuilder,
+    config::{ExporterKind, LoggingConfig, LoggingConfigs},
+    provider,
+    test::Capture,
+};
+use tracing::level_filters::LevelFilter;
+
+#[test]
+fn reconfigure_applies_the_new_level() {
+    let console = Capture::default();
+    let cfg = LoggingConfig {
+        exporter: Some(ExporterKind::Noop),
+        level: Some("info".to_string()),
+        logging: LoggingConfigs {
+            startup_banner: false,
+            ..LoggingConfigs::default()
+        },
+        ..LoggingConfig::default()
+    };
+    LoggingBuilder::from_config(cfg.clone())
+        .with_console_writer(console.clone())
+        .install()
+        .expect("Failed to initialize logging");
+    tracing::debug!("before the reload");
+
+    provider::reconfigure(LoggingConfig {
+        level: Some("debug".to_string()),
+        ..cfg
+    })
+    .expect("Failed to reconfigure logging");
+    tracing::debug!("after the reload");
+
+    assert_eq!(logging::current_level(), LevelFilter::DEBUG);
+    assert!(!console.contains("before the reload"));
+    assert!(console.contains("after the reload"));
+}
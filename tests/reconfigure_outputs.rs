@@ -0,0 +1,56 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Reconfiguring refuses to change the OTLP endpoint of the installed outputs, checked in
+//! a process of its own.
+
+#![cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+
+use logging::{
+    config::{LoggingConfig, LoggingConfigs, OutputConfig},
+    errors::LoggingError,
+    provider,
+};
+
+fn output(endpoint: &str) -> OutputConfig {
+    OutputConfig::Otlp {
+        level: None,
+        below: None,
+        endpoint: Some(endpoint.to_string()),
+        timeout: None,
+        compression: None,
+        tls: None,
+    }
+}
+
+#[test]
+fn reconfigure_keeps_the_endpoints_of_the_outputs() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let _runtime = runtime.enter();
+    let cfg = LoggingConfig {
+        level: Some("info".to_string()),
+        outputs: vec![
+            output("http://127.0.0.1:4317"),
+            output("http://127.0.0.1:4318"),
+        ],
+        logging: LoggingConfigs {
+            console_output: false,
+            startup_banner: false,
+            ..LoggingConfigs::default()
+        },
+        ..LoggingConfig::default()
+    };
+    let _provider =
+        provider::install_from_config(cfg.clone()).expect("Failed to initialize logging");
+
+    let result = provider::reconfigure(LoggingConfig {
+        otlp_endpoint: Some("http://127.0.0.1:4319".to_string()),
+        ..cfg
+    });
+
+    let Err(LoggingError::ReconfigureError(message)) = result else {
+        panic!("expected a reconfigure error, got {result:?}");
+    };
+    assert!(message.contains("outputs"));
+}
@@ -0,0 +1,59 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Shutting down gives up on an exporter hanging past the configured shutdown timeout,
+//! checked in a process of its own.
+
+#![cfg(all(feature = "otlp", not(target_arch = "wasm32")))]
+
+use logging::{
+    builder::LoggingBuilder,
+    config::{ExporterKind, LoggingConfig, LoggingConfigs},
+    provider,
+    test::Capture,
+};
+use std::{
+    net::TcpListener,
+    thread,
+    time::{Duration, Instant},
+};
+
+#[test]
+fn hanging_exporter_times_the_shutdown_out() {
+    // A collector accepting connections but never answering
+    let collector = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}", collector.local_addr().unwrap());
+    thread::spawn(move || {
+        let connections: Vec<_> = collector.incoming().flatten().collect();
+        drop(connections);
+    });
+
+    // The exporter is built on the current Tokio runtime
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let _runtime = runtime.enter();
+    LoggingBuilder::from_config(LoggingConfig {
+        exporter: Some(ExporterKind::Otlp),
+        level: Some("info".to_string()),
+        otlp_endpoint: Some(endpoint),
+        otlp_timeout: Some(Duration::from_secs(30)),
+        logging: LoggingConfigs {
+            startup_banner: false,
+            shutdown_timeout: Duration::from_millis(300),
+            ..LoggingConfigs::default()
+        },
+        ..LoggingConfig::default()
+    })
+    .with_console_writer(Capture::default())
+    .install()
+    .expect("Failed to initialize logging");
+
+    tracing::info!("order charged");
+
+    let start = Instant::now();
+    let report = provider::shutdown(provider::shutdown_timeout()).unwrap();
+    assert_eq!(provider::shutdown_timeout(), Duration::from_millis(300));
+    assert!(report.timed_out);
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(report.flushed, 0);
+}
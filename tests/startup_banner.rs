@@ -0,0 +1,30 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! The startup banner is logged by the global install, checked in a process of its own.
+
+#![cfg(feature = "sdk")]
+
+use logging::{
+    builder::LoggingBuilder,
+    config::{ExporterKind, LoggingConfig},
+    test::Capture,
+};
+
+#[test]
+fn banner_summarizes_the_installed_configuration() {
+    let console = Capture::default();
+    LoggingBuilder::from_config(LoggingConfig {
+        exporter: Some(ExporterKind::Noop),
+        level: Some("debug".to_string()),
+        ..LoggingConfig::default()
+    })
+    .with_console_writer(console.clone())
+    .install()
+    .expect("Failed to initialize logging");
+
+    assert!(console.contains("logging installed"));
+    assert!(console.contains("exporter=noop"));
+    assert!(console.contains("level=debug"));
+}
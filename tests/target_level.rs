@@ -0,0 +1,45 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Target overrides rebuild the filters of the global install, checked in a process of
+//! its own.
+
+#![cfg(feature = "sdk")]
+
+use logging::{
+    builder::LoggingBuilder,
+    config::{ExporterKind, LoggingConfig, LoggingConfigs},
+    provider,
+    test::Capture,
+};
+use tracing::level_filters::LevelFilter;
+
+#[test]
+fn target_override_applies_until_reset() {
+    let console = Capture::default();
+    LoggingBuilder::from_config(LoggingConfig {
+        exporter: Some(ExporterKind::Noop),
+        level: Some("info".to_string()),
+        logging: LoggingConfigs {
+            startup_banner: false,
+            ..LoggingConfigs::default()
+        },
+        ..LoggingConfig::default()
+    })
+    .with_console_writer(console.clone())
+    .install()
+    .expect("Failed to initialize logging");
+
+    tracing::debug!(target: "my_app::billing", "before");
+    provider::set_target_level("my_app::billing", LevelFilter::DEBUG).unwrap();
+    tracing::debug!(target: "my_app::billing::invoices", "during");
+    tracing::debug!(target: "my_app::orders", "other target");
+    provider::reset_target("my_app::billing").unwrap();
+    tracing::debug!(target: "my_app::billing", "after");
+
+    assert!(!console.contains("before"));
+    assert!(console.contains("during"));
+    assert!(!console.contains("other target"));
+    assert!(!console.contains("after"));
+}
@@ -0,0 +1,31 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! An exporter forced without its feature fails the installation with an error naming
+//! the feature, checked in a process of its own.
+
+#![cfg(feature = "sdk")]
+
+use logging::{
+    config::{ExporterKind, LoggingConfig},
+    errors::LoggingError,
+    provider,
+};
+
+#[test]
+fn unavailable_exporter_fails_the_installation() {
+    // Only available on wasm32
+    let result = provider::install_from_config(LoggingConfig {
+        exporter: Some(ExporterKind::Wasm),
+        ..LoggingConfig::default()
+    });
+
+    match result {
+        Err(LoggingError::InvalidFeaturesError(message)) => {
+            assert!(message.contains("`wasm` feature"), "{message}");
+        }
+        other => panic!("expected an unavailable exporter, got {other:?}"),
+    }
+    assert!(!provider::is_installed());
+}